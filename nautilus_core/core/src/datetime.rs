@@ -15,15 +15,18 @@
 
 use std::time::{Duration, UNIX_EPOCH};
 
+use anyhow::{bail, Result};
 use chrono::{
     prelude::{DateTime, Utc},
     SecondsFormat,
 };
 
-const MILLISECONDS_IN_SECOND: u64 = 1_000;
-const NANOSECONDS_IN_SECOND: u64 = 1_000_000_000;
-const NANOSECONDS_IN_MILLISECOND: u64 = 1_000_000;
-const NANOSECONDS_IN_MICROSECOND: u64 = 1_000;
+use crate::time::UnixNanos;
+
+pub const MILLISECONDS_IN_SECOND: u64 = 1_000;
+pub const NANOSECONDS_IN_SECOND: u64 = 1_000_000_000;
+pub const NANOSECONDS_IN_MILLISECOND: u64 = 1_000_000;
+pub const NANOSECONDS_IN_MICROSECOND: u64 = 1_000;
 
 /// Converts seconds to nanoseconds (ns).
 #[no_mangle]
@@ -81,6 +84,74 @@ pub fn unix_nanos_to_iso8601(timestamp_ns: u64) -> String {
     dt.to_rfc3339_opts(SecondsFormat::Nanos, true)
 }
 
+/// Provides calendar conversions and period alignment for [`UnixNanos`] timestamps.
+pub trait UnixNanosExt: Sized {
+    /// Returns the timestamp for the given UTC `datetime`.
+    ///
+    /// # Errors
+    ///
+    /// If `datetime` is before the UNIX epoch or beyond the range of [`UnixNanos`].
+    fn from_datetime_utc(datetime: DateTime<Utc>) -> Result<Self>;
+
+    /// Returns the timestamp as a UTC datetime.
+    fn to_datetime_utc(self) -> DateTime<Utc>;
+
+    /// Returns the timestamp as an RFC 3339 string with nanosecond precision.
+    fn to_rfc3339(self) -> String;
+
+    /// Returns the timestamp rounded down to a whole multiple of `period_ns`.
+    ///
+    /// # Panics
+    ///
+    /// If `period_ns` is zero.
+    fn floor_to_period(self, period_ns: u64) -> Self;
+
+    /// Returns the timestamp rounded up to a whole multiple of `period_ns`.
+    ///
+    /// # Panics
+    ///
+    /// If `period_ns` is zero.
+    fn ceil_to_period(self, period_ns: u64) -> Self;
+}
+
+impl UnixNanosExt for UnixNanos {
+    fn from_datetime_utc(datetime: DateTime<Utc>) -> Result<Self> {
+        let secs = datetime.timestamp();
+        if secs < 0 {
+            bail!("Invalid `DateTime` for `UnixNanos`, was before the UNIX epoch: {datetime}");
+        }
+        match (secs as u64)
+            .checked_mul(NANOSECONDS_IN_SECOND)
+            .and_then(|nanos| nanos.checked_add(u64::from(datetime.timestamp_subsec_nanos())))
+        {
+            Some(nanos) => Ok(nanos),
+            None => bail!("Invalid `DateTime` for `UnixNanos`, was out of range: {datetime}"),
+        }
+    }
+
+    fn to_datetime_utc(self) -> DateTime<Utc> {
+        DateTime::<Utc>::from(UNIX_EPOCH + Duration::from_nanos(self))
+    }
+
+    fn to_rfc3339(self) -> String {
+        unix_nanos_to_iso8601(self)
+    }
+
+    fn floor_to_period(self, period_ns: u64) -> Self {
+        assert!(period_ns > 0, "`period_ns` was zero");
+        self - self % period_ns
+    }
+
+    fn ceil_to_period(self, period_ns: u64) -> Self {
+        let floor = self.floor_to_period(period_ns);
+        if floor == self {
+            self
+        } else {
+            floor + period_ns
+        }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Tests
 ////////////////////////////////////////////////////////////////////////////////
@@ -173,4 +244,71 @@ mod tests {
         let result = nanos_to_micros(value);
         assert_eq!(result, expected);
     }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(1_650_000_000_123_456_789)]
+    #[case(u64::MAX)]
+    fn test_unix_nanos_datetime_round_trip(#[case] value: UnixNanos) {
+        let datetime = value.to_datetime_utc();
+
+        assert_eq!(UnixNanos::from_datetime_utc(datetime).unwrap(), value);
+    }
+
+    #[rstest]
+    fn test_unix_nanos_to_datetime_utc() {
+        let datetime = 1_650_000_000_123_456_789_u64.to_datetime_utc();
+
+        assert_eq!(datetime.timestamp(), 1_650_000_000);
+        assert_eq!(datetime.timestamp_subsec_nanos(), 123_456_789);
+    }
+
+    #[rstest]
+    fn test_unix_nanos_from_datetime_utc_before_epoch() {
+        let datetime = DateTime::parse_from_rfc3339("1969-12-31T23:59:59Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert!(UnixNanos::from_datetime_utc(datetime).is_err());
+    }
+
+    #[rstest]
+    fn test_unix_nanos_to_rfc3339() {
+        assert_eq!(0_u64.to_rfc3339(), "1970-01-01T00:00:00.000000000Z");
+        assert_eq!(
+            1_650_000_000_123_456_789_u64.to_rfc3339(),
+            "2022-04-15T05:20:00.123456789Z"
+        );
+    }
+
+    #[rstest]
+    #[case(0, 0, 0)]
+    #[case(60_000_000_000, 60_000_000_000, 60_000_000_000)]
+    #[case(
+        1_650_000_000_123_456_789,
+        1_650_000_000_000_000_000,
+        1_650_000_060_000_000_000
+    )]
+    #[case(119_999_999_999, 60_000_000_000, 120_000_000_000)]
+    fn test_unix_nanos_align_to_minute(
+        #[case] value: UnixNanos,
+        #[case] expected_floor: UnixNanos,
+        #[case] expected_ceil: UnixNanos,
+    ) {
+        let period_ns = 60e9 as u64;
+        let floor = value.floor_to_period(period_ns);
+
+        assert_eq!(floor, expected_floor);
+        assert_eq!(value.ceil_to_period(period_ns), expected_ceil);
+        assert_eq!(floor % period_ns, 0);
+        assert_eq!(floor.to_datetime_utc().timestamp_subsec_nanos(), 0);
+        assert_eq!(floor.to_datetime_utc().timestamp() % 60, 0);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "`period_ns` was zero")]
+    fn test_unix_nanos_floor_to_zero_period() {
+        let _ = 1_u64.floor_to_period(0);
+    }
 }
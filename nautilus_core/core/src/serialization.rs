@@ -14,8 +14,18 @@
 // -------------------------------------------------------------------------------------------------
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
-/// Represents types which are serializable for JSON and `MsgPack` specifications.
+/// Represents an error encoding or decoding CBOR bytes.
+#[derive(Debug, Error)]
+pub enum CborError {
+    #[error("CBOR encode error: {0}")]
+    Encode(#[from] ciborium::ser::Error<std::io::Error>),
+    #[error("CBOR decode error: {0}")]
+    Decode(#[from] ciborium::de::Error<std::io::Error>),
+}
+
+/// Represents types which are serializable for JSON, `MsgPack` and CBOR specifications.
 pub trait Serializable: Serialize + for<'de> Deserialize<'de> {
     /// Deserialize an object from JSON encoded bytes.
     fn from_json_bytes(data: Vec<u8>) -> Result<Self, serde_json::Error> {
@@ -36,4 +46,16 @@ pub trait Serializable: Serialize + for<'de> Deserialize<'de> {
     fn as_msgpack_bytes(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
         rmp_serde::to_vec_named(self)
     }
+
+    /// Deserialize an object from CBOR encoded bytes.
+    fn from_cbor_bytes(data: &[u8]) -> Result<Self, CborError> {
+        Ok(ciborium::de::from_reader(data)?)
+    }
+
+    /// Serialize an object to CBOR encoded bytes.
+    fn as_cbor_bytes(&self) -> Result<Vec<u8>, CborError> {
+        let mut buf = Vec::new();
+        ciborium::ser::into_writer(self, &mut buf)?;
+        Ok(buf)
+    }
 }
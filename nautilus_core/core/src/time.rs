@@ -13,7 +13,15 @@
 //  limitations under the License.
 // -------------------------------------------------------------------------------------------------
 
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{
+    fmt::{Display, Formatter},
+    ops::Add,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::datetime::{
+    NANOSECONDS_IN_MICROSECOND, NANOSECONDS_IN_MILLISECOND, NANOSECONDS_IN_SECOND,
+};
 
 /// Represents a timestamp in nanoseconds since UNIX epoch.
 pub type UnixNanos = u64;
@@ -28,6 +36,90 @@ pub fn duration_since_unix_epoch() -> Duration {
         .expect("Error calling `SystemTime::now.duration_since`")
 }
 
+/// Represents a signed duration in nanoseconds between two [`UnixNanos`] timestamps.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NanosDuration(pub i64);
+
+impl NanosDuration {
+    /// Returns the duration from `start` to `end`, which is negative if `end`
+    /// is before `start`.
+    ///
+    /// # Panics
+    ///
+    /// If the difference overflows an `i64`.
+    #[must_use]
+    pub fn between(start: UnixNanos, end: UnixNanos) -> Self {
+        let nanos = i128::from(end) - i128::from(start);
+        Self(i64::try_from(nanos).expect("`NanosDuration` overflowed"))
+    }
+
+    /// Returns the duration for the given seconds, rounded to the nearest nanosecond.
+    #[must_use]
+    pub fn from_secs(secs: f64) -> Self {
+        Self((secs * NANOSECONDS_IN_SECOND as f64).round() as i64)
+    }
+
+    /// Returns the duration for the given milliseconds.
+    ///
+    /// # Panics
+    ///
+    /// If the duration overflows an `i64`.
+    #[must_use]
+    pub fn from_millis(millis: u64) -> Self {
+        let nanos = millis
+            .checked_mul(NANOSECONDS_IN_MILLISECOND)
+            .and_then(|nanos| i64::try_from(nanos).ok())
+            .expect("`NanosDuration` overflowed");
+        Self(nanos)
+    }
+
+    /// Returns the number of nanoseconds in the duration.
+    #[must_use]
+    pub fn as_nanos(&self) -> i64 {
+        self.0
+    }
+
+    /// Returns the absolute duration.
+    #[must_use]
+    pub fn abs(&self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// Returns whether the duration is greater than zero.
+    #[must_use]
+    pub fn is_positive(&self) -> bool {
+        self.0 > 0
+    }
+}
+
+impl Add<NanosDuration> for UnixNanos {
+    type Output = UnixNanos;
+
+    fn add(self, rhs: NanosDuration) -> Self::Output {
+        self.checked_add_signed(rhs.0)
+            .expect("`UnixNanos` overflowed adding `NanosDuration`")
+    }
+}
+
+impl Display for NanosDuration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let nanos = self.0.unsigned_abs();
+        let (divisor, unit) = if nanos >= NANOSECONDS_IN_SECOND {
+            (NANOSECONDS_IN_SECOND, "s")
+        } else if nanos >= NANOSECONDS_IN_MILLISECOND {
+            (NANOSECONDS_IN_MILLISECOND, "ms")
+        } else if nanos >= NANOSECONDS_IN_MICROSECOND {
+            (NANOSECONDS_IN_MICROSECOND, "μs")
+        } else {
+            return write!(f, "{sign}{nanos}ns");
+        };
+        let value = format!("{:.3}", nanos as f64 / divisor as f64);
+        let value = value.trim_end_matches('0').trim_end_matches('.');
+        write!(f, "{sign}{value}{unit}")
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // C API
 ////////////////////////////////////////////////////////////////////////////////
@@ -86,6 +178,61 @@ mod tests {
         assert!(duration > Duration::from_secs(1_650_000_000));
     }
 
+    #[rstest]
+    #[case(0, 0)]
+    #[case(1, 0)]
+    #[case(1_650_000_000_123_456_789, 1_649_999_999_000_000_000)]
+    #[case(i64::MAX as u64, 0)]
+    fn test_nanos_duration_abs_is_symmetric(#[case] ts1: UnixNanos, #[case] ts2: UnixNanos) {
+        assert_eq!(
+            NanosDuration::between(ts1, ts2).abs(),
+            NanosDuration::between(ts2, ts1)
+        );
+    }
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(0, 1)]
+    #[case(1, 0)]
+    #[case(1_650_000_000_123_456_789, 1_649_999_999_000_000_000)]
+    #[case(1_649_999_999_000_000_000, 1_650_000_000_123_456_789)]
+    #[case(u64::MAX, u64::MAX - i64::MAX as u64)]
+    fn test_nanos_duration_add_round_trip(#[case] start: UnixNanos, #[case] end: UnixNanos) {
+        let duration = NanosDuration::between(start, end);
+
+        assert_eq!(start + duration, end);
+        assert_eq!(duration.is_positive(), end > start);
+    }
+
+    #[rstest]
+    fn test_nanos_duration_conversions() {
+        assert_eq!(
+            NanosDuration::from_secs(1.234),
+            NanosDuration(1_234_000_000)
+        );
+        assert_eq!(NanosDuration::from_secs(-0.5), NanosDuration(-500_000_000));
+        assert_eq!(NanosDuration::from_millis(150), NanosDuration(150_000_000));
+        assert_eq!(NanosDuration::from_millis(150).as_nanos(), 150_000_000);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "`UnixNanos` overflowed adding `NanosDuration`")]
+    fn test_nanos_duration_add_underflow() {
+        let _ = 1_u64 + NanosDuration(-2);
+    }
+
+    #[rstest]
+    #[case(NanosDuration(0), "0ns")]
+    #[case(NanosDuration(999), "999ns")]
+    #[case(NanosDuration(150_000), "150μs")]
+    #[case(NanosDuration(1_500_000), "1.5ms")]
+    #[case(NanosDuration(1_234_000_000), "1.234s")]
+    #[case(NanosDuration(-1_234_000_000), "-1.234s")]
+    #[case(NanosDuration(60_000_000_000), "60s")]
+    fn test_nanos_duration_display(#[case] duration: NanosDuration, #[case] expected: &str) {
+        assert_eq!(duration.to_string(), expected);
+    }
+
     #[rstest]
     fn test_unix_timestamp_is_monotonic_increasing() {
         let result1 = unix_timestamp();
@@ -0,0 +1,63 @@
+use std::str::FromStr;
+
+use criterion::{black_box, criterion_group, Criterion, Throughput};
+use nautilus_model::{
+    data::{
+        bar::{Bar, BarType},
+        bar_fixed::BAR_FIXED_SIZE,
+    },
+    types::{price::Price, quantity::Quantity},
+};
+
+const NUM_BARS: usize = 1_000_000;
+
+fn create_bars() -> Vec<Bar> {
+    let bar_type = BarType::from_str("AUDUSD.SIM-1-MINUTE-BID-EXTERNAL").unwrap();
+    (0..NUM_BARS)
+        .map(|i| {
+            let price = Price::from_raw(1_000_000_000 + (i % 100) as i64, 5);
+            Bar::new(
+                bar_type,
+                price,
+                price,
+                price,
+                price,
+                Quantity::from_raw(100_000_000_000_000, 0),
+                i as u64,
+                i as u64,
+            )
+        })
+        .collect()
+}
+
+pub fn criterion_bar_fixed_benchmark(c: &mut Criterion) {
+    let bars = create_bars();
+    let mut buffer = vec![[0_u8; BAR_FIXED_SIZE]; NUM_BARS];
+
+    // Reported throughput is in bars per second, targeting 100M/s for encoding
+    let mut group = c.benchmark_group("bar_fixed");
+    group.throughput(Throughput::Elements(NUM_BARS as u64));
+
+    group.bench_function("encode", |b| {
+        b.iter(|| {
+            for (slot, bar) in buffer.iter_mut().zip(black_box(&bars)) {
+                *slot = bar.to_fixed_bytes();
+            }
+        })
+    });
+
+    let bar_type = bars[0].bar_type;
+    group.bench_function("decode", |b| {
+        b.iter(|| {
+            black_box(&buffer)
+                .iter()
+                .map(|data| Bar::from_fixed_bytes(*data, bar_type).unwrap().close.raw)
+                .sum::<i64>()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_bar_fixed_benchmark);
+criterion::criterion_main!(benches);
@@ -0,0 +1,50 @@
+use std::{collections::HashMap, str::FromStr};
+
+use criterion::{black_box, criterion_group, Criterion};
+use nautilus_model::data::bar::{BarType, BarTypeFxHashMap};
+
+const NUM_BAR_TYPES: usize = 100;
+const NUM_LOOKUPS: usize = 1_000_000;
+
+fn create_bar_types() -> Vec<BarType> {
+    (0..NUM_BAR_TYPES)
+        .map(|i| BarType::from_str(&format!("SYM{i}.SIM-1-MINUTE-LAST-EXTERNAL")).unwrap())
+        .collect()
+}
+
+pub fn criterion_bar_type_hash_benchmark(c: &mut Criterion) {
+    let bar_types = create_bar_types();
+    let default_map: HashMap<BarType, u32> = bar_types
+        .iter()
+        .enumerate()
+        .map(|(i, bar_type)| (*bar_type, i as u32))
+        .collect();
+    let fx_map: BarTypeFxHashMap<u32> = bar_types
+        .iter()
+        .enumerate()
+        .map(|(i, bar_type)| (*bar_type, i as u32))
+        .collect();
+
+    c.bench_function("bar_type_hash_map_default_hasher", |b| {
+        b.iter(|| {
+            let mut sum = 0u32;
+            for i in 0..NUM_LOOKUPS {
+                sum = sum.wrapping_add(default_map[black_box(&bar_types[i % NUM_BAR_TYPES])]);
+            }
+            sum
+        })
+    });
+
+    c.bench_function("bar_type_hash_map_fx_hasher", |b| {
+        b.iter(|| {
+            let mut sum = 0u32;
+            for i in 0..NUM_LOOKUPS {
+                sum = sum.wrapping_add(fx_map[black_box(&bar_types[i % NUM_BAR_TYPES])]);
+            }
+            sum
+        })
+    });
+}
+
+criterion_group!(benches, criterion_bar_type_hash_benchmark);
+criterion::criterion_main!(benches);
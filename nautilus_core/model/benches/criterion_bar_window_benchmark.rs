@@ -0,0 +1,59 @@
+use std::str::FromStr;
+
+use criterion::{black_box, criterion_group, Criterion};
+use nautilus_model::{
+    data::{
+        bar::{Bar, BarType},
+        bar_window::BarWindow,
+    },
+    types::{price::Price, quantity::Quantity},
+};
+
+const NUM_BARS: usize = 1_000_000;
+const WINDOW: usize = 20;
+
+fn create_bars() -> Vec<Bar> {
+    let bar_type = BarType::from_str("AUDUSD.SIM-1-MINUTE-BID-EXTERNAL").unwrap();
+    (0..NUM_BARS)
+        .map(|i| {
+            let price = Price::from_raw(1_000_000_000 + (i % 100) as i64, 5);
+            Bar::new(
+                bar_type,
+                price,
+                price,
+                price,
+                price,
+                Quantity::from_raw(100_000_000_000_000, 0),
+                i as u64,
+                i as u64,
+            )
+        })
+        .collect()
+}
+
+pub fn criterion_bar_window_benchmark(c: &mut Criterion) {
+    let bars = create_bars();
+
+    c.bench_function("bar_window_iterator", |b| {
+        b.iter(|| {
+            BarWindow::new(black_box(&bars), WINDOW)
+                .unwrap()
+                .map(|window| window[WINDOW - 1].close.raw - window[0].close.raw)
+                .sum::<i64>()
+        })
+    });
+
+    c.bench_function("bar_window_manual_indexing", |b| {
+        b.iter(|| {
+            let bars = black_box(&bars);
+            let mut sum = 0;
+            for i in WINDOW - 1..bars.len() {
+                sum += bars[i].close.raw - bars[i + 1 - WINDOW].close.raw;
+            }
+            sum
+        })
+    });
+}
+
+criterion_group!(benches, criterion_bar_window_benchmark);
+criterion::criterion_main!(benches);
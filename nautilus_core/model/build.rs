@@ -26,6 +26,12 @@ use std::{
 fn main() {
     let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
 
+    // Any `rerun-if-changed` replaces the default of rerunning on every package
+    // file change, so the header inputs must be listed alongside the proto file
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=cbindgen_cython.toml");
+
     // Generate C headers
     let config_c = cbindgen::Config::from_file("cbindgen.toml")
         .expect("unable to find cbindgen.toml configuration file");
@@ -58,4 +64,25 @@ fn main() {
     let mut dst = File::create(cython_path).expect("`File::create` failed");
     dst.write_all(new_data.as_bytes())
         .expect("I/O error on `dist.write`");
+
+    #[cfg(feature = "protobuf")]
+    generate_protobuf();
+}
+
+/// Generates the Protobuf message types, and gRPC services if enabled, under `OUT_DIR`.
+#[cfg(feature = "protobuf")]
+#[allow(clippy::expect_used)] // OK in build script
+fn generate_protobuf() {
+    println!("cargo:rerun-if-changed=proto/bar.proto");
+
+    // Services are only generated for gRPC, `prost-build` skips them otherwise
+    #[cfg(feature = "grpc")]
+    tonic_build::configure()
+        .compile(&["proto/bar.proto"], &["proto"])
+        .expect("unable to compile gRPC definitions");
+
+    #[cfg(not(feature = "grpc"))]
+    prost_build::Config::new()
+        .compile_protos(&["proto/bar.proto"], &["proto"])
+        .expect("unable to compile Protobuf definitions");
 }
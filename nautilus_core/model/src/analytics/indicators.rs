@@ -0,0 +1,167 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Technical indicators computed over series of [`Bar`]s.
+
+use crate::{data::bar::Bar, types::price::Price};
+
+/// Returns the Bollinger Bands (upper, middle, lower) of the bar closes for
+/// each bar with at least `period` bars of history, where the middle band is
+/// the simple moving average and the outer bands are `k` population standard
+/// deviations either side.
+///
+/// The output is shorter than the `bars` by `period - 1`, and is empty if
+/// `period` is zero or greater than the number of bars. The rolling mean and
+/// variance are updated with Welford's algorithm for numerical stability.
+#[must_use]
+pub fn bollinger_bands(bars: &[Bar], period: usize, k: f64) -> Vec<(Price, Price, Price)> {
+    if period == 0 || period > bars.len() {
+        return Vec::new();
+    }
+    let n = period as f64;
+    let mut mean = 0.0;
+    let mut m2 = 0.0;
+    for (count, bar) in bars[..period].iter().enumerate() {
+        let x = bar.close.as_f64();
+        let delta = x - mean;
+        mean += delta / (count + 1) as f64;
+        m2 += delta * (x - mean);
+    }
+
+    let band = |bar: &Bar, mean: f64, m2: f64| {
+        let std_dev = (m2 / n).max(0.0).sqrt();
+        let precision = bar.close.precision;
+        // Unwrapping is safe as the bands are within the range of valid prices
+        // for any realistic `k`
+        (
+            Price::new(mean + k * std_dev, precision).unwrap(),
+            Price::new(mean, precision).unwrap(),
+            Price::new(mean - k * std_dev, precision).unwrap(),
+        )
+    };
+
+    let mut bands = Vec::with_capacity(bars.len() - period + 1);
+    bands.push(band(&bars[period - 1], mean, m2));
+    for (old, new) in bars.iter().zip(&bars[period..]) {
+        let (x_old, x_new) = (old.close.as_f64(), new.close.as_f64());
+        let prev_mean = mean;
+        mean += (x_new - x_old) / n;
+        m2 += (x_new - x_old) * (x_new - mean + x_old - prev_mean);
+        bands.push(band(new, mean, m2));
+    }
+    bands
+}
+
+/// Returns the bandwidth (upper - lower) / middle of each of the `bands`.
+#[must_use]
+pub fn bandwidth(bands: &[(Price, Price, Price)]) -> Vec<f64> {
+    bands
+        .iter()
+        .map(|(upper, middle, lower)| (upper.as_f64() - lower.as_f64()) / middle.as_f64())
+        .collect()
+}
+
+/// Returns the %B of the bar close within the (upper, middle, lower) `bands`,
+/// (close - lower) / (upper - lower), which is 0 at the lower band and 1 at
+/// the upper band.
+///
+/// Returns NaN if the bands have zero width.
+#[must_use]
+pub fn percent_b(bar: &Bar, bands: (Price, Price, Price)) -> f64 {
+    let (upper, _, lower) = bands;
+    let width = upper.as_f64() - lower.as_f64();
+    if width == 0.0 {
+        return f64::NAN;
+    }
+    (bar.close.as_f64() - lower.as_f64()) / width
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::bars_from_closes;
+
+    #[rstest]
+    fn test_bollinger_bands() {
+        let bars = bars_from_closes(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let bands = bollinger_bands(&bars, 5, 2.0);
+
+        // Each window has a population variance of 2, so the bands are at 2 * sqrt(2)
+        assert_eq!(
+            bands,
+            vec![
+                (
+                    Price::from("5.82843"),
+                    Price::from("3.00000"),
+                    Price::from("0.17157")
+                ),
+                (
+                    Price::from("6.82843"),
+                    Price::from("4.00000"),
+                    Price::from("1.17157")
+                ),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_bollinger_bands_flat_series() {
+        let bars = bars_from_closes(&[2.0; 4]);
+        let bands = bollinger_bands(&bars, 2, 2.0);
+
+        assert_eq!(bands.len(), 3);
+        let price = Price::from("2.00000");
+        assert!(bands.iter().all(|band| *band == (price, price, price)));
+        assert!(percent_b(&bars[3], bands[2]).is_nan());
+    }
+
+    #[rstest]
+    fn test_bollinger_bands_invalid_period() {
+        let bars = bars_from_closes(&[1.0, 2.0, 3.0]);
+
+        assert!(bollinger_bands(&bars, 0, 2.0).is_empty());
+        assert!(bollinger_bands(&bars, 4, 2.0).is_empty());
+        assert_eq!(bollinger_bands(&bars, 3, 2.0).len(), 1);
+    }
+
+    #[rstest]
+    fn test_bandwidth() {
+        let bars = bars_from_closes(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let widths = bandwidth(&bollinger_bands(&bars, 5, 2.0));
+
+        assert_eq!(widths.len(), 2);
+        assert!(approx_eq!(f64, widths[0], 5.656_86 / 3.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, widths[1], 5.656_86 / 4.0, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_percent_b() {
+        let bars = bars_from_closes(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let bands = bollinger_bands(&bars, 5, 2.0);
+
+        assert!(approx_eq!(
+            f64,
+            percent_b(&bars[5], bands[1]),
+            (6.0 - 1.171_57) / 5.656_86,
+            epsilon = 1e-9
+        ));
+    }
+}
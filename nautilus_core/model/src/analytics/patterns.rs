@@ -0,0 +1,230 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Candlestick pattern detection over series of [`Bar`]s.
+
+use strum::{AsRefStr, Display};
+
+use crate::data::bar::Bar;
+
+/// A candlestick pattern formed by two or three consecutive bars.
+#[derive(Copy, Clone, Debug, Display, Hash, PartialEq, Eq, AsRefStr)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum PatternKind {
+    /// A bullish bar whose body engulfs the body of the preceding bearish bar.
+    BullishEngulfing,
+    /// A bearish bar whose body engulfs the body of the preceding bullish bar.
+    BearishEngulfing,
+    /// A bearish bar, a small bodied bar below it, then a bullish recovery.
+    MorningStar,
+    /// A bullish bar, a small bodied bar above it, then a bearish decline.
+    EveningStar,
+}
+
+/// Returns whether `curr` is a bullish bar whose body fully contains, and is
+/// larger than, the body of the bearish `prev` bar.
+#[must_use]
+pub fn is_bullish_engulfing(prev: &Bar, curr: &Bar) -> bool {
+    prev.is_bearish()
+        && curr.is_bullish()
+        && curr.open <= prev.close
+        && curr.close >= prev.open
+        && curr.body() > prev.body()
+}
+
+/// Returns whether `curr` is a bearish bar whose body fully contains, and is
+/// larger than, the body of the bullish `prev` bar.
+#[must_use]
+pub fn is_bearish_engulfing(prev: &Bar, curr: &Bar) -> bool {
+    prev.is_bullish()
+        && curr.is_bearish()
+        && curr.open >= prev.close
+        && curr.close <= prev.open
+        && curr.body() > prev.body()
+}
+
+/// Returns whether the body of the `star` is at most half the body of `first`.
+fn is_small_body(star: &Bar, first: &Bar) -> bool {
+    star.body().raw.saturating_mul(2) <= first.body().raw
+}
+
+/// Returns the raw midpoint of the body of the `bar`.
+fn body_midpoint_raw(bar: &Bar) -> i64 {
+    bar.open.raw / 2 + bar.close.raw / 2
+}
+
+/// Returns whether the bars form a morning star: a bearish `b1`, a small
+/// bodied `b2` whose body is at or below the close of `b1`, then a bullish
+/// `b3` closing above the midpoint of the body of `b1`.
+#[must_use]
+pub fn is_morning_star(b1: &Bar, b2: &Bar, b3: &Bar) -> bool {
+    b1.is_bearish()
+        && is_small_body(b2, b1)
+        && b2.open.max(b2.close) <= b1.close
+        && b3.is_bullish()
+        && b3.close.raw > body_midpoint_raw(b1)
+}
+
+/// Returns whether the bars form an evening star: a bullish `b1`, a small
+/// bodied `b2` whose body is at or above the close of `b1`, then a bearish
+/// `b3` closing below the midpoint of the body of `b1`.
+#[must_use]
+pub fn is_evening_star(b1: &Bar, b2: &Bar, b3: &Bar) -> bool {
+    b1.is_bullish()
+        && is_small_body(b2, b1)
+        && b2.open.min(b2.close) >= b1.close
+        && b3.is_bearish()
+        && b3.close.raw < body_midpoint_raw(b1)
+}
+
+/// Returns the (index, pattern) pairs of the candlestick patterns in the
+/// `bars`, where the index is of the last bar of the pattern.
+///
+/// Patterns are listed in bar order, with the two-bar patterns before the
+/// three-bar patterns completed at the same bar.
+#[must_use]
+pub fn scan_patterns(bars: &[Bar]) -> Vec<(usize, PatternKind)> {
+    let mut patterns = Vec::new();
+    for i in 1..bars.len() {
+        let (prev, curr) = (&bars[i - 1], &bars[i]);
+        if is_bullish_engulfing(prev, curr) {
+            patterns.push((i, PatternKind::BullishEngulfing));
+        }
+        if is_bearish_engulfing(prev, curr) {
+            patterns.push((i, PatternKind::BearishEngulfing));
+        }
+        if i >= 2 {
+            let first = &bars[i - 2];
+            if is_morning_star(first, prev, curr) {
+                patterns.push((i, PatternKind::MorningStar));
+            }
+            if is_evening_star(first, prev, curr) {
+                patterns.push((i, PatternKind::EveningStar));
+            }
+        }
+    }
+    patterns
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        data::bar::stubs::bar_type_audusd_1_min_bid,
+        types::{price::Price, quantity::Quantity},
+    };
+
+    fn create_bar(open: &str, high: &str, low: &str, close: &str) -> Bar {
+        Bar::new(
+            bar_type_audusd_1_min_bid(),
+            Price::from(open),
+            Price::from(high),
+            Price::from(low),
+            Price::from(close),
+            Quantity::from("100000"),
+            0,
+            0,
+        )
+    }
+
+    #[rstest]
+    fn test_bullish_engulfing() {
+        let prev = create_bar("1.0020", "1.0022", "1.0008", "1.0010");
+        let curr = create_bar("1.0008", "1.0030", "1.0006", "1.0025");
+
+        assert!(is_bullish_engulfing(&prev, &curr));
+        assert!(!is_bearish_engulfing(&prev, &curr));
+        // The reverse order is not engulfing
+        assert!(!is_bullish_engulfing(&curr, &prev));
+    }
+
+    #[rstest]
+    fn test_bearish_engulfing() {
+        let prev = create_bar("1.0010", "1.0022", "1.0008", "1.0020");
+        let curr = create_bar("1.0025", "1.0028", "1.0004", "1.0005");
+
+        assert!(is_bearish_engulfing(&prev, &curr));
+        assert!(!is_bullish_engulfing(&prev, &curr));
+    }
+
+    #[rstest]
+    fn test_engulfing_requires_contained_body() {
+        // Bullish, but opens above the close of the bearish bar
+        let prev = create_bar("1.0020", "1.0022", "1.0008", "1.0010");
+        let curr = create_bar("1.0012", "1.0030", "1.0010", "1.0025");
+
+        assert!(!is_bullish_engulfing(&prev, &curr));
+    }
+
+    #[rstest]
+    fn test_morning_star() {
+        let b1 = create_bar("1.0050", "1.0052", "1.0018", "1.0020");
+        let b2 = create_bar("1.0015", "1.0018", "1.0008", "1.0012");
+        let b3 = create_bar("1.0014", "1.0048", "1.0012", "1.0045");
+
+        assert!(is_morning_star(&b1, &b2, &b3));
+        assert!(!is_evening_star(&b1, &b2, &b3));
+        // The recovery must close above the midpoint of the first body
+        let weak = create_bar("1.0014", "1.0032", "1.0012", "1.0030");
+        assert!(!is_morning_star(&b1, &b2, &weak));
+    }
+
+    #[rstest]
+    fn test_evening_star() {
+        let b1 = create_bar("1.0020", "1.0052", "1.0018", "1.0050");
+        let b2 = create_bar("1.0055", "1.0062", "1.0052", "1.0058");
+        let b3 = create_bar("1.0054", "1.0056", "1.0022", "1.0025");
+
+        assert!(is_evening_star(&b1, &b2, &b3));
+        assert!(!is_morning_star(&b1, &b2, &b3));
+        // The star must have a small body
+        let large = create_bar("1.0052", "1.0080", "1.0050", "1.0078");
+        assert!(!is_evening_star(&b1, &large, &b3));
+    }
+
+    #[rstest]
+    fn test_scan_patterns() {
+        let bars = [
+            create_bar("1.0050", "1.0052", "1.0018", "1.0020"),
+            create_bar("1.0015", "1.0018", "1.0008", "1.0012"),
+            create_bar("1.0010", "1.0048", "1.0008", "1.0045"),
+        ];
+
+        // The recovery bar both engulfs the star and completes the morning star
+        assert_eq!(
+            scan_patterns(&bars),
+            vec![
+                (2, PatternKind::BullishEngulfing),
+                (2, PatternKind::MorningStar)
+            ]
+        );
+        assert!(scan_patterns(&bars[..1]).is_empty());
+        assert!(scan_patterns(&[]).is_empty());
+    }
+
+    #[rstest]
+    fn test_pattern_kind_display() {
+        assert_eq!(
+            PatternKind::BullishEngulfing.to_string(),
+            "BULLISH_ENGULFING"
+        );
+        assert_eq!(PatternKind::EveningStar.as_ref(), "EVENING_STAR");
+    }
+}
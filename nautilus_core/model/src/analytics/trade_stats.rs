@@ -0,0 +1,460 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Trade-level performance statistics computed from the returns of closed trades.
+
+use crate::{enums::OrderSide, types::price::Price};
+
+/// Represents summary statistics of a series of trade returns.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TradeStatistics {
+    /// The number of trades.
+    pub trade_count: usize,
+    /// The fraction of trades with a positive return.
+    pub win_rate: f64,
+    /// The mean return of the winning trades, or zero if there are none.
+    pub average_win: f64,
+    /// The mean return of the losing trades (negative), or zero if there are none.
+    pub average_loss: f64,
+    /// The expected return per trade, win_rate * average_win + loss_rate * average_loss.
+    pub expectancy: f64,
+    /// The gross profit over the gross loss, or `None` if there are no losing trades.
+    pub profit_factor: Option<f64>,
+}
+
+impl TradeStatistics {
+    /// Returns the statistics of the `trade_returns`, or `None` if there are no trades.
+    ///
+    /// Trades with a zero return count as neither wins nor losses, but are
+    /// included in the trade count.
+    #[must_use]
+    pub fn from_returns(trade_returns: &[f64]) -> Option<Self> {
+        if trade_returns.is_empty() {
+            return None;
+        }
+        let trade_count = trade_returns.len();
+        let (wins, gross_profit) = sum_where(trade_returns, |r| r > 0.0);
+        let (losses, gross_loss) = sum_where(trade_returns, |r| r < 0.0);
+        let mean = |sum: f64, count: usize| {
+            if count == 0 {
+                0.0
+            } else {
+                sum / count as f64
+            }
+        };
+        let win_rate = wins as f64 / trade_count as f64;
+        let loss_rate = losses as f64 / trade_count as f64;
+        let average_win = mean(gross_profit, wins);
+        let average_loss = mean(gross_loss, losses);
+
+        Some(Self {
+            trade_count,
+            win_rate,
+            average_win,
+            average_loss,
+            expectancy: win_rate * average_win + loss_rate * average_loss,
+            profit_factor: profit_factor(trade_returns),
+        })
+    }
+}
+
+/// Returns the number and sum of the `values` matching the `predicate`.
+fn sum_where(values: &[f64], predicate: impl Fn(f64) -> bool) -> (usize, f64) {
+    values
+        .iter()
+        .filter(|v| predicate(**v))
+        .fold((0, 0.0), |(count, sum), v| (count + 1, sum + v))
+}
+
+/// Returns the profit factor of the `trade_returns`, the gross profit of the
+/// winning trades over the absolute gross loss of the losing trades.
+///
+/// Returns `None` if there are no losing trades.
+#[must_use]
+pub fn profit_factor(trade_returns: &[f64]) -> Option<f64> {
+    let (losses, gross_loss) = sum_where(trade_returns, |r| r < 0.0);
+    if losses == 0 {
+        return None;
+    }
+    let (_, gross_profit) = sum_where(trade_returns, |r| r > 0.0);
+    Some(gross_profit / gross_loss.abs())
+}
+
+/// Represents the winning and losing streaks of a series of trade returns.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StreakStats {
+    /// The longest run of consecutive winning trades.
+    pub max_win_streak: usize,
+    /// The longest run of consecutive losing trades.
+    pub max_loss_streak: usize,
+    /// The length of the streak at the last trade, positive for wins and
+    /// negative for losses (zero if the last trade broke even).
+    pub current_streak: i32,
+    /// The mean length of the winning streaks, or zero if there are none.
+    pub avg_win_streak: f64,
+    /// The mean length of the losing streaks, or zero if there are none.
+    pub avg_loss_streak: f64,
+}
+
+/// Returns the streak statistics of the `trade_returns`.
+///
+/// A trade with a zero return ends any current streak without starting a new
+/// one. Returns the default (all zero) statistics if there are no trades.
+#[must_use]
+pub fn streak_analysis(trade_returns: &[f64]) -> StreakStats {
+    // Completed streaks as signed lengths, positive for wins
+    let mut streaks: Vec<i32> = Vec::new();
+    let mut current: i32 = 0;
+    for &r in trade_returns {
+        let sign = if r > 0.0 {
+            1
+        } else if r < 0.0 {
+            -1
+        } else {
+            0
+        };
+        if current != 0 && current.signum() != sign {
+            streaks.push(current);
+            current = 0;
+        }
+        current += sign;
+    }
+    if current != 0 {
+        streaks.push(current);
+    }
+
+    let (win_streaks, loss_streaks): (Vec<i32>, Vec<i32>) = streaks.iter().partition(|s| **s > 0);
+    let stats = |lengths: &[i32]| {
+        let max = lengths.iter().map(|s| s.unsigned_abs() as usize).max();
+        let total: f64 = lengths.iter().map(|s| f64::from(s.abs())).sum();
+        match max {
+            Some(max) => (max, total / lengths.len() as f64),
+            None => (0, 0.0),
+        }
+    };
+    let (max_win_streak, avg_win_streak) = stats(&win_streaks);
+    let (max_loss_streak, avg_loss_streak) = stats(&loss_streaks);
+
+    StreakStats {
+        max_win_streak,
+        max_loss_streak,
+        current_streak: current,
+        avg_win_streak,
+        avg_loss_streak,
+    }
+}
+
+/// Represents the prices of a closed trade for R-multiple analysis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TradeResult {
+    /// The side of the order which opened the trade.
+    pub side: OrderSide,
+    /// The entry price of the trade.
+    pub entry: Price,
+    /// The initial stop-loss price of the trade, which defines the risk (1R).
+    pub stop: Price,
+    /// The exit price of the trade.
+    pub exit: Price,
+}
+
+impl TradeResult {
+    #[must_use]
+    pub fn new(side: OrderSide, entry: Price, stop: Price, exit: Price) -> Self {
+        Self {
+            side,
+            entry,
+            stop,
+            exit,
+        }
+    }
+
+    /// Returns the R-multiple of the trade, see [`r_multiple`].
+    #[must_use]
+    pub fn r_multiple(&self) -> f64 {
+        r_multiple(self.entry, self.stop, self.exit, self.side)
+    }
+}
+
+/// Returns the R-multiple of a trade, the profit or loss in units of the
+/// initial risk: (exit - entry) / (entry - stop).
+///
+/// The `side` is the side of the order which opened the trade, so a buy
+/// requires the stop below the entry and a sell requires it above. Returns NaN
+/// if the stop is on the wrong side of (or equal to) the entry, or the side is
+/// not specified.
+#[must_use]
+pub fn r_multiple(entry: Price, stop: Price, exit: Price, side: OrderSide) -> f64 {
+    let valid = match side {
+        OrderSide::Buy => stop < entry,
+        OrderSide::Sell => stop > entry,
+        OrderSide::NoOrderSide => false,
+    };
+    if !valid {
+        return f64::NAN;
+    }
+    // Dividing the raw fixed-point differences avoids the rounding of each price
+    let (entry, stop, exit) = (
+        i128::from(entry.raw),
+        i128::from(stop.raw),
+        i128::from(exit.raw),
+    );
+    (exit - entry) as f64 / (entry - stop) as f64
+}
+
+/// Represents the distribution of the R-multiples of a series of trades.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RMultipleStats {
+    /// The R-multiples of the trades, sorted ascending.
+    pub r_multiples: Vec<f64>,
+    /// The mean R-multiple, the expectancy of the system in units of risk.
+    pub mean: f64,
+    /// The sample standard deviation of the R-multiples, or zero for a single trade.
+    pub std_dev: f64,
+    /// The median R-multiple.
+    pub median: f64,
+    /// The smallest R-multiple.
+    pub min: f64,
+    /// The largest R-multiple.
+    pub max: f64,
+    /// The fraction of trades with a positive R-multiple.
+    pub win_rate: f64,
+    /// The mean R-multiple over its standard deviation (the system quality
+    /// number before scaling by the square root of the trade count), or `None`
+    /// if the standard deviation is zero.
+    pub expectancy_ratio: Option<f64>,
+}
+
+/// Returns the distribution of the R-multiples of the `trades`, or `None` if
+/// there are no trades with a valid R-multiple.
+///
+/// Trades whose stop is on the wrong side of the entry are excluded.
+#[must_use]
+pub fn r_multiple_distribution(trades: &[TradeResult]) -> Option<RMultipleStats> {
+    let mut r_multiples: Vec<f64> = trades
+        .iter()
+        .map(TradeResult::r_multiple)
+        .filter(|r| !r.is_nan())
+        .collect();
+    if r_multiples.is_empty() {
+        return None;
+    }
+    r_multiples.sort_by(f64::total_cmp);
+
+    let n = r_multiples.len();
+    let mean = r_multiples.iter().sum::<f64>() / n as f64;
+    let std_dev = if n > 1 {
+        let sum_sq: f64 = r_multiples.iter().map(|r| (r - mean).powi(2)).sum();
+        (sum_sq / (n - 1) as f64).sqrt()
+    } else {
+        0.0
+    };
+    let median = if n % 2 == 0 {
+        (r_multiples[n / 2 - 1] + r_multiples[n / 2]) / 2.0
+    } else {
+        r_multiples[n / 2]
+    };
+    let (wins, _) = sum_where(&r_multiples, |r| r > 0.0);
+
+    Some(RMultipleStats {
+        mean,
+        std_dev,
+        median,
+        min: r_multiples[0],
+        max: r_multiples[n - 1],
+        win_rate: wins as f64 / n as f64,
+        expectancy_ratio: (std_dev > 0.0).then(|| mean / std_dev),
+        r_multiples,
+    })
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    fn test_profit_factor() {
+        let returns = [100.0, -50.0, 200.0, -100.0, 0.0];
+
+        assert_eq!(profit_factor(&returns), Some(2.0));
+    }
+
+    #[rstest]
+    fn test_profit_factor_no_losses() {
+        assert_eq!(profit_factor(&[100.0, 0.0]), None);
+        assert_eq!(profit_factor(&[]), None);
+    }
+
+    #[rstest]
+    fn test_trade_statistics() {
+        let returns = [100.0, -50.0, 200.0, -100.0, 0.0];
+        let stats = TradeStatistics::from_returns(&returns).unwrap();
+
+        assert_eq!(stats.trade_count, 5);
+        assert!(approx_eq!(f64, stats.win_rate, 0.4, epsilon = 1e-12));
+        assert!(approx_eq!(f64, stats.average_win, 150.0, epsilon = 1e-12));
+        assert!(approx_eq!(f64, stats.average_loss, -75.0, epsilon = 1e-12));
+        // The expectancy is the mean return: 0.4 * 150 + 0.4 * -75
+        assert!(approx_eq!(f64, stats.expectancy, 30.0, epsilon = 1e-12));
+        assert_eq!(stats.profit_factor, Some(2.0));
+    }
+
+    #[rstest]
+    fn test_trade_statistics_only_wins() {
+        let stats = TradeStatistics::from_returns(&[10.0, 30.0]).unwrap();
+
+        assert_eq!(stats.win_rate, 1.0);
+        assert_eq!(stats.average_win, 20.0);
+        assert_eq!(stats.average_loss, 0.0);
+        assert_eq!(stats.expectancy, 20.0);
+        assert_eq!(stats.profit_factor, None);
+    }
+
+    #[rstest]
+    fn test_trade_statistics_no_trades() {
+        assert_eq!(TradeStatistics::from_returns(&[]), None);
+    }
+
+    #[rstest]
+    fn test_streak_analysis() {
+        // Streaks: W3, L2, W1, L1, (break even), L2
+        let returns = [1.0, 2.0, 3.0, -1.0, -1.0, 5.0, -2.0, 0.0, -1.0, -3.0];
+        let stats = streak_analysis(&returns);
+
+        assert_eq!(stats.max_win_streak, 3);
+        assert_eq!(stats.max_loss_streak, 2);
+        assert_eq!(stats.current_streak, -2);
+        assert_eq!(stats.avg_win_streak, 2.0);
+        assert!(approx_eq!(
+            f64,
+            stats.avg_loss_streak,
+            5.0 / 3.0,
+            epsilon = 1e-12
+        ));
+    }
+
+    #[rstest]
+    #[case(&[1.0, 1.0], 2)]
+    #[case(&[-1.0, 1.0, -1.0, -1.0, -1.0], -3)]
+    #[case(&[1.0, 0.0], 0)]
+    #[case(&[], 0)]
+    fn test_streak_analysis_current_streak(#[case] returns: &[f64], #[case] expected: i32) {
+        assert_eq!(streak_analysis(returns).current_streak, expected);
+    }
+
+    #[rstest]
+    fn test_streak_analysis_no_trades() {
+        assert_eq!(streak_analysis(&[]), StreakStats::default());
+    }
+
+    #[rstest]
+    #[case(OrderSide::Buy, "100.0", "95.0", "110.0", 2.0)]
+    #[case(OrderSide::Buy, "100.0", "95.0", "95.0", -1.0)]
+    #[case(OrderSide::Sell, "100.0", "105.0", "90.0", 2.0)]
+    #[case(OrderSide::Sell, "100.0", "105.0", "102.5", -0.5)]
+    fn test_r_multiple(
+        #[case] side: OrderSide,
+        #[case] entry: &str,
+        #[case] stop: &str,
+        #[case] exit: &str,
+        #[case] expected: f64,
+    ) {
+        let r = r_multiple(
+            Price::from(entry),
+            Price::from(stop),
+            Price::from(exit),
+            side,
+        );
+
+        assert!(approx_eq!(f64, r, expected, epsilon = 1e-12));
+    }
+
+    #[rstest]
+    #[case(OrderSide::Buy, "105.0")]
+    #[case(OrderSide::Sell, "95.0")]
+    #[case(OrderSide::Buy, "100.0")]
+    #[case(OrderSide::NoOrderSide, "95.0")]
+    fn test_r_multiple_invalid_stop(#[case] side: OrderSide, #[case] stop: &str) {
+        let r = r_multiple(
+            Price::from("100.0"),
+            Price::from(stop),
+            Price::from("110.0"),
+            side,
+        );
+
+        assert!(r.is_nan());
+    }
+
+    #[rstest]
+    fn test_r_multiple_distribution() {
+        let trade = |side, entry: &str, stop: &str, exit: &str| {
+            TradeResult::new(
+                side,
+                Price::from(entry),
+                Price::from(stop),
+                Price::from(exit),
+            )
+        };
+        let trades = [
+            trade(OrderSide::Buy, "100.0", "95.0", "110.0"), // 2R
+            trade(OrderSide::Buy, "100.0", "95.0", "95.0"),  // -1R
+            trade(OrderSide::Sell, "100.0", "105.0", "85.0"), // 3R
+            trade(OrderSide::Sell, "100.0", "105.0", "105.0"), // -1R
+            trade(OrderSide::Buy, "100.0", "105.0", "120.0"), // Invalid stop
+        ];
+        let stats = r_multiple_distribution(&trades).unwrap();
+
+        assert_eq!(stats.r_multiples, vec![-1.0, -1.0, 2.0, 3.0]);
+        assert!(approx_eq!(f64, stats.mean, 0.75, epsilon = 1e-12));
+        // Squared deviations 3.0625 + 3.0625 + 1.5625 + 5.0625 = 12.75 over 3
+        assert!(approx_eq!(
+            f64,
+            stats.std_dev,
+            4.25_f64.sqrt(),
+            epsilon = 1e-12
+        ));
+        assert_eq!(stats.median, 0.5);
+        assert_eq!(stats.min, -1.0);
+        assert_eq!(stats.max, 3.0);
+        assert_eq!(stats.win_rate, 0.5);
+        assert!(approx_eq!(
+            f64,
+            stats.expectancy_ratio.unwrap(),
+            0.75 / 4.25_f64.sqrt(),
+            epsilon = 1e-12
+        ));
+    }
+
+    #[rstest]
+    fn test_r_multiple_distribution_single_and_no_trades() {
+        let trade = TradeResult::new(
+            OrderSide::Buy,
+            Price::from("100.0"),
+            Price::from("90.0"),
+            Price::from("115.0"),
+        );
+        let stats = r_multiple_distribution(&[trade]).unwrap();
+
+        assert_eq!(stats.r_multiples, vec![1.5]);
+        assert_eq!(stats.std_dev, 0.0);
+        assert_eq!(stats.expectancy_ratio, None);
+        assert_eq!(r_multiple_distribution(&[]), None);
+    }
+}
@@ -0,0 +1,216 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Volume profiles, the traded volume at each price level over a series of [`Bar`]s.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    data::bar::Bar,
+    types::{price::Price, quantity::Quantity},
+};
+
+/// Returns the volume profile of the `bars`, with the volume of each bar
+/// distributed uniformly across the `tick_size` price levels from its low to
+/// its high inclusive.
+///
+/// The levels are the multiples of `tick_size`, with bar prices truncated to
+/// the level at or below. Any indivisible remainder of a bar's volume is
+/// assigned to its lowest levels, so the total volume is preserved exactly.
+///
+/// # Panics
+///
+/// If `tick_size` is not positive.
+#[must_use]
+pub fn volume_profile(bars: &[Bar], tick_size: Price) -> BTreeMap<Price, Quantity> {
+    assert!(tick_size.raw > 0, "`tick_size` was not positive");
+    let mut profile: BTreeMap<Price, Quantity> = BTreeMap::new();
+    for bar in bars {
+        let low_tick = bar.low.ticks_from_zero(tick_size);
+        let high_tick = bar.high.ticks_from_zero(tick_size).max(low_tick);
+        let levels = (high_tick - low_tick + 1) as u64;
+        let share = bar.volume.raw / levels;
+        let remainder = bar.volume.raw % levels;
+        for (i, tick) in (low_tick..=high_tick).enumerate() {
+            let price = Price::from_raw(tick * tick_size.raw, tick_size.precision);
+            let volume = share + u64::from((i as u64) < remainder);
+            *profile
+                .entry(price)
+                .or_insert_with(|| Quantity::from_raw(0, bar.volume.precision)) += volume;
+        }
+    }
+    profile
+}
+
+/// Returns the point of control (POC) of the volume `profile`, the price level
+/// with the highest volume, where the lowest price wins ties.
+///
+/// # Panics
+///
+/// If `profile` is empty.
+#[must_use]
+pub fn point_of_control(profile: &BTreeMap<Price, Quantity>) -> Price {
+    let mut levels = profile.iter();
+    let (mut poc, mut max_volume) = levels.next().expect("`profile` was empty");
+    for (price, volume) in levels {
+        if volume > max_volume {
+            (poc, max_volume) = (price, volume);
+        }
+    }
+    *poc
+}
+
+/// Returns the (low, high) bounds of the value area of the volume `profile`,
+/// the price range around the point of control containing at least `pct` of
+/// the total volume, e.g. 0.7 for the conventional 70% value area.
+///
+/// Starting from the point of control, the area is extended one level at a
+/// time to whichever adjacent level has the higher volume, preferring the
+/// level above on ties.
+///
+/// # Panics
+///
+/// If `profile` is empty, or `pct` is not in the range (0, 1].
+#[must_use]
+pub fn value_area(profile: &BTreeMap<Price, Quantity>, pct: f64) -> (Price, Price) {
+    assert!(pct > 0.0 && pct <= 1.0, "`pct` was not in the range (0, 1]");
+    let levels: Vec<(Price, u64)> = profile.iter().map(|(p, q)| (*p, q.raw)).collect();
+    let poc = point_of_control(profile);
+    let poc_index = levels
+        .iter()
+        .position(|(price, _)| *price == poc)
+        .expect("point of control was in profile");
+
+    let total: u64 = levels.iter().map(|(_, volume)| volume).sum();
+    let target = pct * total as f64;
+    let (mut low, mut high) = (poc_index, poc_index);
+    let mut area_volume = levels[poc_index].1;
+    while (area_volume as f64) < target {
+        let below = low.checked_sub(1).map(|i| levels[i].1);
+        let above = levels.get(high + 1).map(|(_, volume)| *volume);
+        match (below, above) {
+            (Some(below), Some(above)) if below > above => {
+                low -= 1;
+                area_volume += below;
+            }
+            (_, Some(above)) => {
+                high += 1;
+                area_volume += above;
+            }
+            (Some(below), None) => {
+                low -= 1;
+                area_volume += below;
+            }
+            (None, None) => break,
+        }
+    }
+    (levels[low].0, levels[high].0)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::bars_from_closes;
+
+    fn create_bar(low: &str, high: &str, volume: i64) -> Bar {
+        let mut bar = bars_from_closes(&[1.0])[0];
+        bar.open = Price::from(low);
+        bar.low = Price::from(low);
+        bar.high = Price::from(high);
+        bar.close = Price::from(high);
+        bar.volume = Quantity::from(volume);
+        bar
+    }
+
+    /// Returns 20 bars with a profile of 1000, 2000, 5000, 1000 and 1000 at
+    /// the prices 100 to 104.
+    fn create_profile_bars() -> Vec<Bar> {
+        let mut bars = vec![create_bar("100.00000", "104.00000", 500); 10];
+        bars.extend(vec![create_bar("102.00000", "102.00000", 600); 5]);
+        bars.extend(vec![create_bar("101.00000", "102.00000", 400); 5]);
+        bars
+    }
+
+    #[rstest]
+    fn test_volume_profile() {
+        let profile = volume_profile(&create_profile_bars(), Price::from("1.00000"));
+
+        let expected: Vec<(Price, Quantity)> = [
+            ("100.00000", 1000),
+            ("101.00000", 2000),
+            ("102.00000", 5000),
+            ("103.00000", 1000),
+            ("104.00000", 1000),
+        ]
+        .iter()
+        .map(|&(price, volume)| (Price::from(price), Quantity::from(volume)))
+        .collect();
+        assert_eq!(profile.into_iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[rstest]
+    fn test_volume_profile_preserves_indivisible_volume() {
+        let bars = [create_bar("1.00000", "1.00002", 100)];
+        let profile = volume_profile(&bars, Price::from("0.00001"));
+
+        let total: u64 = profile.values().map(|volume| volume.raw).sum();
+        assert_eq!(profile.len(), 3);
+        assert_eq!(total, Quantity::from(100).raw);
+    }
+
+    #[rstest]
+    fn test_point_of_control() {
+        let profile = volume_profile(&create_profile_bars(), Price::from("1.00000"));
+
+        assert_eq!(point_of_control(&profile), Price::from("102.00000"));
+    }
+
+    #[rstest]
+    fn test_value_area() {
+        let profile = volume_profile(&create_profile_bars(), Price::from("1.00000"));
+
+        // The POC holds 50%, extended to the higher volume level below for 70%
+        assert_eq!(
+            value_area(&profile, 0.7),
+            (Price::from("101.00000"), Price::from("102.00000"))
+        );
+        assert_eq!(
+            value_area(&profile, 0.5),
+            (Price::from("102.00000"), Price::from("102.00000"))
+        );
+        assert_eq!(
+            value_area(&profile, 1.0),
+            (Price::from("100.00000"), Price::from("104.00000"))
+        );
+    }
+
+    #[rstest]
+    #[should_panic(expected = "`profile` was empty")]
+    fn test_point_of_control_empty_profile() {
+        let _ = point_of_control(&BTreeMap::new());
+    }
+
+    #[rstest]
+    #[should_panic(expected = "`pct` was not in the range (0, 1]")]
+    fn test_value_area_invalid_pct() {
+        let profile = volume_profile(&create_profile_bars(), Price::from("1.00000"));
+        let _ = value_area(&profile, 1.5);
+    }
+}
@@ -0,0 +1,643 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use anyhow::{bail, Result};
+
+use super::{
+    bar::{Bar, BarSpecification, BarType},
+    trade::TradeTick,
+};
+use crate::{
+    enums::{AggregationSource, BarAggregation},
+    types::{price::Price, quantity::Quantity},
+};
+
+/// Aggregates incoming bars into bars of a target specification.
+#[derive(Clone, Debug)]
+pub struct BarAggregator {
+    spec: BarSpecification,
+    pending: Vec<Bar>,
+}
+
+impl BarAggregator {
+    #[must_use]
+    pub fn new(spec: BarSpecification) -> Self {
+        Self {
+            spec,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns the target bar specification of the aggregator.
+    #[must_use]
+    pub fn spec(&self) -> BarSpecification {
+        self.spec
+    }
+
+    /// Returns the bars received since the last aggregated bar was built.
+    #[must_use]
+    pub fn pending(&self) -> &[Bar] {
+        &self.pending
+    }
+
+    /// Handles the given `bar`, returning the aggregated bar once enough bars
+    /// have been received to complete it.
+    pub fn handle_bar(&mut self, bar: Bar) -> Result<Option<Bar>> {
+        let Some(factor) = bar.bar_type.spec.resample_factor(&self.spec) else {
+            bail!(
+                "Cannot aggregate {} bars to {}",
+                bar.bar_type.spec,
+                self.spec
+            );
+        };
+        if let Some(first) = self.pending.first() {
+            if first.bar_type != bar.bar_type {
+                bail!(
+                    "Cannot aggregate mixed bar types, expected {} was {}",
+                    first.bar_type,
+                    bar.bar_type
+                );
+            }
+        }
+        self.pending.push(bar);
+        if self.pending.len() < factor {
+            return Ok(None);
+        }
+
+        // The pending bars are dropped even on error so the next bar starts afresh
+        let aggregated = Bar::resample(&self.pending, &self.spec);
+        self.pending.clear();
+        Ok(aggregated?.first().copied())
+    }
+}
+
+/// Aggregates trade ticks into range bars, which close once the price range
+/// `high - low` would exceed the bar type step in increments of the tick size.
+///
+/// A tick which would widen the range beyond the step closes the bar without
+/// that tick, and opens the next bar at its price, so every bar spans at most
+/// `step * tick_size` regardless of time or volume.
+#[derive(Clone, Debug)]
+pub struct RangeBarAggregator {
+    bar_type: BarType,
+    range: i64,
+    partial: Option<Bar>,
+}
+
+impl RangeBarAggregator {
+    /// Creates a new range bar aggregator for the given `bar_type`, with the
+    /// step interpreted as the range in increments of `tick_size`.
+    ///
+    /// # Errors
+    ///
+    /// If the `bar_type` aggregation is not [`BarAggregation::Range`], or the
+    /// range is not positive.
+    pub fn new(bar_type: BarType, tick_size: Price) -> Result<Self> {
+        if bar_type.spec.aggregation != BarAggregation::Range {
+            bail!("Cannot aggregate range bars for {bar_type}");
+        }
+        let range = i64::try_from(bar_type.spec.step)
+            .ok()
+            .and_then(|step| tick_size.raw.checked_mul(step))
+            .unwrap_or_default();
+        if range <= 0 {
+            bail!(
+                "Invalid range for {bar_type}, was {} ticks of {tick_size}",
+                bar_type.spec.step
+            );
+        }
+        Ok(Self {
+            bar_type,
+            range,
+            partial: None,
+        })
+    }
+
+    /// Returns the bar type of the aggregator.
+    #[must_use]
+    pub fn bar_type(&self) -> BarType {
+        self.bar_type
+    }
+
+    /// Returns the bar being built from the ticks received since the last
+    /// range bar was closed.
+    #[must_use]
+    pub fn partial(&self) -> Option<Bar> {
+        self.partial
+    }
+
+    /// Handles the given trade `tick`, returning the closed range bar if the
+    /// tick price would push the range beyond the step.
+    pub fn handle_trade(&mut self, tick: &TradeTick) -> Option<Bar> {
+        if let Some(bar) = &mut self.partial {
+            let high = bar.high.max(tick.price);
+            let low = bar.low.min(tick.price);
+            if high.raw - low.raw <= self.range {
+                bar.high = high;
+                bar.low = low;
+                bar.close = tick.price;
+                bar.volume += tick.size;
+                bar.ts_event = tick.ts_event;
+                bar.ts_init = tick.ts_init;
+                return None;
+            }
+        }
+
+        let closed = self.partial.take();
+        self.partial = Some(Bar::new(
+            self.bar_type,
+            tick.price,
+            tick.price,
+            tick.price,
+            tick.price,
+            tick.size,
+            tick.ts_event,
+            tick.ts_init,
+        ));
+        closed
+    }
+}
+
+/// Aggregates trade ticks into Renko bricks, which form once the price has
+/// moved the bar type step in increments of the tick size from the close of
+/// the last brick.
+///
+/// Continuing in the current direction requires a move of one brick size,
+/// whereas a reversal requires a move of twice the brick size. The open of
+/// each brick is the close of the previous brick, so a reversal forms two
+/// bricks, and a large move forms as many bricks as whole brick sizes moved.
+#[derive(Clone, Debug)]
+pub struct RenkoBarAggregator {
+    bar_type: BarType,
+    brick_size: i64,
+    last_close: Option<Price>,
+    current_direction: i8,
+    volume: Quantity,
+}
+
+impl RenkoBarAggregator {
+    /// Creates a new Renko bar aggregator for the given `bar_type`, with the
+    /// step interpreted as the brick size in increments of `tick_size`.
+    ///
+    /// # Errors
+    ///
+    /// If the `bar_type` aggregation is not [`BarAggregation::Renko`], or the
+    /// brick size is not positive.
+    pub fn new(bar_type: BarType, tick_size: Price) -> Result<Self> {
+        if bar_type.spec.aggregation != BarAggregation::Renko {
+            bail!("Cannot aggregate Renko bars for {bar_type}");
+        }
+        let brick_size = i64::try_from(bar_type.spec.step)
+            .ok()
+            .and_then(|step| tick_size.raw.checked_mul(step))
+            .unwrap_or_default();
+        if brick_size <= 0 {
+            bail!(
+                "Invalid brick size for {bar_type}, was {} ticks of {tick_size}",
+                bar_type.spec.step
+            );
+        }
+        Ok(Self {
+            bar_type,
+            brick_size,
+            last_close: None,
+            current_direction: 0,
+            volume: Quantity::zero(0),
+        })
+    }
+
+    /// Returns the bar type of the aggregator.
+    #[must_use]
+    pub fn bar_type(&self) -> BarType {
+        self.bar_type
+    }
+
+    /// Returns the close of the last brick, or the first trade price if no
+    /// brick has formed yet.
+    #[must_use]
+    pub fn last_close(&self) -> Option<Price> {
+        self.last_close
+    }
+
+    /// Returns the direction of the last brick, 1 for up and -1 for down, or 0
+    /// if no brick has formed yet.
+    #[must_use]
+    pub fn current_direction(&self) -> i8 {
+        self.current_direction
+    }
+
+    /// Handles the given trade `tick`, returning the bricks formed by the move
+    /// in the tick price, in order.
+    ///
+    /// The volume traded since the last brick is assigned to the first brick
+    /// formed, with any further bricks formed by the same tick having zero volume.
+    pub fn handle_trade(&mut self, tick: &TradeTick) -> Vec<Bar> {
+        self.volume = if self.volume.is_zero() {
+            tick.size
+        } else {
+            self.volume + tick.size
+        };
+        let Some(mut last_close) = self.last_close else {
+            self.last_close = Some(tick.price);
+            return Vec::new();
+        };
+
+        let moved = tick.price.raw - last_close.raw;
+        let direction: i8 = if moved > 0 { 1 } else { -1 };
+        let mut bricks = moved.abs() / self.brick_size;
+        if self.current_direction == -direction && bricks < 2 {
+            return Vec::new();
+        }
+
+        let mut bars = Vec::new();
+        while bricks > 0 {
+            let close = Price::from_raw(
+                last_close.raw + i64::from(direction) * self.brick_size,
+                last_close.precision,
+            );
+            bars.push(Bar::new(
+                self.bar_type,
+                last_close,
+                last_close.max(close),
+                last_close.min(close),
+                close,
+                self.volume,
+                tick.ts_event,
+                tick.ts_init,
+            ));
+            self.volume = Quantity::zero(self.volume.precision);
+            last_close = close;
+            bricks -= 1;
+        }
+        if !bars.is_empty() {
+            self.last_close = Some(last_close);
+            self.current_direction = direction;
+        }
+        bars
+    }
+}
+
+/// Routes bars by their aggregation source.
+///
+/// Externally aggregated bars are published to external subscribers as-is,
+/// whereas internally aggregated bars are fed through a [`BarAggregator`] with
+/// the resulting bars published to internal subscribers.
+#[derive(Debug)]
+pub struct BarRouter {
+    aggregator: BarAggregator,
+    external: Vec<Sender<Bar>>,
+    internal: Vec<Sender<Bar>>,
+}
+
+impl BarRouter {
+    #[must_use]
+    pub fn new(aggregator: BarAggregator) -> Self {
+        Self {
+            aggregator,
+            external: Vec::new(),
+            internal: Vec::new(),
+        }
+    }
+
+    /// Returns the aggregator for internally aggregated bars.
+    #[must_use]
+    pub fn aggregator(&self) -> &BarAggregator {
+        &self.aggregator
+    }
+
+    /// Returns a receiver of externally aggregated bars.
+    pub fn subscribe_external(&mut self) -> Receiver<Bar> {
+        let (tx, rx) = channel();
+        self.external.push(tx);
+        rx
+    }
+
+    /// Returns a receiver of bars built by the internal aggregator.
+    pub fn subscribe_internal(&mut self) -> Receiver<Bar> {
+        let (tx, rx) = channel();
+        self.internal.push(tx);
+        rx
+    }
+
+    /// Routes the given `bar` based on its aggregation source.
+    pub fn route(&mut self, bar: Bar) -> Result<()> {
+        match bar.bar_type.aggregation_source {
+            AggregationSource::External => publish(&mut self.external, bar),
+            AggregationSource::Internal => {
+                if let Some(aggregated) = self.aggregator.handle_bar(bar)? {
+                    publish(&mut self.internal, aggregated);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sends the bar to each subscriber, dropping any whose receiver has hung up.
+fn publish(subscribers: &mut Vec<Sender<Bar>>, bar: Bar) {
+    subscribers.retain(|tx| tx.send(bar).is_ok());
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        data::bar::stubs::bars_from_closes,
+        enums::{AggressorSide, PriceType},
+        identifiers::{instrument_id::InstrumentId, trade_id::TradeId},
+    };
+
+    fn create_router() -> BarRouter {
+        BarRouter::new(BarAggregator::new(BarSpecification {
+            step: 2,
+            aggregation: BarAggregation::Minute,
+            price_type: PriceType::Bid,
+        }))
+    }
+
+    fn create_internal_bars(closes: &[f64]) -> Vec<Bar> {
+        let mut bars = bars_from_closes(closes);
+        for bar in &mut bars {
+            bar.bar_type.aggregation_source = AggregationSource::Internal;
+        }
+        bars
+    }
+
+    #[rstest]
+    fn test_route_external_bar_bypasses_aggregation() {
+        let mut router = create_router();
+        let external_rx = router.subscribe_external();
+        let internal_rx = router.subscribe_internal();
+        let bar = bars_from_closes(&[1.0])[0];
+        router.route(bar).unwrap();
+
+        assert_eq!(external_rx.try_recv().unwrap(), bar);
+        assert!(internal_rx.try_recv().is_err());
+        assert!(router.aggregator().pending().is_empty());
+    }
+
+    #[rstest]
+    fn test_route_internal_bar_forwarded_to_aggregator() {
+        let mut router = create_router();
+        let external_rx = router.subscribe_external();
+        let internal_rx = router.subscribe_internal();
+        let bars = create_internal_bars(&[1.0, 2.0]);
+
+        router.route(bars[0]).unwrap();
+        assert_eq!(router.aggregator().pending(), &bars[..1]);
+        assert!(internal_rx.try_recv().is_err());
+
+        router.route(bars[1]).unwrap();
+        let aggregated = internal_rx.try_recv().unwrap();
+        assert_eq!(aggregated.bar_type.spec.step, 2);
+        assert_eq!(aggregated.close, bars[1].close);
+        assert!(router.aggregator().pending().is_empty());
+        assert!(external_rx.try_recv().is_err());
+    }
+
+    #[rstest]
+    fn test_route_drops_disconnected_subscribers() {
+        let mut router = create_router();
+        drop(router.subscribe_external());
+        let external_rx = router.subscribe_external();
+        let bar = bars_from_closes(&[1.0])[0];
+        router.route(bar).unwrap();
+
+        assert_eq!(router.external.len(), 1);
+        assert_eq!(external_rx.try_recv().unwrap(), bar);
+    }
+
+    #[rstest]
+    fn test_aggregator_incompatible_bar() {
+        let mut aggregator = BarAggregator::new(BarSpecification {
+            step: 5,
+            aggregation: BarAggregation::Tick,
+            price_type: PriceType::Bid,
+        });
+        let bar = bars_from_closes(&[1.0])[0];
+
+        assert!(aggregator.handle_bar(bar).is_err());
+        assert!(aggregator.pending().is_empty());
+    }
+
+    #[rstest]
+    fn test_aggregator_mixed_bar_types() {
+        let mut router = create_router();
+        let internal_rx = router.subscribe_internal();
+        let bars = create_internal_bars(&[1.0, 2.0]);
+        let mut other = bars[1];
+        other.bar_type.instrument_id = InstrumentId::from("GBPUSD.SIM");
+        router.route(bars[0]).unwrap();
+
+        assert!(router.route(other).is_err());
+        assert_eq!(router.aggregator().pending(), &bars[..1]);
+
+        router.route(bars[1]).unwrap();
+        assert_eq!(internal_rx.try_recv().unwrap().close, bars[1].close);
+        assert!(router.aggregator().pending().is_empty());
+    }
+
+    fn create_range_aggregator() -> RangeBarAggregator {
+        let bar_type = BarType::from_str("AUDUSD.SIM-10-RANGE-LAST-INTERNAL").unwrap();
+        RangeBarAggregator::new(bar_type, Price::from("0.01")).unwrap()
+    }
+
+    fn create_trade(price: &str, ts: u64) -> TradeTick {
+        TradeTick::new(
+            InstrumentId::from("AUDUSD.SIM"),
+            Price::from(price),
+            Quantity::from("1"),
+            AggressorSide::Buyer,
+            TradeId::new(&ts.to_string()).unwrap(),
+            ts,
+            ts,
+        )
+    }
+
+    fn handle_prices(aggregator: &mut RangeBarAggregator, prices: &[&str]) -> Vec<Bar> {
+        prices
+            .iter()
+            .enumerate()
+            .filter_map(|(i, price)| aggregator.handle_trade(&create_trade(price, i as u64)))
+            .collect()
+    }
+
+    #[rstest]
+    fn test_range_aggregator_exact_range_trigger() {
+        let mut aggregator = create_range_aggregator();
+
+        // A range of exactly 10 ticks does not close the bar
+        let bars = handle_prices(&mut aggregator, &["1.00", "1.05", "1.10"]);
+        assert!(bars.is_empty());
+
+        let bar = aggregator.handle_trade(&create_trade("1.11", 3)).unwrap();
+        assert_eq!(bar.open, Price::from("1.00"));
+        assert_eq!(bar.high, Price::from("1.10"));
+        assert_eq!(bar.low, Price::from("1.00"));
+        assert_eq!(bar.close, Price::from("1.10"));
+        assert_eq!(bar.volume, Quantity::from("3"));
+        assert_eq!(bar.ts_event, 2);
+        assert_eq!(bar.high - bar.low, Price::from("0.10"));
+        assert_eq!(aggregator.partial().unwrap().open, Price::from("1.11"));
+    }
+
+    #[rstest]
+    fn test_range_aggregator_partial_range_accumulation() {
+        let mut aggregator = create_range_aggregator();
+        let bars = handle_prices(&mut aggregator, &["1.00", "1.03", "0.98", "1.02", "1.08"]);
+        let partial = aggregator.partial().unwrap();
+
+        assert!(bars.is_empty());
+        assert_eq!(partial.open, Price::from("1.00"));
+        assert_eq!(partial.high, Price::from("1.08"));
+        assert_eq!(partial.low, Price::from("0.98"));
+        assert_eq!(partial.close, Price::from("1.08"));
+        assert_eq!(partial.volume, Quantity::from("5"));
+        assert_eq!(partial.ts_event, 4);
+    }
+
+    proptest! {
+        #[test]
+        fn test_range_aggregator_consecutive_bars_share_at_most_one_boundary(
+            steps in proptest::collection::vec(-4_i64..=4, 1..1_000)
+        ) {
+            let mut aggregator = create_range_aggregator();
+            let mut price: i64 = 10_000;
+            let prices: Vec<String> = steps
+                .iter()
+                .map(|step| {
+                    price += step;
+                    format!("{}.{:02}", price / 100, price % 100)
+                })
+                .collect();
+            let prices: Vec<&str> = prices.iter().map(String::as_str).collect();
+            let bars = handle_prices(&mut aggregator, &prices);
+
+            for bar in &bars {
+                prop_assert!(bar.high - bar.low <= Price::from("0.10"));
+            }
+            for pair in bars.windows(2) {
+                let shared = [pair[1].low, pair[1].high]
+                    .iter()
+                    .filter(|p| **p == pair[0].low || **p == pair[0].high)
+                    .count();
+                prop_assert!(shared <= 1);
+            }
+        }
+    }
+
+    fn create_renko_aggregator() -> RenkoBarAggregator {
+        let bar_type = BarType::from_str("AUDUSD.SIM-1-RENKO-LAST-INTERNAL").unwrap();
+        RenkoBarAggregator::new(bar_type, Price::from("1")).unwrap()
+    }
+
+    fn handle_renko_prices(aggregator: &mut RenkoBarAggregator, prices: &[&str]) -> Vec<Bar> {
+        prices
+            .iter()
+            .enumerate()
+            .flat_map(|(i, price)| aggregator.handle_trade(&create_trade(price, i as u64)))
+            .collect()
+    }
+
+    fn brick_prices(bars: &[Bar]) -> Vec<(Price, Price)> {
+        bars.iter().map(|bar| (bar.open, bar.close)).collect()
+    }
+
+    #[rstest]
+    fn test_renko_aggregator_brick_sequence() {
+        let mut aggregator = create_renko_aggregator();
+        let bars = handle_renko_prices(&mut aggregator, &["100", "101", "102", "101", "103"]);
+
+        // The pullback to 101 is a single brick against the trend, so no reversal
+        assert_eq!(
+            brick_prices(&bars),
+            vec![
+                (Price::from("100"), Price::from("101")),
+                (Price::from("101"), Price::from("102")),
+                (Price::from("102"), Price::from("103")),
+            ]
+        );
+        for pair in bars.windows(2) {
+            assert_eq!(pair[1].open, pair[0].close);
+        }
+        for bar in &bars {
+            assert_eq!(bar.high, bar.close);
+            assert_eq!(bar.low, bar.open);
+        }
+        // The trades at 100 and 101 make up the first brick, and the second
+        // pullback trade is included in the last brick
+        assert_eq!(bars[0].volume, Quantity::from("2"));
+        assert_eq!(bars[2].volume, Quantity::from("2"));
+        assert_eq!(aggregator.last_close(), Some(Price::from("103")));
+        assert_eq!(aggregator.current_direction(), 1);
+    }
+
+    #[rstest]
+    fn test_renko_aggregator_reversal_requires_two_bricks() {
+        let mut aggregator = create_renko_aggregator();
+        let bars = handle_renko_prices(&mut aggregator, &["100", "101", "102", "100"]);
+
+        assert_eq!(
+            brick_prices(&bars),
+            vec![
+                (Price::from("100"), Price::from("101")),
+                (Price::from("101"), Price::from("102")),
+                (Price::from("102"), Price::from("101")),
+                (Price::from("101"), Price::from("100")),
+            ]
+        );
+        assert_eq!(bars[2].high, Price::from("102"));
+        assert_eq!(bars[2].low, Price::from("101"));
+        assert_eq!(bars[3].volume, Quantity::zero(0));
+        assert_eq!(aggregator.current_direction(), -1);
+    }
+
+    #[rstest]
+    fn test_renko_aggregator_first_trade_sets_anchor() {
+        let mut aggregator = create_renko_aggregator();
+
+        assert!(aggregator.handle_trade(&create_trade("100", 0)).is_empty());
+        assert_eq!(aggregator.last_close(), Some(Price::from("100")));
+        assert_eq!(aggregator.current_direction(), 0);
+    }
+
+    #[rstest]
+    fn test_renko_aggregator_invalid_bar_type() {
+        let range = BarType::from_str("AUDUSD.SIM-1-RANGE-LAST-INTERNAL").unwrap();
+        let zero_step = BarType::from_str("AUDUSD.SIM-0-RENKO-LAST-INTERNAL").unwrap();
+
+        assert!(RenkoBarAggregator::new(range, Price::from("1")).is_err());
+        assert!(RenkoBarAggregator::new(zero_step, Price::from("1")).is_err());
+    }
+
+    #[rstest]
+    fn test_range_aggregator_invalid_bar_type() {
+        let minute = BarType::from_str("AUDUSD.SIM-1-MINUTE-LAST-INTERNAL").unwrap();
+        let zero_step = BarType::from_str("AUDUSD.SIM-0-RANGE-LAST-INTERNAL").unwrap();
+
+        assert!(RangeBarAggregator::new(minute, Price::from("0.01")).is_err());
+        assert!(RangeBarAggregator::new(zero_step, Price::from("0.01")).is_err());
+    }
+}
@@ -13,6 +13,8 @@
 //  limitations under the License.
 // -------------------------------------------------------------------------------------------------
 
+#[cfg(feature = "fx_hash")]
+use std::hash::BuildHasherDefault;
 use std::{
     collections::{hash_map::DefaultHasher, HashMap},
     fmt::{Debug, Display, Formatter},
@@ -20,15 +22,31 @@ use std::{
     str::FromStr,
 };
 
-use nautilus_core::{python::to_pyvalue_err, serialization::Serializable, time::UnixNanos};
-use pyo3::{prelude::*, pyclass::CompareOp, types::PyDict};
+use anyhow::{bail, Result};
+use nautilus_core::{
+    python::to_pyvalue_err,
+    serialization::Serializable,
+    time::{duration_since_unix_epoch, UnixNanos},
+};
+use pyo3::{
+    buffer::PyBuffer,
+    exceptions::PyValueError,
+    prelude::*,
+    pyclass::CompareOp,
+    sync::GILOnceCell,
+    types::{IntoPyDict, PyBytes, PyDict, PyTuple},
+};
+#[cfg(feature = "fx_hash")]
+use rustc_hash::FxHasher;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror;
 
+use super::trade::TradeTick;
 use crate::{
+    analytics::patterns::scan_patterns,
     enums::{AggregationSource, BarAggregation, PriceType},
-    identifiers::instrument_id::InstrumentId,
-    types::{price::Price, quantity::Quantity},
+    identifiers::{instrument_id::InstrumentId, symbol::Symbol, venue::Venue},
+    types::{fixed::FIXED_PRECISION, price::Price, quantity::Quantity},
 };
 
 /// Represents a bar aggregation specification including a step, aggregation
@@ -45,6 +63,80 @@ pub struct BarSpecification {
     pub price_type: PriceType,
 }
 
+impl BarSpecification {
+    /// Returns whether bars of this specification can be aggregated into bars
+    /// of the `target` specification.
+    ///
+    /// Both specifications must share the same aggregation method and price
+    /// type, and the `target` step must be a multiple of this step. Range and
+    /// Renko bars only share a step, as consecutive bars do not make up a wider one.
+    #[must_use]
+    pub fn is_compatible_with(&self, target: &BarSpecification) -> bool {
+        self.aggregation == target.aggregation
+            && self.price_type == target.price_type
+            && self.step != 0
+            && target.step % self.step == 0
+            && (!matches!(
+                self.aggregation,
+                BarAggregation::Range | BarAggregation::Renko
+            ) || self.step == target.step)
+    }
+
+    /// Returns the number of bars of this specification which make up a single
+    /// bar of the `target` specification, or `None` if not compatible.
+    #[must_use]
+    pub fn resample_factor(&self, target: &BarSpecification) -> Option<usize> {
+        if self.is_compatible_with(target) {
+            Some(target.step / self.step)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a human-readable label for the specification, such as for chart
+    /// axes, e.g. `1 Min Bid` or `100 Tick Last`.
+    #[must_use]
+    pub fn display_label(&self) -> String {
+        let aggregation = match self.aggregation {
+            BarAggregation::Tick => "Tick",
+            BarAggregation::TickImbalance => "Tick Imbalance",
+            BarAggregation::TickRuns => "Tick Runs",
+            BarAggregation::Volume => "Volume",
+            BarAggregation::VolumeImbalance => "Volume Imbalance",
+            BarAggregation::VolumeRuns => "Volume Runs",
+            BarAggregation::Value => "Value",
+            BarAggregation::ValueImbalance => "Value Imbalance",
+            BarAggregation::ValueRuns => "Value Runs",
+            BarAggregation::Millisecond => "Ms",
+            BarAggregation::Second => "Sec",
+            BarAggregation::Minute => "Min",
+            BarAggregation::Hour => "Hr",
+            BarAggregation::Day => "Day",
+            BarAggregation::Week => "Week",
+            BarAggregation::Month => "Month",
+            BarAggregation::Range => "Range",
+            BarAggregation::Renko => "Renko",
+        };
+        let price_type = match self.price_type {
+            PriceType::Bid => "Bid",
+            PriceType::Ask => "Ask",
+            PriceType::Mid => "Mid",
+            PriceType::Last => "Last",
+        };
+        format!("{} {aggregation} {price_type}", self.step)
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl BarSpecification {
+    #[getter]
+    #[pyo3(name = "display_label")]
+    fn py_display_label(&self) -> String {
+        self.display_label()
+    }
+}
+
 impl Display for BarSpecification {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}-{}-{}", self.step, self.aggregation, self.price_type)
@@ -55,7 +147,7 @@ impl Display for BarSpecification {
 /// aggregation source.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[pyclass]
+#[pyclass(module = "nautilus_trader.core.nautilus_pyo3.model")]
 pub struct BarType {
     /// The bar types instrument ID.
     pub instrument_id: InstrumentId,
@@ -89,33 +181,88 @@ impl FromStr for BarType {
             });
         }
 
+        BarType::from_pieces(s, rev_pieces[0], &rev_pieces[1..])
+    }
+}
+
+impl BarType {
+    /// Returns the bar type from its individual components, validating each in
+    /// turn.
+    ///
+    /// # Errors
+    ///
+    /// If a component is invalid, returning the error of the first invalid
+    /// component with its position in the standard string form, as for
+    /// [`BarType::from_str`]. A zero `step` is also invalid.
+    pub fn from_parts(
+        symbol: &str,
+        venue: &str,
+        step: usize,
+        aggregation: &str,
+        price_type: &str,
+        aggregation_source: &str,
+    ) -> Result<Self, BarTypeParseError> {
+        let symbol_venue = format!("{symbol}.{venue}");
+        let step = step.to_string();
+        let input =
+            format!("{symbol_venue}-{step}-{aggregation}-{price_type}-{aggregation_source}");
+
+        // Validate the instrument ID components here, as `InstrumentId::from_str` panics
+        if Symbol::new(symbol).is_err() || Venue::new(venue).is_err() {
+            return Err(BarTypeParseError {
+                input,
+                token: symbol_venue,
+                position: 0,
+            });
+        }
+        if step == "0" {
+            return Err(BarTypeParseError {
+                input,
+                token: step,
+                position: 1,
+            });
+        }
+        BarType::from_pieces(
+            &input,
+            &symbol_venue,
+            &[&step, aggregation, price_type, aggregation_source],
+        )
+    }
+
+    /// Returns the bar type parsed from its instrument ID `symbol_venue` and the
+    /// remaining step, aggregation, price type and aggregation source `pieces`,
+    /// reporting errors against the original `input`.
+    fn from_pieces(
+        input: &str,
+        symbol_venue: &str,
+        pieces: &[&str],
+    ) -> Result<Self, BarTypeParseError> {
         let instrument_id =
-            InstrumentId::from_str(rev_pieces[0]).map_err(|_| BarTypeParseError {
-                input: s.to_string(),
-                token: rev_pieces[0].to_string(),
+            InstrumentId::from_str(symbol_venue).map_err(|_| BarTypeParseError {
+                input: input.to_string(),
+                token: symbol_venue.to_string(),
                 position: 0,
             })?;
 
-        let step = rev_pieces[1].parse().map_err(|_| BarTypeParseError {
-            input: s.to_string(),
-            token: rev_pieces[1].to_string(),
+        let step = pieces[0].parse().map_err(|_| BarTypeParseError {
+            input: input.to_string(),
+            token: pieces[0].to_string(),
             position: 1,
         })?;
-        let aggregation =
-            BarAggregation::from_str(rev_pieces[2]).map_err(|_| BarTypeParseError {
-                input: s.to_string(),
-                token: rev_pieces[2].to_string(),
-                position: 2,
-            })?;
-        let price_type = PriceType::from_str(rev_pieces[3]).map_err(|_| BarTypeParseError {
-            input: s.to_string(),
-            token: rev_pieces[3].to_string(),
+        let aggregation = BarAggregation::from_str(pieces[1]).map_err(|_| BarTypeParseError {
+            input: input.to_string(),
+            token: pieces[1].to_string(),
+            position: 2,
+        })?;
+        let price_type = PriceType::from_str(pieces[2]).map_err(|_| BarTypeParseError {
+            input: input.to_string(),
+            token: pieces[2].to_string(),
             position: 3,
         })?;
         let aggregation_source =
-            AggregationSource::from_str(rev_pieces[4]).map_err(|_| BarTypeParseError {
-                input: s.to_string(),
-                token: rev_pieces[4].to_string(),
+            AggregationSource::from_str(pieces[3]).map_err(|_| BarTypeParseError {
+                input: input.to_string(),
+                token: pieces[3].to_string(),
                 position: 4,
             })?;
 
@@ -129,6 +276,81 @@ impl FromStr for BarType {
             aggregation_source,
         })
     }
+
+    /// Returns a human-readable label for the bar type, such as for chart axes,
+    /// prepending the instrument symbol to the specification label, e.g.
+    /// `AUDUSD 1 Min Bid`.
+    #[must_use]
+    pub fn display_label(&self) -> String {
+        format!(
+            "{} {}",
+            self.instrument_id.symbol,
+            self.spec.display_label()
+        )
+    }
+
+    /// Returns the bar type as a REST API path, using `/` as the delimiter and
+    /// percent-encoding the instrument ID, e.g.
+    /// `BTCUSDT-PERP%2EBINANCE/1/MINUTE/LAST/EXTERNAL`.
+    ///
+    /// The `.` separating the symbol and venue, along with any `.`, `/` or `%`
+    /// in the symbol, is encoded so the path round-trips through
+    /// [`BarType::from_uri_path`].
+    #[must_use]
+    pub fn to_uri_path(&self) -> String {
+        let mut instrument_id = String::new();
+        for c in self.instrument_id.to_string().chars() {
+            match c {
+                '%' => instrument_id.push_str("%25"),
+                '.' => instrument_id.push_str("%2E"),
+                '/' => instrument_id.push_str("%2F"),
+                _ => instrument_id.push(c),
+            }
+        }
+        format!(
+            "{}/{}/{}/{}/{}",
+            instrument_id,
+            self.spec.step,
+            self.spec.aggregation,
+            self.spec.price_type,
+            self.aggregation_source
+        )
+    }
+
+    /// Returns the bar type parsed from a REST API path produced by
+    /// [`BarType::to_uri_path`].
+    pub fn from_uri_path(s: &str) -> Result<Self, BarTypeParseError> {
+        let pieces: Vec<&str> = s.split('/').collect();
+        if pieces.len() != 5 {
+            return Err(BarTypeParseError {
+                input: s.to_string(),
+                token: "".to_string(),
+                position: 0,
+            });
+        }
+        let symbol_venue = percent_decode(pieces[0]).ok_or_else(|| BarTypeParseError {
+            input: s.to_string(),
+            token: pieces[0].to_string(),
+            position: 0,
+        })?;
+        BarType::from_pieces(s, &symbol_venue, &pieces[1..])
+    }
+}
+
+/// Returns the percent-decoded `s`, or `None` if it holds an invalid escape.
+fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(b) = iter.next() {
+        if b == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(b);
+        }
+    }
+    String::from_utf8(bytes).ok()
 }
 
 impl Display for BarType {
@@ -160,6 +382,22 @@ impl<'de> Deserialize<'de> for BarType {
     }
 }
 
+/// A [`HashMap`] keyed by [`BarType`] using the FX hasher, for fast lookups on hot
+/// paths such as bar subscription routing.
+#[cfg(feature = "fx_hash")]
+pub type BarTypeFxHashMap<V> = HashMap<BarType, V, BuildHasherDefault<FxHasher>>;
+
+#[cfg(feature = "fx_hash")]
+impl BarType {
+    /// Returns the hash of the bar type using the FX hasher.
+    #[must_use]
+    pub fn fx_hash(&self) -> u64 {
+        let mut hasher = FxHasher::default();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
 #[cfg(feature = "python")]
 #[pymethods]
 impl BarType {
@@ -184,13 +422,131 @@ impl BarType {
     fn __repr__(&self) -> String {
         format!("{self:?}")
     }
+
+    fn __copy__(&self) -> Self {
+        *self
+    }
+
+    fn __deepcopy__(&self, _memo: &PyDict) -> Self {
+        *self
+    }
+
+    fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        let from_str = py.get_type::<Self>().getattr("from_str")?;
+        Ok((from_str, (self.to_string(),)).to_object(py))
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "from_str")]
+    fn py_from_str(value: &str) -> PyResult<Self> {
+        Self::from_str(value).map_err(to_pyvalue_err)
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "from_parts")]
+    fn py_from_parts(
+        symbol: &str,
+        venue: &str,
+        step: usize,
+        aggregation: &str,
+        price_type: &str,
+        aggregation_source: &str,
+    ) -> PyResult<Self> {
+        Self::from_parts(
+            symbol,
+            venue,
+            step,
+            aggregation,
+            price_type,
+            aggregation_source,
+        )
+        .map_err(to_pyvalue_err)
+    }
+
+    #[getter]
+    #[pyo3(name = "display_label")]
+    fn py_display_label(&self) -> String {
+        self.display_label()
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum BarCsvError {
+    #[error("Invalid column count, expected {expected} was {count}")]
+    ColumnCount { expected: usize, count: usize },
+    #[error("Invalid value '{value}' for column '{column}'")]
+    InvalidValue { column: &'static str, value: String },
+    #[error("Invalid UTF-8 in CSV data")]
+    InvalidUtf8,
+    #[error("Error parsing CSV {}", fmt_line_errors(.0))]
+    Lines(Vec<(usize, BarCsvError)>),
+}
+
+fn fmt_line_errors(errors: &[(usize, BarCsvError)]) -> String {
+    errors
+        .iter()
+        .map(|(line, e)| format!("line {line}: {e}"))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum BarBuildError {
+    #[error("Cannot build `Bar` from empty input")]
+    EmptyInput,
+    #[error("Instrument ID mismatch, expected {expected} was {actual}")]
+    InstrumentMismatch {
+        expected: InstrumentId,
+        actual: InstrumentId,
+    },
+    #[error("Input at index {index} was out of order, `ts_event` {ts} was before {prev_ts}")]
+    OutOfOrder {
+        index: usize,
+        ts: UnixNanos,
+        prev_ts: UnixNanos,
+    },
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum BarBatchError {
+    #[error("Bar at index {index} was out of order, `ts_event` {ts} was before {prev_ts}")]
+    OutOfOrder {
+        index: usize,
+        ts: UnixNanos,
+        prev_ts: UnixNanos,
+    },
+}
+
+/// Provides validation of batches of [`Bar`]s before they are processed or stored.
+pub struct BarBatchValidator;
+
+impl BarBatchValidator {
+    /// Checks the `ts_event` of the given `bars` is monotonically non-decreasing.
+    ///
+    /// # Errors
+    ///
+    /// If any bar has a `ts_event` before that of the previous bar, for the
+    /// first such bar.
+    pub fn validate(bars: &[Bar]) -> Result<(), BarBatchError> {
+        match bars.windows(2).position(|w| w[1].ts_event < w[0].ts_event) {
+            Some(i) => Err(BarBatchError::OutOfOrder {
+                index: i + 1,
+                ts: bars[i + 1].ts_event,
+                prev_ts: bars[i].ts_event,
+            }),
+            None => Ok(()),
+        }
+    }
 }
 
+/// The column order used for the CSV representation of a [`Bar`].
+pub const BAR_CSV_COLUMNS: [&str; 6] = ["open", "high", "low", "close", "volume", "ts_event"];
+
 /// Represents an aggregated bar.
 #[repr(C)]
 #[derive(Clone, Copy, Hash, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[serde(tag = "type")]
-#[pyclass]
+#[pyclass(module = "nautilus_trader.core.nautilus_pyo3.model")]
 pub struct Bar {
     /// The bar type for this bar.
     pub bar_type: BarType,
@@ -235,6 +591,256 @@ impl Bar {
         }
     }
 
+    /// Returns the dollar volume of the bar, the close price multiplied by the volume.
+    #[must_use]
+    pub fn dollar_volume(&self) -> f64 {
+        self.close.as_f64() * self.volume.as_f64()
+    }
+
+    /// Returns the log return from the close of `prev` to the close of the bar.
+    #[must_use]
+    pub fn log_return(&self, prev: &Bar) -> f64 {
+        (self.close.as_f64() / prev.close.as_f64()).ln()
+    }
+
+    /// Returns whether the bar closed above its open.
+    #[must_use]
+    pub fn is_bullish(&self) -> bool {
+        self.close > self.open
+    }
+
+    /// Returns whether the bar closed below its open.
+    #[must_use]
+    pub fn is_bearish(&self) -> bool {
+        self.close < self.open
+    }
+
+    /// Returns whether the bar closed within `threshold_ticks` price increments
+    /// of its open, where a price increment is the smallest price change at
+    /// the precision of the close.
+    #[must_use]
+    pub fn is_doji(&self, threshold_ticks: usize) -> bool {
+        let increment = 10_i64.pow(u32::from(
+            FIXED_PRECISION.saturating_sub(self.close.precision),
+        ));
+        let threshold = i64::try_from(threshold_ticks)
+            .ok()
+            .and_then(|ticks| ticks.checked_mul(increment))
+            .unwrap_or(i64::MAX);
+        self.body().raw <= threshold
+    }
+
+    /// Returns the upper shadow (wick) of the bar, `high - max(open, close)`.
+    #[must_use]
+    pub fn upper_shadow(&self) -> Price {
+        self.high - self.open.max(self.close)
+    }
+
+    /// Returns the lower shadow (wick) of the bar, `min(open, close) - low`.
+    #[must_use]
+    pub fn lower_shadow(&self) -> Price {
+        self.open.min(self.close) - self.low
+    }
+
+    /// Returns the body of the bar, `|close - open|`.
+    #[must_use]
+    pub fn body(&self) -> Price {
+        self.open.max(self.close) - self.open.min(self.close)
+    }
+
+    /// Returns the open, high, low, close and volume of the bar as a tuple.
+    #[must_use]
+    pub fn as_ohlcv_tuple(&self) -> (Price, Price, Price, Price, Quantity) {
+        (self.open, self.high, self.low, self.close, self.volume)
+    }
+
+    /// Resamples the given `bars` into bars of the `target` specification.
+    ///
+    /// Each consecutive group of source bars is aggregated into a single
+    /// internally aggregated bar, a trailing incomplete group is discarded.
+    pub fn resample(bars: &[Bar], target: &BarSpecification) -> Result<Vec<Bar>> {
+        let Some(first) = bars.first() else {
+            return Ok(Vec::new());
+        };
+        let source = first.bar_type;
+        let Some(factor) = source.spec.resample_factor(target) else {
+            bail!("Cannot resample bars from {} to {target}", source.spec);
+        };
+        if let Some(bar) = bars.iter().find(|bar| bar.bar_type != source) {
+            bail!(
+                "Cannot resample mixed bar types, expected {source} was {}",
+                bar.bar_type
+            );
+        }
+
+        let bar_type = BarType {
+            instrument_id: source.instrument_id,
+            spec: *target,
+            aggregation_source: AggregationSource::Internal,
+        };
+        let resampled = bars
+            .chunks_exact(factor)
+            .map(|chunk| {
+                let first = chunk[0];
+                let last = chunk[chunk.len() - 1];
+                let mut high = first.high;
+                let mut low = first.low;
+                let mut volume = Quantity::zero(first.volume.precision);
+                for bar in chunk {
+                    high = high.max(bar.high);
+                    low = low.min(bar.low);
+                    volume += bar.volume;
+                }
+                Bar::new(
+                    bar_type,
+                    first.open,
+                    high,
+                    low,
+                    last.close,
+                    volume,
+                    last.ts_event,
+                    last.ts_init,
+                )
+            })
+            .collect();
+
+        Ok(resampled)
+    }
+
+    /// Aggregates the given trade `ticks` into a single [`Bar`] of `bar_type`.
+    ///
+    /// The `ts_event` of the bar is that of the last tick, and `ts_init` is the
+    /// current time. The `ticks` must be in non-decreasing `ts_event` order.
+    pub fn from_trade_ticks(ticks: &[TradeTick], bar_type: BarType) -> Result<Self, BarBuildError> {
+        let (Some(first), Some(last)) = (ticks.first(), ticks.last()) else {
+            return Err(BarBuildError::EmptyInput);
+        };
+        if let Some(tick) = ticks
+            .iter()
+            .find(|tick| tick.instrument_id != bar_type.instrument_id)
+        {
+            return Err(BarBuildError::InstrumentMismatch {
+                expected: bar_type.instrument_id,
+                actual: tick.instrument_id,
+            });
+        }
+        if let Some(i) = ticks.windows(2).position(|w| w[1].ts_event < w[0].ts_event) {
+            return Err(BarBuildError::OutOfOrder {
+                index: i + 1,
+                ts: ticks[i + 1].ts_event,
+                prev_ts: ticks[i].ts_event,
+            });
+        }
+
+        let mut high = first.price;
+        let mut low = first.price;
+        let mut volume = Quantity::zero(first.size.precision);
+        for tick in ticks {
+            high = high.max(tick.price);
+            low = low.min(tick.price);
+            volume += tick.size;
+        }
+
+        Ok(Self::new(
+            bar_type,
+            first.price,
+            high,
+            low,
+            last.price,
+            volume,
+            last.ts_event,
+            duration_since_unix_epoch().as_nanos() as UnixNanos,
+        ))
+    }
+
+    /// Parses a [`Bar`] from a CSV line with the columns
+    /// `open,high,low,close,volume,ts_event`.
+    ///
+    /// The `ts_init` of the bar is set to `ts_event`.
+    pub fn from_csv_line(
+        line: &str,
+        bar_type: &BarType,
+        price_precision: u8,
+        size_precision: u8,
+    ) -> Result<Self, BarCsvError> {
+        let fields: Vec<&str> = line.trim_end_matches('\r').split(',').collect();
+        if fields.len() != BAR_CSV_COLUMNS.len() {
+            return Err(BarCsvError::ColumnCount {
+                expected: BAR_CSV_COLUMNS.len(),
+                count: fields.len(),
+            });
+        }
+
+        let invalid = |i: usize| BarCsvError::InvalidValue {
+            column: BAR_CSV_COLUMNS[i],
+            value: fields[i].to_string(),
+        };
+        let parse_price = |i: usize| {
+            fields[i]
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(|value| Price::new(value, price_precision).ok())
+                .ok_or_else(|| invalid(i))
+        };
+
+        let open = parse_price(0)?;
+        let high = parse_price(1)?;
+        let low = parse_price(2)?;
+        let close = parse_price(3)?;
+        let volume = fields[4]
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .and_then(|value| Quantity::new(value, size_precision).ok())
+            .ok_or_else(|| invalid(4))?;
+        let ts_event: UnixNanos = fields[5].trim().parse().map_err(|_| invalid(5))?;
+
+        Ok(Self::new(
+            *bar_type, open, high, low, close, volume, ts_event, ts_event,
+        ))
+    }
+
+    /// Parses [`Bar`]s from CSV data with one bar per line, see [`Bar::from_csv_line`].
+    ///
+    /// Empty lines are skipped. If any lines are invalid, the errors of all of
+    /// them are returned with their 1-based line numbers.
+    pub fn from_csv_bytes(
+        data: &[u8],
+        bar_type: &BarType,
+        price_precision: u8,
+        size_precision: u8,
+    ) -> Result<Vec<Self>, BarCsvError> {
+        let text = std::str::from_utf8(data).map_err(|_| BarCsvError::InvalidUtf8)?;
+        let mut bars = Vec::new();
+        let mut errors = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match Self::from_csv_line(line, bar_type, price_precision, size_precision) {
+                Ok(bar) => bars.push(bar),
+                Err(e) => errors.push((i + 1, e)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(bars)
+        } else {
+            Err(BarCsvError::Lines(errors))
+        }
+    }
+
+    /// Returns the CSV line representation of the bar with the columns
+    /// `open,high,low,close,volume,ts_event`.
+    #[must_use]
+    pub fn to_csv_line(&self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            self.open, self.high, self.low, self.close, self.volume, self.ts_event
+        )
+    }
+
     /// Returns the metadata for the type, for use with serialization formats.
     pub fn get_metadata(
         bar_type: &BarType,
@@ -319,11 +925,17 @@ impl Bar {
         Self::new(bar_type, open, high, low, close, volume, ts_event, ts_init)
     }
 
+    /// Bars are ordered chronologically by `ts_event`, then by `ts_init`.
     fn __richcmp__(&self, other: &Self, op: CompareOp, py: Python<'_>) -> Py<PyAny> {
+        let lhs = (self.ts_event, self.ts_init);
+        let rhs = (other.ts_event, other.ts_init);
         match op {
             CompareOp::Eq => self.eq(other).into_py(py),
             CompareOp::Ne => self.ne(other).into_py(py),
-            _ => py.NotImplemented(),
+            CompareOp::Lt => (lhs < rhs).into_py(py),
+            CompareOp::Le => (lhs <= rhs).into_py(py),
+            CompareOp::Gt => (lhs > rhs).into_py(py),
+            CompareOp::Ge => (lhs >= rhs).into_py(py),
         }
     }
 
@@ -341,6 +953,29 @@ impl Bar {
         format!("{self:?}")
     }
 
+    fn __copy__(&self) -> Self {
+        *self
+    }
+
+    fn __deepcopy__(&self, _memo: &PyDict) -> Self {
+        *self
+    }
+
+    fn __reduce__(&self, py: Python) -> PyResult<PyObject> {
+        let args = (
+            self.bar_type,
+            self.open,
+            self.high,
+            self.low,
+            self.close,
+            self.volume,
+            self.ts_event,
+            self.ts_init,
+        );
+        let args: Py<PyTuple> = args.into_py(py);
+        Ok((py.get_type::<Self>(), args).to_object(py))
+    }
+
     #[getter]
     fn bar_type(&self) -> BarType {
         self.bar_type
@@ -381,6 +1016,72 @@ impl Bar {
         self.ts_init
     }
 
+    #[getter]
+    #[pyo3(name = "is_bullish")]
+    fn py_is_bullish(&self) -> bool {
+        self.is_bullish()
+    }
+
+    #[getter]
+    #[pyo3(name = "is_bearish")]
+    fn py_is_bearish(&self) -> bool {
+        self.is_bearish()
+    }
+
+    /// Return whether the bar closed within `threshold_ticks` price increments of its open.
+    #[pyo3(name = "is_doji")]
+    fn py_is_doji(&self, threshold_ticks: usize) -> bool {
+        self.is_doji(threshold_ticks)
+    }
+
+    #[getter]
+    #[pyo3(name = "upper_shadow")]
+    fn py_upper_shadow(&self) -> Price {
+        self.upper_shadow()
+    }
+
+    #[getter]
+    #[pyo3(name = "lower_shadow")]
+    fn py_lower_shadow(&self) -> Price {
+        self.lower_shadow()
+    }
+
+    #[getter]
+    #[pyo3(name = "body")]
+    fn py_body(&self) -> Price {
+        self.body()
+    }
+
+    /// Return the (index, pattern) pairs of the candlestick patterns in the
+    /// given bars, where the index is of the last bar of the pattern.
+    #[staticmethod]
+    #[pyo3(name = "scan_patterns")]
+    fn py_scan_patterns(bars: Vec<Bar>) -> Vec<(usize, String)> {
+        scan_patterns(&bars)
+            .into_iter()
+            .map(|(index, pattern)| (index, pattern.to_string()))
+            .collect()
+    }
+
+    /// Return the open, high, low, close and volume of the bar as a tuple.
+    #[pyo3(name = "as_ohlcv_tuple")]
+    fn py_as_ohlcv_tuple(&self) -> (Price, Price, Price, Price, Quantity) {
+        self.as_ohlcv_tuple()
+    }
+
+    /// Return the open, high, low, close and volume of the bar as an `OHLCV`
+    /// named tuple.
+    fn as_named_tuple(&self, py: Python<'_>) -> PyResult<PyObject> {
+        static OHLCV: GILOnceCell<PyObject> = GILOnceCell::new();
+        let ohlcv = OHLCV.get_or_try_init(py, || -> PyResult<PyObject> {
+            let fields = ["open", "high", "low", "close", "volume"];
+            Ok(PyModule::import(py, "collections")?
+                .call_method1("namedtuple", ("OHLCV", fields))?
+                .into())
+        })?;
+        ohlcv.call1(py, self.as_ohlcv_tuple())
+    }
+
     /// Return a dictionary representation of the object.
     pub fn as_dict(&self, py: Python<'_>) -> PyResult<Py<PyDict>> {
         // Serialize object to JSON bytes
@@ -405,6 +1106,22 @@ impl Bar {
         Ok(instance)
     }
 
+    /// Return the metadata for the type, for use with serialization formats.
+    #[staticmethod]
+    #[pyo3(name = "get_metadata")]
+    fn py_get_metadata(
+        py: Python<'_>,
+        bar_type: BarType,
+        price_precision: u8,
+        size_precision: u8,
+    ) -> PyResult<Py<PyDict>> {
+        Ok(
+            Self::get_metadata(&bar_type, price_precision, size_precision)
+                .into_py_dict(py)
+                .into(),
+        )
+    }
+
     #[staticmethod]
     fn from_json(data: Vec<u8>) -> PyResult<Self> {
         Self::from_json_bytes(data).map_err(to_pyvalue_err)
@@ -415,6 +1132,11 @@ impl Bar {
         Self::from_msgpack_bytes(data).map_err(to_pyvalue_err)
     }
 
+    #[staticmethod]
+    fn from_cbor(data: &[u8]) -> PyResult<Self> {
+        Self::from_cbor_bytes(data).map_err(to_pyvalue_err)
+    }
+
     /// Return JSON encoded bytes representation of the object.
     fn as_json(&self, py: Python<'_>) -> Py<PyAny> {
         // Unwrapping is safe when serializing a valid object
@@ -426,36 +1148,189 @@ impl Bar {
         // Unwrapping is safe when serializing a valid object
         self.as_msgpack_bytes().unwrap().into_py(py)
     }
+
+    /// Return CBOR encoded bytes representation of the object.
+    fn as_cbor(&self, py: Python<'_>) -> Py<PyAny> {
+        // Unwrapping is safe when serializing a valid object
+        PyBytes::new(py, &self.as_cbor_bytes().unwrap()).into_py(py)
+    }
+
+    /// Return a `pandas.DataFrame` of the given bars.
+    ///
+    /// The raw fixed-point values and timestamps are copied into pre-allocated
+    /// int64 numpy arrays, with a `bar_type` object column.
+    #[staticmethod]
+    #[pyo3(name = "to_dataframe")]
+    fn py_to_dataframe(py: Python<'_>, bars: Vec<Bar>) -> PyResult<PyObject> {
+        let np = PyModule::import(py, "numpy")?;
+        let pd = PyModule::import(py, "pandas")?;
+
+        let int64_column = |values: Vec<i64>| -> PyResult<&PyAny> {
+            let array = np.call_method1("empty", (values.len(), "int64"))?;
+            PyBuffer::<i64>::get(array)?.copy_from_slice(py, &values)?;
+            Ok(array)
+        };
+        // Unsigned values are checked as they would otherwise wrap to negative
+        let checked_int64_column = |column: &str, values: Vec<u64>| -> PyResult<&PyAny> {
+            let values = values
+                .into_iter()
+                .map(|v| {
+                    i64::try_from(v).map_err(|_| {
+                        PyValueError::new_err(format!("'{column}' value {v} overflows int64"))
+                    })
+                })
+                .collect::<PyResult<Vec<i64>>>()?;
+            int64_column(values)
+        };
+
+        let mut bar_types: Vec<PyObject> = Vec::with_capacity(bars.len());
+        let mut last: Option<(BarType, PyObject)> = None;
+        for bar in &bars {
+            // Share a single Python object across consecutive bars of the same type
+            let obj = match &last {
+                Some((bar_type, obj)) if *bar_type == bar.bar_type => obj.clone_ref(py),
+                _ => {
+                    let obj = bar.bar_type.into_py(py);
+                    last = Some((bar.bar_type, obj.clone_ref(py)));
+                    obj
+                }
+            };
+            bar_types.push(obj);
+        }
+
+        let columns = PyDict::new(py);
+        columns.set_item(
+            "open_raw",
+            int64_column(bars.iter().map(|b| b.open.raw).collect())?,
+        )?;
+        columns.set_item(
+            "high_raw",
+            int64_column(bars.iter().map(|b| b.high.raw).collect())?,
+        )?;
+        columns.set_item(
+            "low_raw",
+            int64_column(bars.iter().map(|b| b.low.raw).collect())?,
+        )?;
+        columns.set_item(
+            "close_raw",
+            int64_column(bars.iter().map(|b| b.close.raw).collect())?,
+        )?;
+        columns.set_item(
+            "volume_raw",
+            checked_int64_column("volume_raw", bars.iter().map(|b| b.volume.raw).collect())?,
+        )?;
+        columns.set_item(
+            "ts_event",
+            checked_int64_column("ts_event", bars.iter().map(|b| b.ts_event).collect())?,
+        )?;
+        columns.set_item(
+            "ts_init",
+            checked_int64_column("ts_init", bars.iter().map(|b| b.ts_init).collect())?,
+        )?;
+        columns.set_item("bar_type", bar_types)?;
+
+        Ok(pd.getattr("DataFrame")?.call1((columns,))?.into_py(py))
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
-// Tests
+// Stubs
 ////////////////////////////////////////////////////////////////////////////////
 #[cfg(test)]
-mod tests {
-    use rstest::rstest;
+pub mod stubs {
+    use rstest::fixture;
 
-    use super::*;
+    use super::{Bar, BarSpecification, BarType};
     use crate::{
-        enums::BarAggregation,
-        identifiers::{symbol::Symbol, venue::Venue},
+        enums::{AggregationSource, BarAggregation, PriceType},
+        identifiers::instrument_id::InstrumentId,
+        types::{price::Price, quantity::Quantity},
     };
 
-    fn create_stub_bar() -> Bar {
-        let instrument_id = InstrumentId {
-            symbol: Symbol::new("AUDUSD").unwrap(),
-            venue: Venue::new("SIM").unwrap(),
-        };
-        let bar_spec = BarSpecification {
-            step: 1,
-            aggregation: BarAggregation::Minute,
-            price_type: PriceType::Bid,
-        };
-        let bar_type = BarType {
-            instrument_id,
-            spec: bar_spec,
-            aggregation_source: AggregationSource::External,
-        };
+    #[fixture]
+    pub fn bar_type_audusd_1_min_bid() -> BarType {
+        BarType {
+            instrument_id: InstrumentId::from("AUDUSD.SIM"),
+            spec: BarSpecification {
+                step: 1,
+                aggregation: BarAggregation::Minute,
+                price_type: PriceType::Bid,
+            },
+            aggregation_source: AggregationSource::External,
+        }
+    }
+
+    #[fixture]
+    pub fn bar_audusd_sim_minute_bid(bar_type_audusd_1_min_bid: BarType) -> Bar {
+        Bar::new(
+            bar_type_audusd_1_min_bid,
+            Price::from("1.00001"),
+            Price::from("1.00004"),
+            Price::from("1.00002"),
+            Price::from("1.00003"),
+            Quantity::from("100000"),
+            0,
+            1,
+        )
+    }
+
+    /// Returns one-minute bars with the given closes, where each bar opens at
+    /// the previous close.
+    pub fn bars_from_closes(closes: &[f64]) -> Vec<Bar> {
+        let bar_type = bar_type_audusd_1_min_bid();
+        let mut prev_close = closes.first().copied().unwrap_or_default();
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| {
+                let open = prev_close;
+                prev_close = close;
+                let ts = (i as u64 + 1) * 60_000_000_000;
+                Bar::new(
+                    bar_type,
+                    Price::new(open, 5).unwrap(),
+                    Price::new(open.max(close), 5).unwrap(),
+                    Price::new(open.min(close), 5).unwrap(),
+                    Price::new(close, 5).unwrap(),
+                    Quantity::from("100000"),
+                    ts,
+                    ts,
+                )
+            })
+            .collect()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::{stubs::*, *};
+    use crate::{
+        enums::{AggressorSide, BarAggregation},
+        identifiers::{symbol::Symbol, trade_id::TradeId, venue::Venue},
+    };
+
+    fn create_stub_bar() -> Bar {
+        let instrument_id = InstrumentId {
+            symbol: Symbol::new("AUDUSD").unwrap(),
+            venue: Venue::new("SIM").unwrap(),
+        };
+        let bar_spec = BarSpecification {
+            step: 1,
+            aggregation: BarAggregation::Minute,
+            price_type: PriceType::Bid,
+        };
+        let bar_type = BarType {
+            instrument_id,
+            spec: bar_spec,
+            aggregation_source: AggregationSource::External,
+        };
         Bar {
             bar_type: bar_type.clone(),
             open: Price::from("1.00001"),
@@ -479,6 +1354,240 @@ mod tests {
         assert_eq!(format!("{bar_spec}"), "1-MINUTE-BID");
     }
 
+    #[rstest]
+    #[case(1, BarAggregation::Minute, 1, BarAggregation::Minute, Some(1))]
+    #[case(1, BarAggregation::Minute, 5, BarAggregation::Minute, Some(5))]
+    #[case(100, BarAggregation::Tick, 1, BarAggregation::Minute, None)]
+    #[case(3, BarAggregation::Minute, 5, BarAggregation::Minute, None)]
+    #[case(10, BarAggregation::Range, 10, BarAggregation::Range, Some(1))]
+    #[case(10, BarAggregation::Range, 20, BarAggregation::Range, None)]
+    #[case(5, BarAggregation::Renko, 5, BarAggregation::Renko, Some(1))]
+    #[case(5, BarAggregation::Renko, 10, BarAggregation::Renko, None)]
+    fn test_bar_spec_compatibility(
+        #[case] source_step: usize,
+        #[case] source_aggregation: BarAggregation,
+        #[case] target_step: usize,
+        #[case] target_aggregation: BarAggregation,
+        #[case] expected: Option<usize>,
+    ) {
+        let source = BarSpecification {
+            step: source_step,
+            aggregation: source_aggregation,
+            price_type: PriceType::Bid,
+        };
+        let target = BarSpecification {
+            step: target_step,
+            aggregation: target_aggregation,
+            price_type: PriceType::Bid,
+        };
+
+        assert_eq!(source.is_compatible_with(&target), expected.is_some());
+        assert_eq!(source.resample_factor(&target), expected);
+    }
+
+    #[rstest]
+    #[case(1, BarAggregation::Millisecond, PriceType::Bid, "1 Ms Bid")]
+    #[case(30, BarAggregation::Second, PriceType::Ask, "30 Sec Ask")]
+    #[case(1, BarAggregation::Minute, PriceType::Bid, "1 Min Bid")]
+    #[case(5, BarAggregation::Minute, PriceType::Ask, "5 Min Ask")]
+    #[case(1, BarAggregation::Hour, PriceType::Mid, "1 Hr Mid")]
+    #[case(1, BarAggregation::Day, PriceType::Last, "1 Day Last")]
+    #[case(1, BarAggregation::Week, PriceType::Last, "1 Week Last")]
+    #[case(1, BarAggregation::Month, PriceType::Mid, "1 Month Mid")]
+    fn test_bar_spec_display_label_time_based(
+        #[case] step: usize,
+        #[case] aggregation: BarAggregation,
+        #[case] price_type: PriceType,
+        #[case] expected: &str,
+    ) {
+        let bar_spec = BarSpecification {
+            step,
+            aggregation,
+            price_type,
+        };
+
+        assert_eq!(bar_spec.display_label(), expected);
+    }
+
+    #[rstest]
+    #[case(100, BarAggregation::Tick, "100 Tick Last")]
+    #[case(50, BarAggregation::TickImbalance, "50 Tick Imbalance Last")]
+    #[case(50, BarAggregation::TickRuns, "50 Tick Runs Last")]
+    #[case(1000, BarAggregation::Volume, "1000 Volume Last")]
+    #[case(1000, BarAggregation::VolumeImbalance, "1000 Volume Imbalance Last")]
+    #[case(1000, BarAggregation::VolumeRuns, "1000 Volume Runs Last")]
+    #[case(10000, BarAggregation::Value, "10000 Value Last")]
+    #[case(10000, BarAggregation::ValueImbalance, "10000 Value Imbalance Last")]
+    #[case(10000, BarAggregation::ValueRuns, "10000 Value Runs Last")]
+    #[case(10, BarAggregation::Range, "10 Range Last")]
+    #[case(5, BarAggregation::Renko, "5 Renko Last")]
+    fn test_bar_spec_display_label_tick_based(
+        #[case] step: usize,
+        #[case] aggregation: BarAggregation,
+        #[case] expected: &str,
+    ) {
+        let bar_spec = BarSpecification {
+            step,
+            aggregation,
+            price_type: PriceType::Last,
+        };
+
+        assert_eq!(bar_spec.display_label(), expected);
+    }
+
+    #[rstest]
+    fn test_bar_type_display_label() {
+        let bar_type = BarType::from_str("BTCUSDT-PERP.BINANCE-1-HOUR-LAST-EXTERNAL").unwrap();
+
+        assert_eq!(bar_type.display_label(), "BTCUSDT-PERP 1 Hr Last");
+    }
+
+    #[rstest]
+    fn test_bar_spec_incompatible_price_type() {
+        let source = BarSpecification {
+            step: 1,
+            aggregation: BarAggregation::Minute,
+            price_type: PriceType::Bid,
+        };
+        let target = BarSpecification {
+            price_type: PriceType::Ask,
+            ..source
+        };
+
+        assert!(!source.is_compatible_with(&target));
+        assert_eq!(source.resample_factor(&target), None);
+    }
+
+    #[rstest]
+    fn test_dollar_volume() {
+        let bar = create_stub_bar();
+
+        assert!(approx_eq!(
+            f64,
+            bar.dollar_volume(),
+            100_003.0,
+            epsilon = 1e-6
+        ));
+    }
+
+    #[rstest]
+    fn test_as_ohlcv_tuple() {
+        let bar = create_stub_bar();
+        let (open, high, low, close, volume) = bar.as_ohlcv_tuple();
+
+        assert_eq!(open, bar.open);
+        assert_eq!(high, bar.high);
+        assert_eq!(low, bar.low);
+        assert_eq!(close, bar.close);
+        assert_eq!(volume, bar.volume);
+    }
+
+    fn create_ohlc_bar(open: &str, high: &str, low: &str, close: &str) -> Bar {
+        Bar::new(
+            bar_type_audusd_1_min_bid(),
+            Price::from(open),
+            Price::from(high),
+            Price::from(low),
+            Price::from(close),
+            Quantity::from("100000"),
+            0,
+            0,
+        )
+    }
+
+    #[rstest]
+    fn test_candle_bullish() {
+        let bar = create_ohlc_bar("1.00010", "1.00050", "1.00000", "1.00040");
+
+        assert!(bar.is_bullish());
+        assert!(!bar.is_bearish());
+        assert!(!bar.is_doji(10));
+        assert!(bar.is_doji(30));
+        assert_eq!(bar.body(), Price::from("0.00030"));
+        assert_eq!(bar.upper_shadow(), Price::from("0.00010"));
+        assert_eq!(bar.lower_shadow(), Price::from("0.00010"));
+    }
+
+    #[rstest]
+    fn test_candle_bearish() {
+        let bar = create_ohlc_bar("1.00040", "1.00045", "1.00000", "1.00020");
+
+        assert!(!bar.is_bullish());
+        assert!(bar.is_bearish());
+        assert_eq!(bar.body(), Price::from("0.00020"));
+        assert_eq!(bar.upper_shadow(), Price::from("0.00005"));
+        assert_eq!(bar.lower_shadow(), Price::from("0.00020"));
+    }
+
+    #[rstest]
+    fn test_candle_flat_bar_is_doji() {
+        let bar = create_ohlc_bar("1.00010", "1.00020", "1.00000", "1.00010");
+
+        assert!(bar.is_doji(0));
+        assert!(!bar.is_bullish());
+        assert!(!bar.is_bearish());
+        assert_eq!(bar.body(), Price::from("0.00000"));
+        assert_eq!(bar.upper_shadow(), Price::from("0.00010"));
+        assert_eq!(bar.lower_shadow(), Price::from("0.00010"));
+    }
+
+    #[rstest]
+    fn test_log_return() {
+        let bars = bars_from_closes(&[1.0, 2.0]);
+
+        assert!(approx_eq!(
+            f64,
+            bars[1].log_return(&bars[0]),
+            2.0_f64.ln(),
+            epsilon = 1e-12
+        ));
+        assert!(approx_eq!(
+            f64,
+            bars[0].log_return(&bars[1]),
+            -2.0_f64.ln(),
+            epsilon = 1e-12
+        ));
+    }
+
+    #[rstest]
+    fn test_bar_resample() {
+        let bars = bars_from_closes(&[1.0, 3.0, 2.0, 4.0, 5.0]);
+        let target = BarSpecification {
+            step: 2,
+            aggregation: BarAggregation::Minute,
+            price_type: PriceType::Bid,
+        };
+        let resampled = Bar::resample(&bars, &target).unwrap();
+
+        assert_eq!(resampled.len(), 2);
+        assert_eq!(resampled[0].bar_type.spec, target);
+        assert_eq!(
+            resampled[0].bar_type.aggregation_source,
+            AggregationSource::Internal
+        );
+        assert_eq!(resampled[0].open, Price::from("1.00000"));
+        assert_eq!(resampled[0].high, Price::from("3.00000"));
+        assert_eq!(resampled[0].low, Price::from("1.00000"));
+        assert_eq!(resampled[0].close, Price::from("3.00000"));
+        assert_eq!(resampled[0].volume, Quantity::from("200000"));
+        assert_eq!(resampled[0].ts_event, bars[1].ts_event);
+        assert_eq!(resampled[1].open, Price::from("3.00000"));
+        assert_eq!(resampled[1].low, Price::from("2.00000"));
+        assert_eq!(resampled[1].close, Price::from("4.00000"));
+    }
+
+    #[rstest]
+    fn test_bar_resample_incompatible_spec() {
+        let bars = bars_from_closes(&[1.0, 2.0, 3.0]);
+        let target = BarSpecification {
+            step: 5,
+            aggregation: BarAggregation::Tick,
+            price_type: PriceType::Bid,
+        };
+
+        assert!(Bar::resample(&bars, &target).is_err());
+    }
+
     #[rstest]
     fn test_bar_type_parse_valid() {
         let input = "BTCUSDT-PERP.BINANCE-1-MINUTE-LAST-EXTERNAL";
@@ -563,6 +1672,100 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn test_bar_type_from_parts() {
+        let bar_type =
+            BarType::from_parts("BTCUSDT-PERP", "BINANCE", 1, "MINUTE", "LAST", "INTERNAL")
+                .unwrap();
+
+        assert_eq!(
+            bar_type,
+            BarType::from_str("BTCUSDT-PERP.BINANCE-1-MINUTE-LAST-INTERNAL").unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case("", "BINANCE", 1, "MINUTE", "LAST", "INTERNAL", (".BINANCE", 0))]
+    #[case("BTCUSDT-PERP", "", 1, "MINUTE", "LAST", "INTERNAL", ("BTCUSDT-PERP.", 0))]
+    #[case("BTCUSDT-PERP", "BINANCE", 0, "MINUTE", "LAST", "INTERNAL", ("0", 1))]
+    #[case("BTCUSDT-PERP", "BINANCE", 1, "INVALID", "LAST", "INTERNAL", ("INVALID", 2))]
+    #[case("BTCUSDT-PERP", "BINANCE", 1, "MINUTE", "INVALID", "INTERNAL", ("INVALID", 3))]
+    #[case("BTCUSDT-PERP", "BINANCE", 1, "MINUTE", "LAST", "INVALID", ("INVALID", 4))]
+    #[case("BTCUSDT-PERP", "BINANCE", 1, "INVALID", "INVALID", "INVALID", ("INVALID", 2))]
+    fn test_bar_type_from_parts_invalid_token(
+        #[case] symbol: &str,
+        #[case] venue: &str,
+        #[case] step: usize,
+        #[case] aggregation: &str,
+        #[case] price_type: &str,
+        #[case] aggregation_source: &str,
+        #[case] expected: (&str, usize),
+    ) {
+        let (token, position) = expected;
+        let result = BarType::from_parts(
+            symbol,
+            venue,
+            step,
+            aggregation,
+            price_type,
+            aggregation_source,
+        );
+
+        let input =
+            format!("{symbol}.{venue}-{step}-{aggregation}-{price_type}-{aggregation_source}");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!(
+                "Error parsing `BarType` from '{input}', invalid token: '{token}' at position {position}"
+            )
+        );
+    }
+
+    #[rstest]
+    fn test_bar_type_to_uri_path() {
+        let bar_type = BarType::from_str("BTCUSDT-PERP.BINANCE-1-MINUTE-LAST-EXTERNAL").unwrap();
+
+        assert_eq!(
+            bar_type.to_uri_path(),
+            "BTCUSDT-PERP%2EBINANCE/1/MINUTE/LAST/EXTERNAL"
+        );
+    }
+
+    #[rstest]
+    #[case("BTCUSDT-PERP.BINANCE-1-MINUTE-LAST-EXTERNAL")]
+    #[case("BTC-USDT.EXCHANGE-5-SECOND-BID-INTERNAL")]
+    #[case("BRK.B.NYSE-100-TICK-LAST-EXTERNAL")]
+    #[case("ES-100%.GLBX-1-DAY-MID-EXTERNAL")]
+    fn test_bar_type_uri_path_round_trip(#[case] input: &str) {
+        let bar_type = BarType::from_str(input).unwrap();
+        let path = bar_type.to_uri_path();
+
+        assert_eq!(path.matches('/').count(), 4);
+        assert!(!path.contains('.'));
+        assert_eq!(BarType::from_uri_path(&path).unwrap(), bar_type);
+    }
+
+    #[rstest]
+    #[case("BTCUSDT-PERP%2EBINANCE/1/MINUTE/LAST", "", 0)]
+    #[case("BTCUSDT-PERP%2/1/MINUTE/LAST/EXTERNAL", "BTCUSDT-PERP%2", 0)]
+    #[case("BTCUSDT-PERP/1/MINUTE/LAST/EXTERNAL", "BTCUSDT-PERP", 0)]
+    #[case("BTCUSDT-PERP%2EBINANCE/X/MINUTE/LAST/EXTERNAL", "X", 1)]
+    #[case("BTCUSDT-PERP%2EBINANCE/1/MINUTE/LAST/INVALID", "INVALID", 4)]
+    fn test_bar_type_from_uri_path_invalid(
+        #[case] input: &str,
+        #[case] token: &str,
+        #[case] position: usize,
+    ) {
+        let result = BarType::from_uri_path(input);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            format!(
+                "Error parsing `BarType` from '{input}', invalid token: '{token}' at position {position}"
+            )
+        );
+    }
+
     #[rstest]
     fn test_bar_type_equality() {
         let instrument_id1 = InstrumentId {
@@ -636,6 +1839,31 @@ mod tests {
         assert!(bar_type3 >= bar_type1);
     }
 
+    #[cfg(feature = "fx_hash")]
+    #[rstest]
+    fn test_bar_type_fx_hash(bar_type_audusd_1_min_bid: BarType) {
+        let other = BarType::from_str("AUDUSD.SIM-1-MINUTE-ASK-EXTERNAL").unwrap();
+
+        assert_eq!(
+            bar_type_audusd_1_min_bid.fx_hash(),
+            bar_type_audusd_1_min_bid.fx_hash()
+        );
+        assert_ne!(bar_type_audusd_1_min_bid.fx_hash(), other.fx_hash());
+    }
+
+    #[cfg(feature = "fx_hash")]
+    #[rstest]
+    fn test_bar_type_fx_hash_map(bar_type_audusd_1_min_bid: BarType) {
+        let other = BarType::from_str("AUDUSD.SIM-1-MINUTE-ASK-EXTERNAL").unwrap();
+        let mut map = BarTypeFxHashMap::default();
+        map.insert(bar_type_audusd_1_min_bid, 1);
+        map.insert(other, 2);
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&bar_type_audusd_1_min_bid], 1);
+        assert_eq!(map.get(&other), Some(&2));
+    }
+
     #[rstest]
     fn test_bar_equality() {
         let instrument_id = InstrumentId {
@@ -703,6 +1931,166 @@ mod tests {
         });
     }
 
+    #[rstest]
+    fn test_py_get_metadata() {
+        pyo3::prepare_freethreaded_python();
+        let bar = create_stub_bar();
+
+        Python::with_gil(|py| {
+            let metadata = Bar::py_get_metadata(py, bar.bar_type, 5, 0).unwrap();
+            let metadata = metadata.as_ref(py);
+
+            assert_eq!(metadata.len(), 4);
+            let bar_type: String = metadata.get_item("bar_type").unwrap().extract().unwrap();
+            assert_eq!(bar_type, "AUDUSD.SIM-1-MINUTE-BID-EXTERNAL");
+            let price_precision: String = metadata
+                .get_item("price_precision")
+                .unwrap()
+                .extract()
+                .unwrap();
+            assert_eq!(price_precision, "5");
+        });
+    }
+
+    #[rstest]
+    fn test_py_get_metadata_as_from_dict_context() {
+        pyo3::prepare_freethreaded_python();
+        let bar = create_stub_bar();
+
+        Python::with_gil(|py| {
+            let metadata = Bar::py_get_metadata(py, bar.bar_type, 5, 0).unwrap();
+            let values = bar.as_dict(py).unwrap();
+            values
+                .as_ref(py)
+                .set_item(
+                    "bar_type",
+                    metadata.as_ref(py).get_item("bar_type").unwrap(),
+                )
+                .unwrap();
+            let parsed = Bar::from_dict(py, values).unwrap();
+
+            assert_eq!(parsed, bar);
+        });
+    }
+
+    #[rstest]
+    fn test_py_copy_and_deepcopy() {
+        pyo3::prepare_freethreaded_python();
+        let bar = create_stub_bar();
+
+        Python::with_gil(|py| {
+            let locals = PyDict::new(py);
+            locals.set_item("bar", bar.into_py(py)).unwrap();
+            py.run(
+                "import copy\n\
+                 assert copy.copy(bar) == bar\n\
+                 assert copy.deepcopy(bar) == bar\n\
+                 assert copy.copy(bar.bar_type) == bar.bar_type\n\
+                 assert copy.deepcopy(bar.bar_type) == bar.bar_type",
+                None,
+                Some(locals),
+            )
+            .unwrap();
+        });
+    }
+
+    #[rstest]
+    fn test_py_reduce_round_trip() {
+        pyo3::prepare_freethreaded_python();
+        let bar = create_stub_bar();
+
+        Python::with_gil(|py| {
+            let locals = PyDict::new(py);
+            locals.set_item("bar", bar.into_py(py)).unwrap();
+            // Reconstructs each object as `pickle` would from its reduced form
+            let rebuilt: Bar = py
+                .eval(
+                    "(lambda f, a: f(*a))(*bar.__reduce__())",
+                    None,
+                    Some(locals),
+                )
+                .unwrap()
+                .extract()
+                .unwrap();
+            let rebuilt_bar_type: BarType = py
+                .eval(
+                    "(lambda f, a: f(*a))(*bar.bar_type.__reduce__())",
+                    None,
+                    Some(locals),
+                )
+                .unwrap()
+                .extract()
+                .unwrap();
+
+            assert_eq!(rebuilt, bar);
+            assert_eq!(rebuilt_bar_type, bar.bar_type);
+        });
+    }
+
+    fn create_stub_bars_py(py: Python<'_>) -> &PyDict {
+        let bars = bars_from_closes(&[1.0, 2.0, 3.0, 4.0]);
+        let mut later = bars[1];
+        later.ts_init += 1;
+        // Shuffled, with `later` sharing `ts_event` with bars[1]
+        let shuffled = vec![bars[2], later, bars[0], bars[3], bars[1]];
+        let locals = PyDict::new(py);
+        locals.set_item("bars", shuffled.into_py(py)).unwrap();
+        locals
+            .set_item(
+                "expected",
+                vec![bars[0], bars[1], later, bars[2], bars[3]].into_py(py),
+            )
+            .unwrap();
+        locals
+    }
+
+    #[rstest]
+    fn test_py_sorted_orders_by_timestamps() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let locals = create_stub_bars_py(py);
+            py.run("assert sorted(bars) == expected", None, Some(locals))
+                .unwrap();
+            py.run(
+                "assert sorted(bars, reverse=True) == expected[::-1]",
+                None,
+                Some(locals),
+            )
+            .unwrap();
+        });
+    }
+
+    #[rstest]
+    fn test_py_min_max() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let locals = create_stub_bars_py(py);
+            py.run(
+                "assert min(bars) == expected[0]\nassert max(bars) == expected[-1]",
+                None,
+                Some(locals),
+            )
+            .unwrap();
+        });
+    }
+
+    #[rstest]
+    fn test_py_heapq() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let locals = create_stub_bars_py(py);
+            py.run(
+                "import heapq\nheap = []\nfor bar in bars:\n    heapq.heappush(heap, bar)\npopped = []\nwhile heap:\n    popped.append(heapq.heappop(heap))\nassert popped == expected",
+                None,
+                Some(locals),
+            )
+            .unwrap();
+        });
+    }
+
     #[rstest]
     fn test_from_pyobject() {
         pyo3::prepare_freethreaded_python();
@@ -715,6 +2103,239 @@ mod tests {
         });
     }
 
+    fn create_stub_trade_ticks(instrument_id: InstrumentId, prices: &[f64]) -> Vec<TradeTick> {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| {
+                TradeTick::new(
+                    instrument_id,
+                    Price::new(price, 5).unwrap(),
+                    Quantity::new((i + 1) as f64, 0).unwrap(),
+                    AggressorSide::Buyer,
+                    TradeId::new(&i.to_string()).unwrap(),
+                    i as u64,
+                    i as u64,
+                )
+            })
+            .collect()
+    }
+
+    #[rstest]
+    fn test_from_trade_ticks(bar_type_audusd_1_min_bid: BarType) {
+        let ticks = create_stub_trade_ticks(
+            bar_type_audusd_1_min_bid.instrument_id,
+            &[1.00002, 1.00005, 1.00001, 1.00003],
+        );
+        let bar = Bar::from_trade_ticks(&ticks, bar_type_audusd_1_min_bid).unwrap();
+
+        assert_eq!(bar.bar_type, bar_type_audusd_1_min_bid);
+        assert_eq!(bar.open, Price::from("1.00002"));
+        assert_eq!(bar.high, Price::from("1.00005"));
+        assert_eq!(bar.low, Price::from("1.00001"));
+        assert_eq!(bar.close, Price::from("1.00003"));
+        assert_eq!(bar.volume, Quantity::from("10"));
+        assert_eq!(bar.ts_event, 3);
+        assert!(bar.ts_init > bar.ts_event);
+    }
+
+    proptest! {
+        #[test]
+        fn test_from_trade_ticks_bounds_hold_for_random_walks(
+            steps in proptest::collection::vec(-100_i64..=100, 1..50)
+        ) {
+            let bar_type = bar_type_audusd_1_min_bid();
+            let mut raw_price: i64 = 100_000;
+            let prices: Vec<f64> = steps
+                .iter()
+                .map(|step| {
+                    raw_price += step;
+                    raw_price as f64 / 100_000.0
+                })
+                .collect();
+            let ticks = create_stub_trade_ticks(bar_type.instrument_id, &prices);
+            let bar = Bar::from_trade_ticks(&ticks, bar_type).unwrap();
+
+            prop_assert_eq!(bar.open, ticks[0].price);
+            prop_assert_eq!(bar.close, ticks[ticks.len() - 1].price);
+            prop_assert!(bar.low <= bar.open && bar.low <= bar.close);
+            prop_assert!(bar.open <= bar.high && bar.close <= bar.high);
+            prop_assert!(ticks.iter().all(|t| bar.low <= t.price && t.price <= bar.high));
+        }
+    }
+
+    #[rstest]
+    fn test_from_trade_ticks_empty_input(bar_type_audusd_1_min_bid: BarType) {
+        let result = Bar::from_trade_ticks(&[], bar_type_audusd_1_min_bid);
+
+        assert_eq!(result.unwrap_err(), BarBuildError::EmptyInput);
+    }
+
+    #[rstest]
+    fn test_from_trade_ticks_instrument_mismatch(bar_type_audusd_1_min_bid: BarType) {
+        let mut ticks =
+            create_stub_trade_ticks(bar_type_audusd_1_min_bid.instrument_id, &[1.0, 1.1]);
+        ticks[1].instrument_id = InstrumentId::from("GBPUSD.SIM");
+        let result = Bar::from_trade_ticks(&ticks, bar_type_audusd_1_min_bid);
+
+        assert_eq!(
+            result.unwrap_err(),
+            BarBuildError::InstrumentMismatch {
+                expected: InstrumentId::from("AUDUSD.SIM"),
+                actual: InstrumentId::from("GBPUSD.SIM"),
+            }
+        );
+    }
+
+    #[rstest]
+    fn test_from_trade_ticks_out_of_order(bar_type_audusd_1_min_bid: BarType) {
+        let mut ticks = create_stub_trade_ticks(
+            bar_type_audusd_1_min_bid.instrument_id,
+            &[1.0, 1.1, 1.2, 1.3],
+        );
+        ticks[2].ts_event = 0;
+        let result = Bar::from_trade_ticks(&ticks, bar_type_audusd_1_min_bid);
+
+        assert_eq!(
+            result.unwrap_err(),
+            BarBuildError::OutOfOrder {
+                index: 2,
+                ts: 0,
+                prev_ts: 1,
+            }
+        );
+    }
+
+    #[rstest]
+    fn test_bar_batch_validator() {
+        let mut bars = bars_from_closes(&[1.0; 5]);
+        bars[2].ts_event = bars[1].ts_event;
+
+        assert_eq!(BarBatchValidator::validate(&bars), Ok(()));
+        assert_eq!(BarBatchValidator::validate(&[]), Ok(()));
+
+        bars[3].ts_event = 0;
+        bars[4].ts_event = 0;
+        assert_eq!(
+            BarBatchValidator::validate(&bars),
+            Err(BarBatchError::OutOfOrder {
+                index: 3,
+                ts: 0,
+                prev_ts: 120_000_000_000,
+            })
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_bar_batch_validator_permutations(
+            timestamps in Just((0..20).map(|i| i / 2).collect::<Vec<UnixNanos>>()).prop_shuffle()
+        ) {
+            let mut bars = bars_from_closes(&[1.0; 20]);
+            for (bar, ts) in bars.iter_mut().zip(&timestamps) {
+                bar.ts_event = *ts;
+            }
+            let expected = (1..timestamps.len()).find(|i| timestamps[*i] < timestamps[i - 1]);
+
+            match (BarBatchValidator::validate(&bars), expected) {
+                (Ok(()), None) => {}
+                (Err(BarBatchError::OutOfOrder { index, ts, prev_ts }), Some(i)) => {
+                    prop_assert_eq!(index, i);
+                    prop_assert_eq!(ts, timestamps[i]);
+                    prop_assert_eq!(prev_ts, timestamps[i - 1]);
+                }
+                (result, expected) => {
+                    prop_assert!(false, "result {result:?} for expected index {expected:?}");
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_csv_line_round_trip() {
+        let bar = create_stub_bar();
+        let line = bar.to_csv_line();
+        let parsed = Bar::from_csv_line(&line, &bar.bar_type, 5, 0).unwrap();
+
+        assert_eq!(line, "1.00001,1.00004,1.00002,1.00003,100000,0");
+        assert_eq!(parsed.open, bar.open);
+        assert_eq!(parsed.high, bar.high);
+        assert_eq!(parsed.low, bar.low);
+        assert_eq!(parsed.close, bar.close);
+        assert_eq!(parsed.volume, bar.volume);
+        assert_eq!(parsed.ts_event, bar.ts_event);
+        assert_eq!(parsed.ts_init, bar.ts_event);
+    }
+
+    #[rstest]
+    fn test_csv_bytes_with_crlf_and_trailing_empty_line() {
+        let bar_type = bar_type_audusd_1_min_bid();
+        let data = b"1.00001,1.00004,1.00002,1.00003,100000,0\r\n1.00003,1.00005,1.00001,1.00002,50000,60000000000\r\n\r\n";
+        let bars = Bar::from_csv_bytes(data, &bar_type, 5, 0).unwrap();
+
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].close, Price::from("1.00003"));
+        assert_eq!(bars[1].close, Price::from("1.00002"));
+        assert_eq!(bars[1].volume, Quantity::from("50000"));
+        assert_eq!(bars[1].ts_event, 60_000_000_000);
+    }
+
+    #[rstest]
+    fn test_csv_bytes_column_count_mismatch() {
+        let bar_type = bar_type_audusd_1_min_bid();
+        let data = b"1.00001,1.00004,1.00002,1.00003,100000,0\n1.00003,1.00005,1.00001,50000,0\n";
+        let result = Bar::from_csv_bytes(data, &bar_type, 5, 0);
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Error parsing CSV line 2: Invalid column count, expected 6 was 5"
+        );
+    }
+
+    #[rstest]
+    fn test_csv_bytes_collects_all_line_errors() {
+        let bar_type = bar_type_audusd_1_min_bid();
+        let data = b"1.00001,abc,1.00002,1.00003,100000,0\n\n1.00003,1.00005,1.00001,1.00002,50000,60000000000\n1.00003,1.00005\n";
+        let result = Bar::from_csv_bytes(data, &bar_type, 5, 0);
+
+        let expected = BarCsvError::Lines(vec![
+            (
+                1,
+                BarCsvError::InvalidValue {
+                    column: "high",
+                    value: "abc".to_string(),
+                },
+            ),
+            (
+                4,
+                BarCsvError::ColumnCount {
+                    expected: 6,
+                    count: 2,
+                },
+            ),
+        ]);
+        assert_eq!(
+            expected.to_string(),
+            "Error parsing CSV line 1: Invalid value 'abc' for column 'high'; \
+             line 4: Invalid column count, expected 6 was 2"
+        );
+        assert_eq!(result, Err(expected));
+    }
+
+    #[rstest]
+    fn test_csv_line_invalid_value() {
+        let bar_type = bar_type_audusd_1_min_bid();
+        let result = Bar::from_csv_line("1.00001,abc,1.00002,1.00003,100000,0", &bar_type, 5, 0);
+
+        assert_eq!(
+            result.unwrap_err(),
+            BarCsvError::InvalidValue {
+                column: "high",
+                value: "abc".to_string(),
+            }
+        );
+    }
+
     #[rstest]
     fn test_json_serialization() {
         let bar = create_stub_bar();
@@ -730,4 +2351,40 @@ mod tests {
         let deserialized = Bar::from_msgpack_bytes(serialized).unwrap();
         assert_eq!(deserialized, bar);
     }
+
+    #[rstest]
+    fn test_cbor_serialization() {
+        let bar = create_stub_bar();
+        let serialized = bar.as_cbor_bytes().unwrap();
+        let deserialized = Bar::from_cbor_bytes(&serialized).unwrap();
+        assert_eq!(deserialized, bar);
+    }
+
+    #[rstest]
+    fn test_cbor_not_larger_than_msgpack() {
+        let bar = create_stub_bar();
+        let cbor = bar.as_cbor_bytes().unwrap();
+        let msgpack = bar.as_msgpack_bytes().unwrap();
+        assert!(cbor.len() <= msgpack.len());
+    }
+
+    #[rstest]
+    fn test_cbor_deserialization_invalid_data() {
+        assert!(Bar::from_cbor_bytes(&[0xff, 0x00, 0x13]).is_err());
+        assert!(Bar::from_cbor_bytes(&[]).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_cbor_decoder_fuzz_never_panics(data in proptest::collection::vec(any::<u8>(), 0..256)) {
+            let _ = Bar::from_cbor_bytes(&data);
+        }
+
+        #[test]
+        fn test_cbor_decoder_fuzz_truncated_never_panics(cut in 0_usize..64) {
+            let bytes = create_stub_bar().as_cbor_bytes().unwrap();
+            let cut = cut.min(bytes.len());
+            let _ = Bar::from_cbor_bytes(&bytes[..cut]);
+        }
+    }
 }
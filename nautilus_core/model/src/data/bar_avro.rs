@@ -0,0 +1,262 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Apache Avro serialization of [`Bar`]s.
+
+use std::str::FromStr;
+
+use apache_avro::{from_avro_datum, schema::Schema, to_avro_datum, types::Value};
+use serde_json::json;
+
+use super::bar::{Bar, BarType};
+use crate::types::{fixed::FIXED_PRECISION, price::Price, quantity::Quantity};
+
+/// The version of the [`Bar`] Avro schema, included in the schema namespace.
+pub const BAR_AVRO_SCHEMA_VERSION: u32 = 1;
+
+lazy_static! {
+    static ref BAR_AVRO_SCHEMA: Schema =
+        Schema::parse(&Bar::avro_schema()).expect("invalid `Bar` Avro schema");
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum AvroError {
+    #[error("Avro error: {0}")]
+    Avro(Box<apache_avro::Error>),
+    #[error("Invalid Avro field '{0}'")]
+    InvalidField(&'static str),
+}
+
+impl From<apache_avro::Error> for AvroError {
+    fn from(e: apache_avro::Error) -> Self {
+        Self::Avro(Box::new(e))
+    }
+}
+
+/// Returns the value of the record field `name`.
+fn field<'a>(fields: &'a [(String, Value)], name: &'static str) -> Result<&'a Value, AvroError> {
+    fields
+        .iter()
+        .find(|(field_name, _)| field_name == name)
+        .map(|(_, value)| value)
+        .ok_or(AvroError::InvalidField(name))
+}
+
+fn long_field(fields: &[(String, Value)], name: &'static str) -> Result<i64, AvroError> {
+    match field(fields, name)? {
+        Value::Long(value) => Ok(*value),
+        _ => Err(AvroError::InvalidField(name)),
+    }
+}
+
+fn precision_field(fields: &[(String, Value)], name: &'static str) -> Result<u8, AvroError> {
+    match field(fields, name)? {
+        Value::Int(value) => u8::try_from(*value)
+            .ok()
+            .filter(|precision| *precision <= FIXED_PRECISION)
+            .ok_or(AvroError::InvalidField(name)),
+        _ => Err(AvroError::InvalidField(name)),
+    }
+}
+
+impl Bar {
+    /// Returns the Avro schema of a bar, with prices and volume as raw
+    /// fixed-point integers.
+    #[must_use]
+    pub fn avro_schema() -> serde_json::Value {
+        json!({
+            "type": "record",
+            "name": "Bar",
+            "namespace": format!("nautilus.model.v{BAR_AVRO_SCHEMA_VERSION}"),
+            "fields": [
+                {"name": "bar_type", "type": "string"},
+                {"name": "open", "type": "long"},
+                {"name": "high", "type": "long"},
+                {"name": "low", "type": "long"},
+                {"name": "close", "type": "long"},
+                {"name": "price_precision", "type": "int"},
+                {"name": "volume", "type": "long"},
+                {"name": "size_precision", "type": "int"},
+                {"name": "ts_event", "type": "long"},
+                {"name": "ts_init", "type": "long"},
+            ],
+        })
+    }
+
+    /// Returns the bar encoded as an Avro datum of [`Bar::avro_schema`],
+    /// without a container header.
+    ///
+    /// # Errors
+    ///
+    /// If the volume or a timestamp exceeds the range of an Avro `long`.
+    pub fn to_avro_bytes(&self) -> Result<Vec<u8>, AvroError> {
+        let long = |name: &'static str, value: u64| {
+            i64::try_from(value)
+                .map(Value::Long)
+                .map_err(|_| AvroError::InvalidField(name))
+        };
+        let record = Value::Record(vec![
+            (
+                "bar_type".to_string(),
+                Value::String(self.bar_type.to_string()),
+            ),
+            ("open".to_string(), Value::Long(self.open.raw)),
+            ("high".to_string(), Value::Long(self.high.raw)),
+            ("low".to_string(), Value::Long(self.low.raw)),
+            ("close".to_string(), Value::Long(self.close.raw)),
+            (
+                "price_precision".to_string(),
+                Value::Int(i32::from(self.open.precision)),
+            ),
+            ("volume".to_string(), long("volume", self.volume.raw)?),
+            (
+                "size_precision".to_string(),
+                Value::Int(i32::from(self.volume.precision)),
+            ),
+            ("ts_event".to_string(), long("ts_event", self.ts_event)?),
+            ("ts_init".to_string(), long("ts_init", self.ts_init)?),
+        ]);
+        Ok(to_avro_datum(&BAR_AVRO_SCHEMA, record)?)
+    }
+
+    /// Returns the bar decoded from an Avro datum of [`Bar::avro_schema`].
+    ///
+    /// # Errors
+    ///
+    /// If the `data` is not a valid datum of the schema.
+    pub fn from_avro_bytes(data: &[u8]) -> Result<Self, AvroError> {
+        Self::decode_avro(data, &BAR_AVRO_SCHEMA)
+    }
+
+    /// Returns the bar decoded from an Avro datum written with the given
+    /// `writer_schema`, such as a later version with additional fields,
+    /// resolved against [`Bar::avro_schema`].
+    ///
+    /// # Errors
+    ///
+    /// If the `writer_schema` is invalid or cannot be resolved, or the `data`
+    /// is not a valid datum of it.
+    pub fn from_avro_bytes_with_schema(
+        data: &[u8],
+        writer_schema: &serde_json::Value,
+    ) -> Result<Self, AvroError> {
+        Self::decode_avro(data, &Schema::parse(writer_schema)?)
+    }
+
+    fn decode_avro(mut data: &[u8], writer_schema: &Schema) -> Result<Self, AvroError> {
+        let value = from_avro_datum(writer_schema, &mut data, Some(&BAR_AVRO_SCHEMA))?;
+        let Value::Record(fields) = value else {
+            return Err(AvroError::InvalidField("bar"));
+        };
+        let bar_type = match field(&fields, "bar_type")? {
+            Value::String(value) => {
+                BarType::from_str(value).map_err(|_| AvroError::InvalidField("bar_type"))?
+            }
+            _ => return Err(AvroError::InvalidField("bar_type")),
+        };
+        let price_precision = precision_field(&fields, "price_precision")?;
+        let size_precision = precision_field(&fields, "size_precision")?;
+        let price =
+            |name| long_field(&fields, name).map(|raw| Price::from_raw(raw, price_precision));
+        let unsigned = |name| {
+            long_field(&fields, name)
+                .and_then(|value| u64::try_from(value).map_err(|_| AvroError::InvalidField(name)))
+        };
+
+        Ok(Self::new(
+            bar_type,
+            price("open")?,
+            price("high")?,
+            price("low")?,
+            price("close")?,
+            Quantity::from_raw(unsigned("volume")?, size_precision),
+            unsigned("ts_event")?,
+            unsigned("ts_init")?,
+        ))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::bars_from_closes;
+
+    #[rstest]
+    fn test_avro_schema_is_valid() {
+        let schema = Bar::avro_schema();
+
+        assert!(Schema::parse(&schema).is_ok());
+        assert_eq!(schema["namespace"], "nautilus.model.v1");
+    }
+
+    #[rstest]
+    fn test_avro_round_trip_is_smaller_than_json() {
+        let closes: Vec<f64> = (0..1_000).map(|i| 1.0 + f64::from(i) * 0.00001).collect();
+        let bars = bars_from_closes(&closes);
+        let mut avro_len = 0;
+        let mut json_len = 0;
+        for bar in &bars {
+            let data = bar.to_avro_bytes().unwrap();
+            avro_len += data.len();
+            json_len += serde_json::to_vec(bar).unwrap().len();
+
+            assert_eq!(Bar::from_avro_bytes(&data).unwrap(), *bar);
+        }
+
+        assert!(avro_len < json_len);
+    }
+
+    #[rstest]
+    fn test_from_avro_bytes_with_forward_compatible_schema() {
+        let bar = bars_from_closes(&[1.0, 1.1])[1];
+        // A later schema with an additional nullable field
+        let mut writer_schema = Bar::avro_schema();
+        writer_schema["fields"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({"name": "vwap", "type": ["null", "long"], "default": null}));
+        let Value::Record(mut fields) = from_avro_datum(
+            &BAR_AVRO_SCHEMA,
+            &mut bar.to_avro_bytes().unwrap().as_slice(),
+            None,
+        )
+        .unwrap() else {
+            panic!("expected a record");
+        };
+        fields.push((
+            "vwap".to_string(),
+            Value::Union(1, Box::new(Value::Long(1_050_000_000))),
+        ));
+        let data = to_avro_datum(
+            &Schema::parse(&writer_schema).unwrap(),
+            Value::Record(fields),
+        )
+        .unwrap();
+        let decoded = Bar::from_avro_bytes_with_schema(&data, &writer_schema).unwrap();
+
+        assert_eq!(decoded, bar);
+    }
+
+    #[rstest]
+    fn test_from_avro_bytes_invalid_data() {
+        assert!(Bar::from_avro_bytes(&[]).is_err());
+        assert!(Bar::from_avro_bytes(&[0x02, 0x41]).is_err());
+    }
+}
@@ -0,0 +1,371 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Bayesian optimization of strategy parameters over series of [`Bar`]s, using
+//! a Gaussian process surrogate of the evaluated metric.
+
+use std::f64::consts::PI;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{
+    bar::Bar,
+    bar_portfolio::{cholesky, cholesky_solve},
+    bar_stats::normal_cdf,
+};
+
+/// The length scale of the squared exponential kernel, over parameters
+/// normalized to the unit hypercube.
+const LENGTH_SCALE: f64 = 0.2;
+
+/// The observation noise added to the kernel diagonal for numerical stability.
+const NOISE: f64 = 1e-6;
+
+/// Represents the acquisition function choosing the next parameters to evaluate
+/// from the Gaussian process posterior.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum AcquisitionFunction {
+    /// The expected improvement over the best observation, plus the margin `xi`.
+    ExpectedImprovement { xi: f64 },
+    /// The probability of improving on the best observation by the margin `xi`.
+    ProbabilityOfImprovement { xi: f64 },
+    /// The posterior mean plus `kappa` posterior standard deviations.
+    UpperConfidenceBound { kappa: f64 },
+}
+
+impl AcquisitionFunction {
+    /// Returns the acquisition value of a posterior `mean` and `std_dev`, given
+    /// the `best` observation so far.
+    fn value(&self, mean: f64, std_dev: f64, best: f64) -> f64 {
+        match *self {
+            Self::ExpectedImprovement { xi } => {
+                let improvement = mean - best - xi;
+                let z = improvement / std_dev;
+                improvement * normal_cdf(z) + std_dev * normal_pdf(z)
+            }
+            Self::ProbabilityOfImprovement { xi } => normal_cdf((mean - best - xi) / std_dev),
+            Self::UpperConfidenceBound { kappa } => mean + kappa * std_dev,
+        }
+    }
+}
+
+/// Provides a Bayesian optimizer of real-valued strategy parameters.
+///
+/// After a number of random initial evaluations, each iteration fits a Gaussian
+/// process to the observations and evaluates the parameters maximizing the
+/// acquisition function over a random sample of candidates.
+#[derive(Clone, Debug)]
+pub struct BayesianOptimizer {
+    n_iterations: usize,
+    acquisition: AcquisitionFunction,
+    n_initial: usize,
+    n_candidates: usize,
+    seed: Option<u64>,
+}
+
+impl BayesianOptimizer {
+    /// Creates a new optimizer making `n_iterations` evaluations in total,
+    /// choosing parameters by the given `acquisition` function.
+    ///
+    /// # Panics
+    ///
+    /// If `n_iterations` is zero.
+    #[must_use]
+    pub fn new(n_iterations: usize, acquisition: AcquisitionFunction) -> Self {
+        assert!(n_iterations > 0, "`n_iterations` was zero");
+        Self {
+            n_iterations,
+            acquisition,
+            n_initial: 5,
+            n_candidates: 1000,
+            seed: None,
+        }
+    }
+
+    /// Sets the number of random evaluations made before fitting the surrogate.
+    ///
+    /// # Panics
+    ///
+    /// If `n_initial` is zero.
+    #[must_use]
+    pub fn with_initial_points(mut self, n_initial: usize) -> Self {
+        assert!(n_initial > 0, "`n_initial` was zero");
+        self.n_initial = n_initial;
+        self
+    }
+
+    /// Sets the number of random candidates the acquisition function is maximized over.
+    ///
+    /// # Panics
+    ///
+    /// If `n_candidates` is zero.
+    #[must_use]
+    pub fn with_candidates(mut self, n_candidates: usize) -> Self {
+        assert!(n_candidates > 0, "`n_candidates` was zero");
+        self.n_candidates = n_candidates;
+        self
+    }
+
+    /// Sets the seed of the random number generator, for reproducible searches.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Returns the parameters within the inclusive (min, max) `bounds` with the
+    /// best metric of the `evaluator` over the `bars` found by the search.
+    ///
+    /// Non-finite metrics are treated as the worst and excluded from the surrogate.
+    pub fn optimize(
+        &self,
+        bars: &[Bar],
+        evaluator: impl Fn(&[f64], &[Bar]) -> f64,
+        bounds: &[(f64, f64)],
+    ) -> Vec<f64> {
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let denormalize = |unit: &[f64]| -> Vec<f64> {
+            unit.iter()
+                .zip(bounds)
+                .map(|(u, (min, max))| min + u * (max - min))
+                .collect()
+        };
+        let random_point =
+            |rng: &mut StdRng| -> Vec<f64> { bounds.iter().map(|_| rng.gen::<f64>()).collect() };
+
+        // Observations are held in the unit hypercube
+        let mut observations: Vec<(Vec<f64>, f64)> = Vec::with_capacity(self.n_iterations);
+        let mut best: Option<(Vec<f64>, f64)> = None;
+
+        for iteration in 0..self.n_iterations {
+            let unit = if iteration < self.n_initial {
+                random_point(&mut rng)
+            } else {
+                match GaussianProcess::fit(&observations) {
+                    Some(gp) => {
+                        let best_y = gp.best_standardized();
+                        (0..self.n_candidates)
+                            .map(|_| {
+                                let candidate = random_point(&mut rng);
+                                let (mean, std_dev) = gp.predict(&candidate);
+                                let score = self.acquisition.value(mean, std_dev, best_y);
+                                (candidate, score)
+                            })
+                            .reduce(|a, b| if b.1 > a.1 { b } else { a })
+                            .map(|(candidate, _)| candidate)
+                            .expect("`n_candidates` was zero")
+                    }
+                    None => random_point(&mut rng),
+                }
+            };
+
+            let params = denormalize(&unit);
+            let metric = evaluator(&params, bars);
+            if !metric.is_finite() {
+                if best.is_none() {
+                    best = Some((params, f64::NEG_INFINITY));
+                }
+                continue;
+            }
+            if best.as_ref().map_or(true, |(_, score)| metric > *score) {
+                best = Some((params, metric));
+            }
+            observations.push((unit, metric));
+        }
+
+        best.expect("`n_iterations` was zero").0
+    }
+}
+
+/// Represents a Gaussian process with a squared exponential kernel, fitted to
+/// standardized observations.
+struct GaussianProcess<'a> {
+    points: Vec<&'a [f64]>,
+    targets: Vec<f64>,
+    l: Vec<f64>,
+    alpha: Vec<f64>,
+}
+
+impl<'a> GaussianProcess<'a> {
+    /// Returns the process fitted to the `observations`, or `None` if there
+    /// are none or the kernel matrix is not positive definite.
+    fn fit(observations: &'a [(Vec<f64>, f64)]) -> Option<Self> {
+        let n = observations.len();
+        if n == 0 {
+            return None;
+        }
+        let mean = observations.iter().map(|(_, y)| y).sum::<f64>() / n as f64;
+        let variance = observations
+            .iter()
+            .map(|(_, y)| (y - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        let std_dev = if variance > 0.0 { variance.sqrt() } else { 1.0 };
+        let targets: Vec<f64> = observations
+            .iter()
+            .map(|(_, y)| (y - mean) / std_dev)
+            .collect();
+        let points: Vec<&[f64]> = observations.iter().map(|(x, _)| x.as_slice()).collect();
+
+        let mut kernel = vec![0.0; n * n];
+        for i in 0..n {
+            for j in 0..n {
+                kernel[i * n + j] = squared_exponential(points[i], points[j]);
+            }
+            kernel[i * n + i] += NOISE;
+        }
+        let l = cholesky(&kernel, n)?;
+        let alpha = cholesky_solve(&l, &targets);
+
+        Some(Self {
+            points,
+            targets,
+            l,
+            alpha,
+        })
+    }
+
+    /// Returns the best standardized observation.
+    fn best_standardized(&self) -> f64 {
+        self.targets
+            .iter()
+            .copied()
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    /// Returns the posterior mean and standard deviation at the point `x`.
+    fn predict(&self, x: &[f64]) -> (f64, f64) {
+        let k: Vec<f64> = self
+            .points
+            .iter()
+            .map(|point| squared_exponential(point, x))
+            .collect();
+        let mean: f64 = k.iter().zip(&self.alpha).map(|(k, a)| k * a).sum();
+        let v = cholesky_solve(&self.l, &k);
+        let variance = 1.0 - k.iter().zip(&v).map(|(k, v)| k * v).sum::<f64>();
+        (mean, variance.max(1e-12).sqrt())
+    }
+}
+
+/// Returns the squared exponential kernel between the points `a` and `b`.
+fn squared_exponential(a: &[f64], b: &[f64]) -> f64 {
+    let distance: f64 = a.iter().zip(b).map(|(a, b)| (a - b).powi(2)).sum();
+    (-distance / (2.0 * LENGTH_SCALE * LENGTH_SCALE)).exp()
+}
+
+/// Returns the standard normal probability density at `x`.
+fn normal_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * PI).sqrt()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::bars_from_closes;
+
+    /// Scores (x, y) by the negative squared distance to the mean close and its negation.
+    fn quadratic(params: &[f64], bars: &[Bar]) -> f64 {
+        let mean = bars.iter().map(|bar| bar.close.as_f64()).sum::<f64>() / bars.len() as f64;
+        -((params[0] - mean).powi(2) + (params[1] + mean).powi(2))
+    }
+
+    #[rstest]
+    #[case(AcquisitionFunction::ExpectedImprovement { xi: 0.01 })]
+    #[case(AcquisitionFunction::ProbabilityOfImprovement { xi: 0.01 })]
+    #[case(AcquisitionFunction::UpperConfidenceBound { kappa: 2.0 })]
+    fn test_optimize_converges(#[case] acquisition: AcquisitionFunction) {
+        let bars = bars_from_closes(&[2.0, 3.0, 4.0]);
+        let optimizer = BayesianOptimizer::new(40, acquisition).with_seed(42);
+        let params = optimizer.optimize(&bars, quadratic, &[(-10.0, 10.0), (-10.0, 10.0)]);
+
+        assert_eq!(params.len(), 2);
+        assert!((params[0] - 3.0).abs() < 1.0);
+        assert!((params[1] + 3.0).abs() < 1.0);
+    }
+
+    #[rstest]
+    fn test_optimize_beats_random_search_with_same_budget() {
+        let bars = bars_from_closes(&[2.0, 3.0, 4.0]);
+        let bounds = [(-10.0, 10.0), (-10.0, 10.0)];
+        let acquisition = AcquisitionFunction::ExpectedImprovement { xi: 0.01 };
+        let bayesian = BayesianOptimizer::new(30, acquisition).with_seed(3);
+        // With every evaluation random, the search degenerates to random search
+        let random = BayesianOptimizer::new(30, acquisition)
+            .with_initial_points(30)
+            .with_seed(3);
+
+        let bayesian_best = quadratic(&bayesian.optimize(&bars, quadratic, &bounds), &bars);
+        let random_best = quadratic(&random.optimize(&bars, quadratic, &bounds), &bars);
+
+        assert!(bayesian_best >= random_best);
+    }
+
+    #[rstest]
+    fn test_optimize_is_reproducible_with_seed() {
+        let bars = bars_from_closes(&[2.0, 3.0, 4.0]);
+        let acquisition = AcquisitionFunction::UpperConfidenceBound { kappa: 1.0 };
+        let optimizer = BayesianOptimizer::new(10, acquisition)
+            .with_candidates(100)
+            .with_seed(7);
+        let bounds = [(-10.0, 10.0), (-10.0, 10.0)];
+
+        assert_eq!(
+            optimizer.optimize(&bars, quadratic, &bounds),
+            optimizer.optimize(&bars, quadratic, &bounds)
+        );
+    }
+
+    #[rstest]
+    fn test_optimize_stays_within_bounds() {
+        let bars = bars_from_closes(&[50.0]);
+        let acquisition = AcquisitionFunction::ExpectedImprovement { xi: 0.0 };
+        let optimizer = BayesianOptimizer::new(20, acquisition).with_seed(1);
+        let params = optimizer.optimize(&bars, quadratic, &[(-10.0, 10.0), (-10.0, 10.0)]);
+
+        assert!((-10.0..=10.0).contains(&params[0]));
+        assert!((-10.0..=10.0).contains(&params[1]));
+    }
+
+    #[rstest]
+    fn test_optimize_ignores_non_finite_metrics() {
+        let bars = bars_from_closes(&[1.0]);
+        let acquisition = AcquisitionFunction::ExpectedImprovement { xi: 0.01 };
+        let optimizer = BayesianOptimizer::new(15, acquisition).with_seed(5);
+        let evaluator = |params: &[f64], _: &[Bar]| {
+            if params[0] < 0.0 {
+                f64::NAN
+            } else {
+                -params[0]
+            }
+        };
+        let params = optimizer.optimize(&bars, evaluator, &[(-1.0, 1.0)]);
+
+        assert!(params[0] >= 0.0);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "`n_iterations` was zero")]
+    fn test_new_with_zero_iterations() {
+        let _ = BayesianOptimizer::new(0, AcquisitionFunction::UpperConfidenceBound { kappa: 1.0 });
+    }
+}
@@ -0,0 +1,134 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Broadcasting of [`Bar`]s to async subscribers over Tokio channels.
+
+use tokio::sync::broadcast::{self, error::SendError};
+pub use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::{wrappers::BroadcastStream, Stream};
+
+use super::bar::Bar;
+
+/// Provides a broadcast channel of bars, for async subscribers to receive every
+/// published bar.
+#[derive(Clone, Debug)]
+pub struct BarBroadcaster {
+    tx: broadcast::Sender<Bar>,
+}
+
+impl BarBroadcaster {
+    /// Creates a new broadcaster buffering up to `capacity` bars per subscriber.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity);
+        Self { tx }
+    }
+
+    /// Publishes the `bar` to all subscribers, returning the number of
+    /// subscribers.
+    ///
+    /// # Errors
+    ///
+    /// If there are no subscribers, returning the unsent bar.
+    #[allow(clippy::result_large_err)] // Returns the unsent bar as the channel does
+    pub fn publish(&self, bar: Bar) -> Result<usize, SendError<Bar>> {
+        self.tx.send(bar)
+    }
+
+    /// Returns a stream of the bars published from now on.
+    ///
+    /// A subscriber which falls behind by more than the channel capacity skips
+    /// the oldest bars, receiving a [`BroadcastStreamRecvError::Lagged`] with the
+    /// number of skipped bars, and continues with the bars still buffered.
+    pub fn subscribe(&self) -> impl Stream<Item = Result<Bar, BroadcastStreamRecvError>> {
+        BroadcastStream::new(self.tx.subscribe())
+    }
+
+    /// Returns a channel receiver of the bars published from now on.
+    #[must_use]
+    pub fn receiver(&self) -> broadcast::Receiver<Bar> {
+        self.tx.subscribe()
+    }
+
+    /// Returns the number of subscribers.
+    #[must_use]
+    pub fn subscriber_count(&self) -> usize {
+        self.tx.receiver_count()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use tokio_stream::StreamExt;
+
+    use super::*;
+    use crate::data::bar::stubs::bars_from_closes;
+
+    #[tokio::test]
+    async fn test_publish_to_subscribers() {
+        let broadcaster = BarBroadcaster::new(16);
+        let bars = bars_from_closes(&[1.0, 1.1, 1.2]);
+        let first = broadcaster.subscribe();
+        let second = broadcaster.subscribe();
+        let mut receiver = broadcaster.receiver();
+
+        assert_eq!(broadcaster.subscriber_count(), 3);
+        for bar in &bars {
+            assert_eq!(broadcaster.publish(*bar).unwrap(), 3);
+        }
+        drop(broadcaster);
+
+        let expected: Vec<_> = bars.iter().copied().map(Ok).collect();
+        assert_eq!(first.collect::<Vec<_>>().await, expected);
+        assert_eq!(second.collect::<Vec<_>>().await, expected);
+        assert_eq!(receiver.recv().await.unwrap(), bars[0]);
+    }
+
+    #[tokio::test]
+    async fn test_publish_without_subscribers() {
+        let broadcaster = BarBroadcaster::new(16);
+        let bar = bars_from_closes(&[1.0])[0];
+
+        assert_eq!(broadcaster.publish(bar).unwrap_err().0, bar);
+    }
+
+    #[tokio::test]
+    async fn test_lagged_subscriber_reports_skipped_bars() {
+        let broadcaster = BarBroadcaster::new(2);
+        let bars = bars_from_closes(&[1.0, 1.1, 1.2, 1.3]);
+        let stream = broadcaster.subscribe();
+
+        for bar in &bars {
+            broadcaster.publish(*bar).unwrap();
+        }
+        drop(broadcaster);
+
+        assert_eq!(
+            stream.collect::<Vec<_>>().await,
+            vec![
+                Err(BroadcastStreamRecvError::Lagged(2)),
+                Ok(bars[2]),
+                Ok(bars[3]),
+            ]
+        );
+    }
+}
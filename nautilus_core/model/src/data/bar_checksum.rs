@@ -0,0 +1,134 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Checksums of [`Bar`]s for data integrity verification.
+
+use super::bar::Bar;
+
+/// The FNV-1a 64-bit offset basis, the checksum of an empty batch.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// The FNV-1a 64-bit prime.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+impl Bar {
+    /// Returns the CRC-32 checksum of the bar, over its bar type and the
+    /// little-endian bytes of its raw values, precisions and timestamps.
+    ///
+    /// The checksum is stable across processes and platforms, and detects any
+    /// single bit corruption.
+    #[must_use]
+    pub fn checksum(&self) -> u32 {
+        let mut hasher = crc32fast::Hasher::new();
+        hasher.update(self.bar_type.to_string().as_bytes());
+        for price in [self.open, self.high, self.low, self.close] {
+            hasher.update(&price.raw.to_le_bytes());
+            hasher.update(&[price.precision]);
+        }
+        hasher.update(&self.volume.raw.to_le_bytes());
+        hasher.update(&[self.volume.precision]);
+        hasher.update(&self.ts_event.to_le_bytes());
+        hasher.update(&self.ts_init.to_le_bytes());
+        hasher.finalize()
+    }
+
+    /// Returns whether the checksum of the bar is the `expected` checksum.
+    #[must_use]
+    pub fn verify_checksum(&self, expected: u32) -> bool {
+        self.checksum() == expected
+    }
+}
+
+/// Returns the checksum of the `bars`, folding their individual checksums in
+/// sequence with FNV-1a so that the result also depends on their order.
+#[must_use]
+pub fn checksum_batch(bars: &[Bar]) -> u64 {
+    bars.iter().fold(FNV_OFFSET_BASIS, |acc, bar| {
+        (acc ^ u64::from(bar.checksum())).wrapping_mul(FNV_PRIME)
+    })
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use nautilus_core::serialization::Serializable;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::*;
+
+    #[rstest]
+    fn test_checksum_is_stable(bar_audusd_sim_minute_bid: Bar) {
+        // The checksum must not depend on the process, such as a random hasher seed
+        assert_eq!(bar_audusd_sim_minute_bid.checksum(), 764_052_491);
+        assert_eq!(
+            checksum_batch(&[bar_audusd_sim_minute_bid]),
+            3_884_291_509_462_504_234
+        );
+    }
+
+    #[rstest]
+    fn test_identical_bars_have_same_checksum(bar_audusd_sim_minute_bid: Bar) {
+        let copy =
+            Bar::from_msgpack_bytes(bar_audusd_sim_minute_bid.as_msgpack_bytes().unwrap()).unwrap();
+
+        assert_eq!(copy.checksum(), bar_audusd_sim_minute_bid.checksum());
+        assert!(copy.verify_checksum(bar_audusd_sim_minute_bid.checksum()));
+    }
+
+    #[rstest]
+    fn test_checksum_detects_single_bit_flips(bar_audusd_sim_minute_bid: Bar) {
+        let expected = bar_audusd_sim_minute_bid.checksum();
+        // The bit width of each field, and a function flipping its masked bits
+        type FieldFlip = (u32, fn(&mut Bar, u64));
+        let flips: [FieldFlip; 12] = [
+            (64, |bar, mask| bar.open.raw ^= mask as i64),
+            (64, |bar, mask| bar.high.raw ^= mask as i64),
+            (64, |bar, mask| bar.low.raw ^= mask as i64),
+            (64, |bar, mask| bar.close.raw ^= mask as i64),
+            (8, |bar, mask| bar.open.precision ^= mask as u8),
+            (8, |bar, mask| bar.high.precision ^= mask as u8),
+            (8, |bar, mask| bar.low.precision ^= mask as u8),
+            (8, |bar, mask| bar.close.precision ^= mask as u8),
+            (64, |bar, mask| bar.volume.raw ^= mask),
+            (8, |bar, mask| bar.volume.precision ^= mask as u8),
+            (64, |bar, mask| bar.ts_event ^= mask),
+            (64, |bar, mask| bar.ts_init ^= mask),
+        ];
+
+        for (field, (bits, flip)) in flips.iter().enumerate() {
+            for bit in 0..*bits {
+                let mut bar = bar_audusd_sim_minute_bid;
+                flip(&mut bar, 1 << bit);
+                assert!(
+                    !bar.verify_checksum(expected),
+                    "flipping bit {bit} of field {field} did not change the checksum"
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_checksum_batch() {
+        let bars = bars_from_closes(&[1.0, 1.1, 1.2]);
+        let mut reversed = bars.clone();
+        reversed.reverse();
+
+        assert_eq!(checksum_batch(&[]), FNV_OFFSET_BASIS);
+        assert_ne!(checksum_batch(&bars), checksum_batch(&reversed));
+        assert_ne!(checksum_batch(&bars), checksum_batch(&bars[..2]));
+    }
+}
@@ -0,0 +1,125 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Batch insertion of [`Bar`]s into the ClickHouse OLAP database.
+
+use super::bar::Bar;
+
+/// The DDL creating the `bars` table, with columns in the order of the tuples
+/// generated by [`bars_to_clickhouse_values`].
+const CLICKHOUSE_DDL: &str = "CREATE TABLE IF NOT EXISTS bars (
+    bar_type LowCardinality(String),
+    open Float64,
+    high Float64,
+    low Float64,
+    close Float64,
+    volume Float64,
+    ts_event UInt64,
+    ts_init UInt64
+)
+ENGINE = MergeTree
+ORDER BY (bar_type, ts_event)";
+
+impl Bar {
+    /// Returns the ClickHouse `CREATE TABLE` DDL for the `bars` table.
+    #[must_use]
+    pub fn clickhouse_ddl() -> &'static str {
+        CLICKHOUSE_DDL
+    }
+
+    /// Returns the ClickHouse tuple literal of the bar, for a `VALUES` clause.
+    #[must_use]
+    pub fn to_clickhouse_tuple(&self) -> String {
+        format!(
+            "('{}',{},{},{},{},{},{},{})",
+            escape_string(&self.bar_type.to_string()),
+            self.open,
+            self.high,
+            self.low,
+            self.close,
+            self.volume,
+            self.ts_event,
+            self.ts_init,
+        )
+    }
+}
+
+/// Returns the string with single quotes and backslashes escaped for use in a
+/// ClickHouse string literal.
+fn escape_string(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+/// Returns the `INSERT INTO bars` statement with a `VALUES` clause of the
+/// `bars`, or an empty string if there are no bars.
+///
+/// The prices and volume are written at their precision for the `Float64`
+/// columns, and the timestamps as UNIX nanoseconds for the `UInt64` columns.
+#[must_use]
+pub fn bars_to_clickhouse_values(bars: &[Bar]) -> String {
+    if bars.is_empty() {
+        return String::new();
+    }
+    let values: Vec<String> = bars.iter().map(Bar::to_clickhouse_tuple).collect();
+    format!(
+        "INSERT INTO bars (bar_type, open, high, low, close, volume, ts_event, ts_init) VALUES {}",
+        values.join(",")
+    )
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::*;
+
+    #[rstest]
+    fn test_bars_to_clickhouse_values(bar_audusd_sim_minute_bid: Bar) {
+        let mut second = bar_audusd_sim_minute_bid;
+        second.ts_event = 60_000_000_000;
+        second.ts_init = 60_000_000_001;
+
+        assert_eq!(
+            bars_to_clickhouse_values(&[bar_audusd_sim_minute_bid, second]),
+            "INSERT INTO bars (bar_type, open, high, low, close, volume, ts_event, ts_init) VALUES \
+             ('AUDUSD.SIM-1-MINUTE-BID-EXTERNAL',1.00001,1.00004,1.00002,1.00003,100000,0,1),\
+             ('AUDUSD.SIM-1-MINUTE-BID-EXTERNAL',1.00001,1.00004,1.00002,1.00003,100000,60000000000,60000000001)"
+        );
+    }
+
+    #[rstest]
+    fn test_bars_to_clickhouse_values_empty() {
+        assert_eq!(bars_to_clickhouse_values(&[]), "");
+    }
+
+    #[rstest]
+    fn test_escape_string() {
+        assert_eq!(escape_string(r"it's a \ test"), r"it\'s a \\ test");
+    }
+
+    #[rstest]
+    fn test_clickhouse_ddl() {
+        let ddl = Bar::clickhouse_ddl();
+
+        assert!(ddl.starts_with("CREATE TABLE IF NOT EXISTS bars"));
+        assert!(ddl.contains("bar_type LowCardinality(String)"));
+        assert!(ddl.contains("close Float64"));
+        assert!(ddl.contains("ts_init UInt64"));
+    }
+}
@@ -0,0 +1,273 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Compact binary encoding of [`BarSpecification`]s and [`BarType`]s for bulk
+//! serialization.
+//!
+//! A bar specification is packed into a single `u32`, with the `step` in the
+//! high 16 bits, then the `aggregation` and `price_type` enum values in the
+//! next 8 bits each.
+//!
+//! A bar type is encoded as, with all integers little-endian:
+//!
+//! | Size | Field                                  |
+//! |------|----------------------------------------|
+//! | 2    | instrument ID length `n` (u16)         |
+//! | `n`  | instrument ID (UTF-8)                  |
+//! | 4    | compact bar specification (u32)        |
+//! | 1    | aggregation source                     |
+
+use std::str::FromStr;
+
+use super::bar::{BarSpecification, BarType};
+use crate::{
+    enums::{AggregationSource, BarAggregation, PriceType},
+    identifiers::instrument_id::InstrumentId,
+};
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum CompactEncodeError {
+    #[error("Step {0} is greater than `u16::MAX`")]
+    StepOverflow(usize),
+    #[error("Instrument ID of {0} bytes is longer than `u16::MAX` bytes")]
+    InstrumentIdTooLong(usize),
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum CompactDecodeError {
+    #[error("Invalid bar aggregation {0}")]
+    InvalidAggregation(u8),
+    #[error("Invalid price type {0}")]
+    InvalidPriceType(u8),
+    #[error("Invalid aggregation source {0}")]
+    InvalidAggregationSource(u8),
+    #[error("Invalid instrument ID '{0}'")]
+    InvalidInstrumentId(String),
+    #[error("Invalid length {len}, expected {expected}")]
+    InvalidLength { len: usize, expected: usize },
+}
+
+impl BarSpecification {
+    /// Returns the specification packed into a `u32`, see the
+    /// [module docs](self) for the layout.
+    ///
+    /// # Errors
+    ///
+    /// If the `step` is greater than [`u16::MAX`].
+    pub fn to_compact(&self) -> Result<u32, CompactEncodeError> {
+        let step =
+            u16::try_from(self.step).map_err(|_| CompactEncodeError::StepOverflow(self.step))?;
+        Ok((u32::from(step) << 16) | ((self.aggregation as u32) << 8) | self.price_type as u32)
+    }
+
+    /// Returns the specification unpacked from its compact encoding.
+    ///
+    /// # Errors
+    ///
+    /// If the aggregation or price type is not a valid enum value.
+    pub fn from_compact(v: u32) -> Result<Self, CompactDecodeError> {
+        let aggregation = ((v >> 8) & 0xFF) as u8;
+        let price_type = (v & 0xFF) as u8;
+        Ok(Self {
+            step: (v >> 16) as usize,
+            aggregation: BarAggregation::from_repr(aggregation as usize)
+                .ok_or(CompactDecodeError::InvalidAggregation(aggregation))?,
+            price_type: PriceType::from_repr(price_type as usize)
+                .ok_or(CompactDecodeError::InvalidPriceType(price_type))?,
+        })
+    }
+}
+
+impl BarType {
+    /// Returns the compact encoding of the bar type, see the
+    /// [module docs](self) for the layout.
+    ///
+    /// # Errors
+    ///
+    /// If the instrument ID is longer than [`u16::MAX`] bytes, or the step is
+    /// greater than [`u16::MAX`].
+    pub fn to_compact_bytes(&self) -> Result<Vec<u8>, CompactEncodeError> {
+        let spec = self.spec.to_compact()?;
+        let instrument_id = self.instrument_id.to_string();
+        let len = u16::try_from(instrument_id.len())
+            .map_err(|_| CompactEncodeError::InstrumentIdTooLong(instrument_id.len()))?;
+        let mut data = Vec::with_capacity(instrument_id.len() + 7);
+        data.extend_from_slice(&len.to_le_bytes());
+        data.extend_from_slice(instrument_id.as_bytes());
+        data.extend_from_slice(&spec.to_le_bytes());
+        data.push(self.aggregation_source as u8);
+        Ok(data)
+    }
+
+    /// Returns the bar type decoded from its compact encoding.
+    ///
+    /// # Errors
+    ///
+    /// If the `data` length does not match the encoded instrument ID length,
+    /// the instrument ID is invalid, or any enum value is invalid.
+    pub fn from_compact_bytes(data: &[u8]) -> Result<Self, CompactDecodeError> {
+        if data.len() < 2 {
+            return Err(CompactDecodeError::InvalidLength {
+                len: data.len(),
+                expected: 7,
+            });
+        }
+        let len = u16::from_le_bytes([data[0], data[1]]) as usize;
+        let expected = len + 7;
+        if data.len() != expected {
+            return Err(CompactDecodeError::InvalidLength {
+                len: data.len(),
+                expected,
+            });
+        }
+
+        let id_bytes = &data[2..2 + len];
+        let instrument_id = std::str::from_utf8(id_bytes)
+            .ok()
+            .and_then(|s| InstrumentId::from_str(s).ok())
+            .ok_or_else(|| {
+                CompactDecodeError::InvalidInstrumentId(
+                    String::from_utf8_lossy(id_bytes).into_owned(),
+                )
+            })?;
+        // Unwrapping is safe as the length was checked above
+        let spec = u32::from_le_bytes(data[2 + len..6 + len].try_into().unwrap());
+        let source = data[6 + len];
+        Ok(Self {
+            instrument_id,
+            spec: BarSpecification::from_compact(spec)?,
+            aggregation_source: AggregationSource::from_repr(source as usize)
+                .ok_or(CompactDecodeError::InvalidAggregationSource(source))?,
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use strum::IntoEnumIterator;
+
+    use super::*;
+    use crate::data::bar::stubs::bar_type_audusd_1_min_bid;
+
+    #[rstest]
+    fn test_to_compact_layout() {
+        let spec = BarSpecification {
+            step: 5,
+            aggregation: BarAggregation::Minute,
+            price_type: PriceType::Bid,
+        };
+
+        assert_eq!(spec.to_compact(), Ok(0x0005_0C01));
+    }
+
+    #[rstest]
+    fn test_compact_round_trip_all_enum_combinations() {
+        for aggregation in BarAggregation::iter() {
+            for price_type in PriceType::iter() {
+                for step in [1, 15, u16::MAX as usize] {
+                    let spec = BarSpecification {
+                        step,
+                        aggregation,
+                        price_type,
+                    };
+
+                    let v = spec.to_compact().unwrap();
+                    assert_eq!(BarSpecification::from_compact(v), Ok(spec));
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    #[case("AUDUSD.SIM-65536-TICK-LAST-EXTERNAL", 65_536)]
+    #[case("AUDUSD.SIM-100000-TICK-LAST-EXTERNAL", 100_000)]
+    #[case("AUDUSD.SIM-1000000-VALUE-LAST-EXTERNAL", 1_000_000)]
+    fn test_to_compact_step_overflow(#[case] bar_type: &str, #[case] step: usize) {
+        let bar_type = BarType::from_str(bar_type).unwrap();
+
+        assert_eq!(
+            bar_type.spec.to_compact(),
+            Err(CompactEncodeError::StepOverflow(step))
+        );
+        assert_eq!(
+            bar_type.to_compact_bytes(),
+            Err(CompactEncodeError::StepOverflow(step))
+        );
+    }
+
+    #[rstest]
+    #[case(0x0001_0001, CompactDecodeError::InvalidAggregation(0))]
+    #[case(0x0001_FF01, CompactDecodeError::InvalidAggregation(255))]
+    #[case(0x0001_0C00, CompactDecodeError::InvalidPriceType(0))]
+    #[case(0x0001_0C05, CompactDecodeError::InvalidPriceType(5))]
+    fn test_from_compact_invalid(#[case] v: u32, #[case] expected: CompactDecodeError) {
+        assert_eq!(BarSpecification::from_compact(v), Err(expected));
+    }
+
+    #[rstest]
+    fn test_bar_type_compact_bytes_round_trip() {
+        for aggregation_source in AggregationSource::iter() {
+            let bar_type = BarType {
+                aggregation_source,
+                ..bar_type_audusd_1_min_bid()
+            };
+            let data = bar_type.to_compact_bytes().unwrap();
+
+            assert_eq!(data.len(), 2 + "AUDUSD.SIM".len() + 5);
+            assert_eq!(BarType::from_compact_bytes(&data), Ok(bar_type));
+        }
+    }
+
+    #[rstest]
+    fn test_bar_type_from_compact_bytes_invalid() {
+        let data = bar_type_audusd_1_min_bid().to_compact_bytes().unwrap();
+
+        assert_eq!(
+            BarType::from_compact_bytes(&data[..data.len() - 1]),
+            Err(CompactDecodeError::InvalidLength {
+                len: data.len() - 1,
+                expected: data.len()
+            })
+        );
+        assert_eq!(
+            BarType::from_compact_bytes(&[1]),
+            Err(CompactDecodeError::InvalidLength {
+                len: 1,
+                expected: 7
+            })
+        );
+
+        let mut invalid_source = data.clone();
+        *invalid_source.last_mut().unwrap() = 9;
+        assert_eq!(
+            BarType::from_compact_bytes(&invalid_source),
+            Err(CompactDecodeError::InvalidAggregationSource(9))
+        );
+
+        let mut invalid_id = vec![6, 0];
+        invalid_id.extend_from_slice(b"AUDUSD");
+        invalid_id.extend_from_slice(&data[data.len() - 5..]);
+        assert_eq!(
+            BarType::from_compact_bytes(&invalid_id),
+            Err(CompactDecodeError::InvalidInstrumentId(
+                "AUDUSD".to_string()
+            ))
+        );
+    }
+}
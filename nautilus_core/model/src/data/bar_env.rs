@@ -0,0 +1,267 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! A Gym style reinforcement learning environment over a series of [`Bar`]s.
+
+use std::fmt::Write;
+
+use super::bar::Bar;
+
+/// The number of features observed for each bar (open, high, low, close and volume).
+pub const BAR_FEATURES: usize = 5;
+
+/// Represents the normalized OHLCV features of the most recent bars, oldest
+/// first, flattened to `lookback * BAR_FEATURES` values.
+pub type Observation = Vec<f64>;
+
+/// Represents an action taken by an agent in a [`BarEnv`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Holds a long position.
+    Buy,
+    /// Holds a short position.
+    Sell,
+    /// Keeps the current position.
+    Hold,
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum BarEnvError {
+    #[error("Invalid lookback, was zero")]
+    ZeroLookback,
+    #[error("Insufficient bars for lookback {lookback}, was {len}")]
+    InsufficientBars { lookback: usize, len: usize },
+}
+
+/// Provides a reinforcement learning environment which steps through a bar
+/// series one bar at a time.
+///
+/// The agent observes the last `lookback` bars, with prices relative to the
+/// latest close and volumes relative to their mean, and is rewarded with the
+/// return of its position (long 1, flat 0 or short -1) over the next bar.
+#[derive(Clone, Debug)]
+pub struct BarEnv {
+    bars: Vec<Bar>,
+    lookback: usize,
+    index: usize,
+    position: f64,
+    total_reward: f64,
+}
+
+impl BarEnv {
+    /// Creates a new environment over the `bars`, observing `lookback` bars.
+    ///
+    /// # Errors
+    ///
+    /// If `lookback` is zero, or there are not more `bars` than `lookback`.
+    pub fn new(bars: Vec<Bar>, lookback: usize) -> Result<Self, BarEnvError> {
+        if lookback == 0 {
+            return Err(BarEnvError::ZeroLookback);
+        }
+        if bars.len() <= lookback {
+            return Err(BarEnvError::InsufficientBars {
+                lookback,
+                len: bars.len(),
+            });
+        }
+        Ok(Self {
+            bars,
+            lookback,
+            index: lookback - 1,
+            position: 0.0,
+            total_reward: 0.0,
+        })
+    }
+
+    /// Returns the current position, 1.0 for long, 0.0 for flat or -1.0 for short.
+    #[must_use]
+    pub fn position(&self) -> f64 {
+        self.position
+    }
+
+    /// Returns the sum of the rewards since the last reset.
+    #[must_use]
+    pub fn total_reward(&self) -> f64 {
+        self.total_reward
+    }
+
+    /// Returns whether the episode is done, with no further bars to step to.
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.index == self.bars.len() - 1
+    }
+
+    /// Resets the environment to the start of the bars with a flat position,
+    /// returning the initial observation.
+    pub fn reset(&mut self) -> Observation {
+        self.index = self.lookback - 1;
+        self.position = 0.0;
+        self.total_reward = 0.0;
+        self.observe()
+    }
+
+    /// Applies the `action` and advances one bar, returning the observation,
+    /// the reward and whether the episode is done.
+    ///
+    /// Once done, the environment stays on the last bar with a zero reward
+    /// until reset.
+    pub fn step(&mut self, action: Action) -> (Observation, f64, bool) {
+        match action {
+            Action::Buy => self.position = 1.0,
+            Action::Sell => self.position = -1.0,
+            Action::Hold => {}
+        }
+        if self.is_done() {
+            return (self.observe(), 0.0, true);
+        }
+
+        self.index += 1;
+        let prev_close = self.bars[self.index - 1].close.as_f64();
+        let close = self.bars[self.index].close.as_f64();
+        let reward = self.position * (close / prev_close - 1.0);
+        self.total_reward += reward;
+        (self.observe(), reward, self.is_done())
+    }
+
+    /// Returns a text rendering of the current state of the environment.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let bar = &self.bars[self.index];
+        let mut output = String::new();
+        let _ = write!(
+            output,
+            "step={} ts_event={} close={} position={} total_reward={:.6}",
+            self.index + 1 - self.lookback,
+            bar.ts_event,
+            bar.close,
+            self.position,
+            self.total_reward,
+        );
+        output
+    }
+
+    fn observe(&self) -> Observation {
+        let window = &self.bars[self.index + 1 - self.lookback..=self.index];
+        let close = window[window.len() - 1].close.as_f64();
+        let mean_volume =
+            window.iter().map(|bar| bar.volume.as_f64()).sum::<f64>() / window.len() as f64;
+
+        let mut observation = Vec::with_capacity(window.len() * BAR_FEATURES);
+        for bar in window {
+            for price in [bar.open, bar.high, bar.low, bar.close] {
+                observation.push(price.as_f64() / close - 1.0);
+            }
+            observation.push(if mean_volume == 0.0 {
+                0.0
+            } else {
+                bar.volume.as_f64() / mean_volume - 1.0
+            });
+        }
+        observation
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::bars_from_closes;
+
+    #[rstest]
+    fn test_reset_observation() {
+        let mut env = BarEnv::new(bars_from_closes(&[1.0, 1.1, 1.21, 1.331]), 2).unwrap();
+        let observation = env.reset();
+
+        assert_eq!(observation.len(), 2 * BAR_FEATURES);
+        // The first bar opened and closed at 1.0, the second from 1.0 to 1.1
+        let expected = [
+            1.0 / 1.1 - 1.0,
+            1.0 / 1.1 - 1.0,
+            1.0 / 1.1 - 1.0,
+            1.0 / 1.1 - 1.0,
+            0.0,
+            1.0 / 1.1 - 1.0,
+            0.0,
+            1.0 / 1.1 - 1.0,
+            0.0,
+            0.0,
+        ];
+        for (value, expected) in observation.iter().zip(expected) {
+            assert!(approx_eq!(f64, *value, expected, epsilon = 1e-9));
+        }
+        assert_eq!(env.position(), 0.0);
+        assert!(!env.is_done());
+    }
+
+    #[rstest]
+    fn test_step_rewards_position_return() {
+        let mut env = BarEnv::new(bars_from_closes(&[1.0, 1.1, 1.21, 1.331]), 2).unwrap();
+        env.reset();
+
+        let (_, reward, done) = env.step(Action::Buy);
+        assert!(approx_eq!(f64, reward, 0.1, epsilon = 1e-9));
+        assert!(!done);
+
+        let (_, reward, done) = env.step(Action::Sell);
+        assert!(approx_eq!(f64, reward, -0.1, epsilon = 1e-9));
+        assert!(done);
+        assert!(approx_eq!(f64, env.total_reward(), 0.0, epsilon = 1e-9));
+
+        // Stepping once done keeps the last bar with no reward
+        let (observation, reward, done) = env.step(Action::Hold);
+        assert_eq!(observation.len(), 2 * BAR_FEATURES);
+        assert_eq!(reward, 0.0);
+        assert!(done);
+    }
+
+    #[rstest]
+    fn test_hold_keeps_position_and_reset_restarts() {
+        let mut env = BarEnv::new(bars_from_closes(&[1.0, 1.1, 1.21, 1.331]), 1).unwrap();
+        env.reset();
+        env.step(Action::Sell);
+        let (_, reward, _) = env.step(Action::Hold);
+
+        assert_eq!(env.position(), -1.0);
+        assert!(approx_eq!(f64, reward, -0.1, epsilon = 1e-9));
+
+        let observation = env.reset();
+        assert_eq!(observation.len(), BAR_FEATURES);
+        assert_eq!(env.position(), 0.0);
+        assert_eq!(env.total_reward(), 0.0);
+        assert!(env.render().starts_with("step=0 "));
+    }
+
+    #[rstest]
+    fn test_new_with_invalid_lookback() {
+        let bars = bars_from_closes(&[1.0, 1.1]);
+
+        assert_eq!(
+            BarEnv::new(bars.clone(), 0).unwrap_err(),
+            BarEnvError::ZeroLookback
+        );
+        assert_eq!(
+            BarEnv::new(bars, 2).unwrap_err(),
+            BarEnvError::InsufficientBars {
+                lookback: 2,
+                len: 2
+            }
+        );
+    }
+}
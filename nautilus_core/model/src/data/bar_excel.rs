@@ -0,0 +1,143 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Export of [`Bar`]s to Excel XLSX worksheets.
+
+use rust_xlsxwriter::{Format, Worksheet, XlsxError};
+
+use super::bar::Bar;
+
+/// The column headers of a worksheet row written by [`Bar::to_xlsx_row`].
+pub const BAR_XLSX_HEADERS: [&str; 8] = [
+    "bar_type", "open", "high", "low", "close", "volume", "ts_event", "ts_init",
+];
+
+/// The number of days from the Excel epoch (1899-12-30) to the UNIX epoch.
+const EXCEL_UNIX_EPOCH_DAYS: f64 = 25_569.0;
+const NANOSECONDS_IN_DAY: f64 = 86_400_000_000_000.0;
+const EXCEL_DATETIME_FORMAT: &str = "yyyy-mm-dd hh:mm:ss.000";
+
+/// Returns the Excel number format showing a value with `precision` decimal places.
+fn decimal_num_format(precision: u8) -> String {
+    if precision == 0 {
+        "0".to_string()
+    } else {
+        format!("0.{}", "0".repeat(precision as usize))
+    }
+}
+
+/// Returns the Excel serial datetime (fractional days since the Excel epoch)
+/// of the UNIX timestamp (nanoseconds) `ts`.
+fn excel_serial_datetime(ts: u64) -> f64 {
+    EXCEL_UNIX_EPOCH_DAYS + ts as f64 / NANOSECONDS_IN_DAY
+}
+
+impl Bar {
+    /// Writes the bar to the given `row` of the `worksheet`, in the column
+    /// order of [`BAR_XLSX_HEADERS`].
+    ///
+    /// The prices and volume are written as numbers formatted to their
+    /// precision, and the timestamps as Excel datetimes with millisecond
+    /// resolution (the finest Excel supports).
+    ///
+    /// # Errors
+    ///
+    /// If the `row` is beyond the worksheet limits.
+    pub fn to_xlsx_row(&self, worksheet: &mut Worksheet, row: u32) -> Result<(), XlsxError> {
+        let price_format = Format::new().set_num_format(decimal_num_format(self.close.precision));
+        let volume_format = Format::new().set_num_format(decimal_num_format(self.volume.precision));
+        let datetime_format = Format::new().set_num_format(EXCEL_DATETIME_FORMAT);
+
+        worksheet.write_string(row, 0, &self.bar_type.to_string())?;
+        for (col, price) in [self.open, self.high, self.low, self.close]
+            .iter()
+            .enumerate()
+        {
+            worksheet.write_number_with_format(
+                row,
+                col as u16 + 1,
+                price.as_f64(),
+                &price_format,
+            )?;
+        }
+        worksheet.write_number_with_format(row, 5, self.volume.as_f64(), &volume_format)?;
+        worksheet.write_number_with_format(
+            row,
+            6,
+            excel_serial_datetime(self.ts_event),
+            &datetime_format,
+        )?;
+        worksheet.write_number_with_format(
+            row,
+            7,
+            excel_serial_datetime(self.ts_init),
+            &datetime_format,
+        )?;
+        Ok(())
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::*;
+
+    #[rstest]
+    #[case(0, "0")]
+    #[case(2, "0.00")]
+    #[case(5, "0.00000")]
+    fn test_decimal_num_format(#[case] precision: u8, #[case] expected: &str) {
+        assert_eq!(decimal_num_format(precision), expected);
+    }
+
+    #[rstest]
+    fn test_excel_serial_datetime() {
+        assert_eq!(excel_serial_datetime(0), 25_569.0);
+        // 2023-01-01 12:00:00 UTC
+        assert!(approx_eq!(
+            f64,
+            excel_serial_datetime(1_672_574_400_000_000_000),
+            44_927.5,
+            epsilon = 1e-9
+        ));
+    }
+
+    #[rstest]
+    fn test_to_xlsx_row(bar_audusd_sim_minute_bid: Bar) {
+        let mut worksheet = Worksheet::new();
+        for (col, header) in BAR_XLSX_HEADERS.iter().enumerate() {
+            worksheet.write_string(0, col as u16, *header).unwrap();
+        }
+
+        assert!(bar_audusd_sim_minute_bid
+            .to_xlsx_row(&mut worksheet, 1)
+            .is_ok());
+    }
+
+    #[rstest]
+    fn test_to_xlsx_row_beyond_worksheet_limits(bar_audusd_sim_minute_bid: Bar) {
+        let mut worksheet = Worksheet::new();
+
+        assert!(bar_audusd_sim_minute_bid
+            .to_xlsx_row(&mut worksheet, u32::MAX)
+            .is_err());
+    }
+}
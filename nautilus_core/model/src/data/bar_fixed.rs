@@ -0,0 +1,184 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Fixed-size binary encoding of [`Bar`]s for low-latency capture.
+//!
+//! Each bar is written as exactly [`BAR_FIXED_SIZE`] bytes with no framing, so
+//! that records can be written to and read from memory-mapped ring buffers at
+//! fixed offsets. The layout is, with all integers little-endian:
+//!
+//! | Offset | Size | Field                  |
+//! |--------|------|------------------------|
+//! | 0      | 1    | magic ([`BAR_FIXED_MAGIC`]) |
+//! | 1      | 1    | `open` precision       |
+//! | 2      | 1    | `high` precision       |
+//! | 3      | 1    | `low` precision        |
+//! | 4      | 1    | `close` precision      |
+//! | 5      | 1    | `volume` precision     |
+//! | 6      | 2    | reserved (zero)        |
+//! | 8      | 8    | `open` raw (i64)       |
+//! | 16     | 8    | `high` raw (i64)       |
+//! | 24     | 8    | `low` raw (i64)        |
+//! | 32     | 8    | `close` raw (i64)      |
+//! | 40     | 8    | `volume` raw (u64)     |
+//! | 48     | 8    | `ts_event` (u64)       |
+//! | 56     | 8    | `ts_init` (u64)        |
+//!
+//! The bar type is not encoded, as a capture buffer holds bars of a single bar
+//! type which is supplied when decoding.
+//!
+//! This encoding is not stable across platforms or builds: the raw values are
+//! scaled by the build's fixed-point precision and the layout may change
+//! between versions. It must not be used for persistence or interchange.
+
+use super::bar::{Bar, BarType};
+use crate::types::{fixed::FIXED_PRECISION, price::Price, quantity::Quantity};
+
+/// The size in bytes of a fixed-size encoded bar.
+pub const BAR_FIXED_SIZE: usize = 64;
+
+/// The magic byte at offset 0 of every fixed-size encoded bar.
+pub const BAR_FIXED_MAGIC: u8 = 0xBA;
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum BarDecodeError {
+    #[error("Invalid magic byte {0:#04x}, expected {BAR_FIXED_MAGIC:#04x}")]
+    InvalidMagic(u8),
+    #[error("Invalid precision {precision} for field '{field}'")]
+    InvalidPrecision { field: &'static str, precision: u8 },
+}
+
+fn read_precision(data: &[u8], offset: usize, field: &'static str) -> Result<u8, BarDecodeError> {
+    let precision = data[offset];
+    if precision > FIXED_PRECISION {
+        return Err(BarDecodeError::InvalidPrecision { field, precision });
+    }
+    Ok(precision)
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn read_i64(data: &[u8], offset: usize) -> i64 {
+    i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+impl Bar {
+    /// Returns the fixed-size encoding of the bar, see the [module docs](self)
+    /// for the layout.
+    #[must_use]
+    pub fn to_fixed_bytes(&self) -> [u8; BAR_FIXED_SIZE] {
+        let mut data = [0; BAR_FIXED_SIZE];
+        data[0] = BAR_FIXED_MAGIC;
+        data[1] = self.open.precision;
+        data[2] = self.high.precision;
+        data[3] = self.low.precision;
+        data[4] = self.close.precision;
+        data[5] = self.volume.precision;
+        data[8..16].copy_from_slice(&self.open.raw.to_le_bytes());
+        data[16..24].copy_from_slice(&self.high.raw.to_le_bytes());
+        data[24..32].copy_from_slice(&self.low.raw.to_le_bytes());
+        data[32..40].copy_from_slice(&self.close.raw.to_le_bytes());
+        data[40..48].copy_from_slice(&self.volume.raw.to_le_bytes());
+        data[48..56].copy_from_slice(&self.ts_event.to_le_bytes());
+        data[56..64].copy_from_slice(&self.ts_init.to_le_bytes());
+        data
+    }
+
+    /// Returns the bar of the given `bar_type` decoded from its fixed-size encoding.
+    ///
+    /// # Errors
+    ///
+    /// If the magic byte is invalid, or any precision is greater than the
+    /// fixed-point precision.
+    pub fn from_fixed_bytes(
+        data: [u8; BAR_FIXED_SIZE],
+        bar_type: BarType,
+    ) -> Result<Self, BarDecodeError> {
+        if data[0] != BAR_FIXED_MAGIC {
+            return Err(BarDecodeError::InvalidMagic(data[0]));
+        }
+        Ok(Self::new(
+            bar_type,
+            Price::from_raw(read_i64(&data, 8), read_precision(&data, 1, "open")?),
+            Price::from_raw(read_i64(&data, 16), read_precision(&data, 2, "high")?),
+            Price::from_raw(read_i64(&data, 24), read_precision(&data, 3, "low")?),
+            Price::from_raw(read_i64(&data, 32), read_precision(&data, 4, "close")?),
+            Quantity::from_raw(read_u64(&data, 40), read_precision(&data, 5, "volume")?),
+            read_u64(&data, 48),
+            read_u64(&data, 56),
+        ))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::bar_audusd_sim_minute_bid;
+
+    #[rstest]
+    fn test_to_fixed_bytes_layout(bar_audusd_sim_minute_bid: Bar) {
+        let bar = bar_audusd_sim_minute_bid;
+        let data = bar.to_fixed_bytes();
+
+        let mut expected = vec![BAR_FIXED_MAGIC, 5, 5, 5, 5, 0, 0, 0];
+        expected.extend_from_slice(&1_000_010_000_i64.to_le_bytes());
+        expected.extend_from_slice(&1_000_040_000_i64.to_le_bytes());
+        expected.extend_from_slice(&1_000_020_000_i64.to_le_bytes());
+        expected.extend_from_slice(&1_000_030_000_i64.to_le_bytes());
+        expected.extend_from_slice(&100_000_000_000_000_u64.to_le_bytes());
+        expected.extend_from_slice(&0_u64.to_le_bytes());
+        expected.extend_from_slice(&1_u64.to_le_bytes());
+        assert_eq!(data.as_slice(), expected.as_slice());
+    }
+
+    #[rstest]
+    fn test_fixed_bytes_round_trip(bar_audusd_sim_minute_bid: Bar) {
+        let bar = bar_audusd_sim_minute_bid;
+        let decoded = Bar::from_fixed_bytes(bar.to_fixed_bytes(), bar.bar_type).unwrap();
+        assert_eq!(decoded, bar);
+    }
+
+    #[rstest]
+    fn test_from_fixed_bytes_invalid_magic(bar_audusd_sim_minute_bid: Bar) {
+        let bar = bar_audusd_sim_minute_bid;
+        let mut data = bar.to_fixed_bytes();
+        data[0] = 0;
+        assert_eq!(
+            Bar::from_fixed_bytes(data, bar.bar_type),
+            Err(BarDecodeError::InvalidMagic(0))
+        );
+    }
+
+    #[rstest]
+    fn test_from_fixed_bytes_invalid_precision(bar_audusd_sim_minute_bid: Bar) {
+        let bar = bar_audusd_sim_minute_bid;
+        let mut data = bar.to_fixed_bytes();
+        data[5] = FIXED_PRECISION + 1;
+        assert_eq!(
+            Bar::from_fixed_bytes(data, bar.bar_type),
+            Err(BarDecodeError::InvalidPrecision {
+                field: "volume",
+                precision: FIXED_PRECISION + 1,
+            })
+        );
+    }
+}
@@ -0,0 +1,277 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Genetic algorithm search of strategy parameters over series of [`Bar`]s.
+
+use std::f64::consts::PI;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{bar::Bar, bar_optimize::Strategy};
+
+/// The fraction of each gene's range used as the standard deviation of mutations.
+const MUTATION_SCALE: f64 = 0.1;
+
+/// Provides the encoding of a strategy's parameters as real-valued genes, for
+/// searching with a [`GeneticOptimizer`].
+pub trait GeneticStrategy: Strategy {
+    /// Returns the inclusive (min, max) bounds of each gene.
+    fn gene_bounds(&self) -> Vec<(f64, f64)>;
+
+    /// Returns the parameters encoded by the `genes`.
+    fn params_from_genes(&self, genes: &[f64]) -> Self::Params;
+}
+
+/// Provides a genetic algorithm optimizer of strategy parameters, using
+/// tournament selection, single-point crossover and Gaussian mutation.
+///
+/// The fittest individual of each generation is carried over unchanged.
+#[derive(Clone, Debug)]
+pub struct GeneticOptimizer {
+    population_size: usize,
+    generations: usize,
+    mutation_rate: f64,
+    tournament_size: usize,
+    seed: Option<u64>,
+}
+
+impl GeneticOptimizer {
+    /// Creates a new optimizer evolving `population_size` individuals over
+    /// `generations`, mutating each gene with probability `mutation_rate`.
+    ///
+    /// # Panics
+    ///
+    /// If `population_size` is less than 2, or `mutation_rate` is not in the
+    /// range [0, 1].
+    #[must_use]
+    pub fn new(population_size: usize, generations: usize, mutation_rate: f64) -> Self {
+        assert!(population_size >= 2, "`population_size` was less than 2");
+        assert!(
+            (0.0..=1.0).contains(&mutation_rate),
+            "`mutation_rate` was not in the range [0, 1]"
+        );
+        Self {
+            population_size,
+            generations,
+            mutation_rate,
+            tournament_size: 3,
+            seed: None,
+        }
+    }
+
+    /// Sets the number of individuals competing in each tournament selection.
+    ///
+    /// # Panics
+    ///
+    /// If `tournament_size` is zero.
+    #[must_use]
+    pub fn with_tournament_size(mut self, tournament_size: usize) -> Self {
+        assert!(tournament_size > 0, "`tournament_size` was zero");
+        self.tournament_size = tournament_size;
+        self
+    }
+
+    /// Sets the seed of the random number generator, for reproducible searches.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Returns the parameters of the `strategy` with the best metric over the
+    /// `bars` found by the search. NaN metrics are treated as the worst.
+    pub fn optimize<S: GeneticStrategy>(&self, strategy: &S, bars: &[Bar]) -> S::Params {
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let bounds = strategy.gene_bounds();
+        let fitness = |genes: &[f64]| {
+            let metric = strategy.evaluate(&strategy.params_from_genes(genes), bars);
+            if metric.is_nan() {
+                f64::NEG_INFINITY
+            } else {
+                metric
+            }
+        };
+
+        let mut population: Vec<(Vec<f64>, f64)> = (0..self.population_size)
+            .map(|_| {
+                let genes: Vec<f64> = bounds
+                    .iter()
+                    .map(|(min, max)| min + rng.gen::<f64>() * (max - min))
+                    .collect();
+                let score = fitness(&genes);
+                (genes, score)
+            })
+            .collect();
+
+        for _ in 0..self.generations {
+            let mut next = Vec::with_capacity(self.population_size);
+            next.push(fittest(&population).clone());
+            while next.len() < self.population_size {
+                let first = &self.tournament(&population, &mut rng).0;
+                let second = &self.tournament(&population, &mut rng).0;
+                let mut genes = crossover(first, second, &mut rng);
+                for (gene, (min, max)) in genes.iter_mut().zip(&bounds) {
+                    if rng.gen::<f64>() < self.mutation_rate {
+                        *gene = (*gene + gaussian(&mut rng) * MUTATION_SCALE * (max - min))
+                            .clamp(*min, *max);
+                    }
+                }
+                let score = fitness(&genes);
+                next.push((genes, score));
+            }
+            population = next;
+        }
+
+        strategy.params_from_genes(&fittest(&population).0)
+    }
+
+    /// Returns the fittest of `tournament_size` individuals drawn at random.
+    fn tournament<'a>(
+        &self,
+        population: &'a [(Vec<f64>, f64)],
+        rng: &mut StdRng,
+    ) -> &'a (Vec<f64>, f64) {
+        let mut best = &population[rng.gen_range(0..population.len())];
+        for _ in 1..self.tournament_size {
+            let candidate = &population[rng.gen_range(0..population.len())];
+            if candidate.1 > best.1 {
+                best = candidate;
+            }
+        }
+        best
+    }
+}
+
+/// Returns the individual with the highest score, the first on ties.
+fn fittest(population: &[(Vec<f64>, f64)]) -> &(Vec<f64>, f64) {
+    population
+        .iter()
+        .reduce(|best, individual| {
+            if individual.1 > best.1 {
+                individual
+            } else {
+                best
+            }
+        })
+        .expect("population was empty")
+}
+
+/// Returns the child taking the genes of `first` before a random crossover
+/// point and the genes of `second` after it.
+fn crossover(first: &[f64], second: &[f64], rng: &mut StdRng) -> Vec<f64> {
+    if first.len() < 2 {
+        return first.to_vec();
+    }
+    let point = rng.gen_range(1..first.len());
+    first[..point]
+        .iter()
+        .chain(&second[point..])
+        .copied()
+        .collect()
+}
+
+/// Returns a sample of the standard normal distribution by the Box-Muller transform.
+fn gaussian(rng: &mut StdRng) -> f64 {
+    let u1 = 1.0 - rng.gen::<f64>();
+    let u2 = rng.gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::bars_from_closes;
+
+    /// Scores each (x, y) by the negative squared distance to the mean close
+    /// and its negation.
+    struct QuadraticStrategy;
+
+    impl Strategy for QuadraticStrategy {
+        type Params = (f64, f64);
+
+        fn candidate_params(&self) -> Vec<(f64, f64)> {
+            Vec::new()
+        }
+
+        fn evaluate(&self, params: &(f64, f64), bars: &[Bar]) -> f64 {
+            let mean = bars.iter().map(|bar| bar.close.as_f64()).sum::<f64>() / bars.len() as f64;
+            -((params.0 - mean).powi(2) + (params.1 + mean).powi(2))
+        }
+    }
+
+    impl GeneticStrategy for QuadraticStrategy {
+        fn gene_bounds(&self) -> Vec<(f64, f64)> {
+            vec![(-10.0, 10.0), (-10.0, 10.0)]
+        }
+
+        fn params_from_genes(&self, genes: &[f64]) -> (f64, f64) {
+            (genes[0], genes[1])
+        }
+    }
+
+    #[rstest]
+    fn test_optimize_converges() {
+        let bars = bars_from_closes(&[2.0, 3.0, 4.0]);
+        let optimizer = GeneticOptimizer::new(50, 50, 0.2).with_seed(42);
+        let (x, y) = optimizer.optimize(&QuadraticStrategy, &bars);
+
+        assert!((x - 3.0).abs() < 0.5);
+        assert!((y + 3.0).abs() < 0.5);
+    }
+
+    #[rstest]
+    fn test_optimize_is_reproducible_with_seed() {
+        let bars = bars_from_closes(&[2.0, 3.0, 4.0]);
+        let optimizer = GeneticOptimizer::new(10, 5, 0.5).with_seed(7);
+
+        assert_eq!(
+            optimizer.optimize(&QuadraticStrategy, &bars),
+            optimizer.optimize(&QuadraticStrategy, &bars)
+        );
+    }
+
+    #[rstest]
+    fn test_optimize_stays_within_bounds() {
+        let bars = bars_from_closes(&[50.0]);
+        let optimizer = GeneticOptimizer::new(20, 20, 1.0)
+            .with_tournament_size(2)
+            .with_seed(1);
+        let (x, y) = optimizer.optimize(&QuadraticStrategy, &bars);
+
+        // The optimum (50, -50) is outside the bounds, so the best is the corner
+        assert!((9.0..=10.0).contains(&x));
+        assert!((-10.0..=-9.0).contains(&y));
+    }
+
+    #[rstest]
+    #[should_panic(expected = "`population_size` was less than 2")]
+    fn test_new_with_invalid_population_size() {
+        let _ = GeneticOptimizer::new(1, 10, 0.1);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "`mutation_rate` was not in the range [0, 1]")]
+    fn test_new_with_invalid_mutation_rate() {
+        let _ = GeneticOptimizer::new(10, 10, 1.5);
+    }
+}
@@ -0,0 +1,191 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! gRPC streaming of [`Bar`]s to subscribers.
+
+use std::pin::Pin;
+
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status};
+
+use super::{
+    bar::{Bar, BarType},
+    bar_broadcast::BarBroadcaster,
+    bar_proto::BarResponse,
+};
+pub use crate::generated::{
+    bar_service_server::{BarService, BarServiceServer},
+    SubscribeBarsRequest,
+};
+
+/// Provides the `BarService` gRPC service, streaming published bars to the
+/// subscribers of their bar type.
+///
+/// Subscribers which fall behind by more than the channel capacity are sent a
+/// `DATA_LOSS` status, ending their stream.
+#[derive(Clone, Debug)]
+pub struct BarStreamServer {
+    broadcaster: BarBroadcaster,
+}
+
+impl BarStreamServer {
+    /// Creates a new server buffering up to `capacity` bars per subscriber.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self::from(BarBroadcaster::new(capacity))
+    }
+
+    /// Publishes the `bar` to the subscribers of its bar type, returning the
+    /// number of subscribers of any bar type.
+    pub fn publish(&self, bar: Bar) -> usize {
+        // Publishing without any subscribers is not an error
+        self.broadcaster.publish(bar).unwrap_or(0)
+    }
+
+    /// Returns the server as a gRPC service, to add to a `tonic` router.
+    #[must_use]
+    pub fn into_service(self) -> BarServiceServer<Self> {
+        BarServiceServer::new(self)
+    }
+}
+
+impl From<BarBroadcaster> for BarStreamServer {
+    /// Creates a new server streaming the bars published to the `broadcaster`.
+    fn from(broadcaster: BarBroadcaster) -> Self {
+        Self { broadcaster }
+    }
+}
+
+#[tonic::async_trait]
+impl BarService for BarStreamServer {
+    type SubscribeBarsStream = Pin<Box<dyn Stream<Item = Result<BarResponse, Status>> + Send>>;
+
+    async fn subscribe_bars(
+        &self,
+        request: Request<SubscribeBarsRequest>,
+    ) -> Result<Response<Self::SubscribeBarsStream>, Status> {
+        let bar_type = request
+            .into_inner()
+            .bar_type
+            .ok_or_else(|| Status::invalid_argument("missing bar type filter"))
+            .and_then(|proto| {
+                BarType::from_proto(proto).map_err(|e| Status::invalid_argument(e.to_string()))
+            })?;
+
+        let stream = self
+            .broadcaster
+            .subscribe()
+            .filter_map(move |result| match result {
+                Ok(bar) if bar.bar_type == bar_type => Some(Ok(bar.to_grpc_bar_response())),
+                Ok(_) => None,
+                Err(e) => Some(Err(Status::data_loss(e.to_string()))),
+            });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use tonic::Code;
+
+    use super::*;
+    use crate::data::bar::stubs::bars_from_closes;
+
+    fn subscribe_request(bar_type: &BarType) -> Request<SubscribeBarsRequest> {
+        Request::new(SubscribeBarsRequest {
+            bar_type: Some(bar_type.to_proto()),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_bars_filters_by_bar_type() {
+        let server = BarStreamServer::new(16);
+        let bars = bars_from_closes(&[1.0, 1.1]);
+        let mut other = bars[0];
+        other.bar_type = BarType::from_str("EURUSD.SIM-1-MINUTE-BID-EXTERNAL").unwrap();
+        let mut stream = server
+            .subscribe_bars(subscribe_request(&bars[0].bar_type))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(server.publish(bars[0]), 1);
+        server.publish(other);
+        server.publish(bars[1]);
+
+        for bar in bars {
+            let response = stream.next().await.unwrap().unwrap();
+            assert_eq!(Bar::from_grpc_bar_response(response), Ok(bar));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_bars_lagged_subscriber() {
+        let server = BarStreamServer::new(1);
+        let bars = bars_from_closes(&[1.0, 1.1, 1.2]);
+        let mut stream = server
+            .subscribe_bars(subscribe_request(&bars[0].bar_type))
+            .await
+            .unwrap()
+            .into_inner();
+
+        for bar in &bars {
+            server.publish(*bar);
+        }
+
+        let status = stream.next().await.unwrap().unwrap_err();
+        assert_eq!(status.code(), Code::DataLoss);
+        let response = stream.next().await.unwrap().unwrap();
+        assert_eq!(Bar::from_grpc_bar_response(response), Ok(bars[2]));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_bars_from_broadcaster() {
+        let broadcaster = BarBroadcaster::new(16);
+        let server = BarStreamServer::from(broadcaster.clone());
+        let bar = bars_from_closes(&[1.0])[0];
+        let mut stream = server
+            .subscribe_bars(subscribe_request(&bar.bar_type))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(broadcaster.publish(bar).unwrap(), 1);
+        let response = stream.next().await.unwrap().unwrap();
+        assert_eq!(Bar::from_grpc_bar_response(response), Ok(bar));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_bars_without_filter() {
+        let server = BarStreamServer::new(16);
+
+        let status = server
+            .subscribe_bars(Request::new(SubscribeBarsRequest { bar_type: None }))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(server.publish(bars_from_closes(&[1.0])[0]), 0);
+    }
+}
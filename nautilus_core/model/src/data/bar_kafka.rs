@@ -0,0 +1,186 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Conversion of [`Bar`]s to and from Kafka message payloads.
+
+use bytes::Bytes;
+use nautilus_core::serialization::Serializable;
+
+use super::bar::Bar;
+
+/// Represents the key, value and headers of a Kafka message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KafkaPayload {
+    /// The message key, used by Kafka to assign the message to a partition.
+    pub key: Bytes,
+    /// The message value.
+    pub value: Bytes,
+    /// The message headers, as (name, value) pairs.
+    pub headers: Vec<(String, Bytes)>,
+}
+
+impl KafkaPayload {
+    /// Returns the value of the first header with the given `name`.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&Bytes> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum KafkaDecodeError {
+    #[error("Failed to decode MsgPack value: {0}")]
+    Decode(#[from] rmp_serde::decode::Error),
+    #[error("Message key '{key}' does not match the bar instrument ID {instrument_id}")]
+    KeyMismatch { key: String, instrument_id: String },
+    #[error("Message header `bar_type` '{header}' does not match the bar type {bar_type}")]
+    BarTypeMismatch { header: String, bar_type: String },
+}
+
+impl Bar {
+    /// Returns the Kafka message payload of the bar.
+    ///
+    /// The key is the instrument ID, so that the bars of an instrument are
+    /// ordered within a single partition, and the value is the MsgPack
+    /// encoded bar. The `bar_type`, `price_precision` and `size_precision`
+    /// headers allow consumers to route and filter messages without decoding
+    /// the value, with the precisions as a single byte each.
+    #[must_use]
+    pub fn to_kafka_payload(&self) -> KafkaPayload {
+        // Unwrapping is safe when serializing a valid object
+        let value = self.as_msgpack_bytes().unwrap();
+        KafkaPayload {
+            key: Bytes::from(self.bar_type.instrument_id.to_string()),
+            value: Bytes::from(value),
+            headers: vec![
+                (
+                    "bar_type".to_string(),
+                    Bytes::from(self.bar_type.to_string()),
+                ),
+                (
+                    "price_precision".to_string(),
+                    Bytes::from(vec![self.close.precision]),
+                ),
+                (
+                    "size_precision".to_string(),
+                    Bytes::from(vec![self.volume.precision]),
+                ),
+            ],
+        }
+    }
+
+    /// Returns the bar decoded from a Kafka message `payload`, as produced by
+    /// [`Bar::to_kafka_payload`].
+    ///
+    /// # Errors
+    ///
+    /// If the value cannot be decoded, or the key or any `bar_type` header
+    /// does not match the decoded bar.
+    pub fn from_kafka_payload(payload: &KafkaPayload) -> Result<Self, KafkaDecodeError> {
+        let bar = Self::from_msgpack_bytes(payload.value.to_vec())?;
+
+        let instrument_id = bar.bar_type.instrument_id.to_string();
+        if payload.key != instrument_id.as_bytes() {
+            return Err(KafkaDecodeError::KeyMismatch {
+                key: String::from_utf8_lossy(&payload.key).into_owned(),
+                instrument_id,
+            });
+        }
+        if let Some(header) = payload.header("bar_type") {
+            let bar_type = bar.bar_type.to_string();
+            if header != bar_type.as_bytes() {
+                return Err(KafkaDecodeError::BarTypeMismatch {
+                    header: String::from_utf8_lossy(header).into_owned(),
+                    bar_type,
+                });
+            }
+        }
+        Ok(bar)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::*;
+
+    #[rstest]
+    fn test_to_kafka_payload(bar_audusd_sim_minute_bid: Bar) {
+        let payload = bar_audusd_sim_minute_bid.to_kafka_payload();
+
+        assert_eq!(payload.key, Bytes::from_static(b"AUDUSD.SIM"));
+        assert_eq!(
+            payload.header("bar_type"),
+            Some(&Bytes::from_static(b"AUDUSD.SIM-1-MINUTE-BID-EXTERNAL"))
+        );
+        assert_eq!(
+            payload.header("price_precision"),
+            Some(&Bytes::from_static(&[5]))
+        );
+        assert_eq!(
+            payload.header("size_precision"),
+            Some(&Bytes::from_static(&[0]))
+        );
+        assert_eq!(payload.header("missing"), None);
+    }
+
+    #[rstest]
+    fn test_kafka_payload_round_trip(bar_audusd_sim_minute_bid: Bar) {
+        let payload = bar_audusd_sim_minute_bid.to_kafka_payload();
+
+        assert_eq!(
+            Bar::from_kafka_payload(&payload).unwrap(),
+            bar_audusd_sim_minute_bid
+        );
+    }
+
+    #[rstest]
+    fn test_from_kafka_payload_mismatches(bar_audusd_sim_minute_bid: Bar) {
+        let payload = bar_audusd_sim_minute_bid.to_kafka_payload();
+
+        let wrong_key = KafkaPayload {
+            key: Bytes::from_static(b"EURUSD.SIM"),
+            ..payload.clone()
+        };
+        assert!(matches!(
+            Bar::from_kafka_payload(&wrong_key),
+            Err(KafkaDecodeError::KeyMismatch { .. })
+        ));
+
+        let mut wrong_bar_type = payload.clone();
+        wrong_bar_type.headers[0].1 = Bytes::from_static(b"AUDUSD.SIM-5-MINUTE-BID-EXTERNAL");
+        assert!(matches!(
+            Bar::from_kafka_payload(&wrong_bar_type),
+            Err(KafkaDecodeError::BarTypeMismatch { .. })
+        ));
+
+        let invalid_value = KafkaPayload {
+            value: Bytes::from_static(b"invalid"),
+            ..payload
+        };
+        assert!(matches!(
+            Bar::from_kafka_payload(&invalid_value),
+            Err(KafkaDecodeError::Decode(_))
+        ));
+    }
+}
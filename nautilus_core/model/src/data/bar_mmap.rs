@@ -0,0 +1,568 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Memory-mapped files of [`Bar`]s for high-throughput sequential and random access.
+//!
+//! A file holds bars of a single bar type, as a [`BAR_MMAP_HEADER_SIZE`] byte
+//! header followed by the bars as fixed-size records (see
+//! [`bar_fixed`](super::bar_fixed)), so the record at index `i` is at offset
+//! `BAR_MMAP_HEADER_SIZE + i * BAR_FIXED_SIZE`. The header layout is, with all
+//! integers little-endian:
+//!
+//! | Offset | Size | Field                                          |
+//! |--------|------|------------------------------------------------|
+//! | 0      | 8    | magic ([`BAR_MMAP_MAGIC`])                     |
+//! | 8      | 1    | price precision                                |
+//! | 9      | 1    | size precision                                 |
+//! | 10     | 6    | reserved (zero)                                |
+//! | 16     | 8    | record count (u64)                             |
+//! | 24     | ...  | compact bar type (see [`bar_compact`](super::bar_compact)), empty if no bars |
+//!
+//! As the records use the fixed-size encoding, the files share its caveats
+//! and are not portable across builds.
+
+use std::{
+    fs::{File, OpenOptions},
+    path::Path,
+};
+
+use memmap2::{Mmap, MmapMut};
+
+use super::{
+    bar::{Bar, BarType},
+    bar_compact::{CompactDecodeError, CompactEncodeError},
+    bar_fixed::{BarDecodeError, BAR_FIXED_SIZE},
+};
+
+/// The size in bytes of the header of a bar file.
+pub const BAR_MMAP_HEADER_SIZE: usize = 256;
+
+/// The magic bytes at offset 0 of every bar file.
+pub const BAR_MMAP_MAGIC: [u8; 8] = *b"NTBARS01";
+
+const RECORD_COUNT_OFFSET: usize = 16;
+const BAR_TYPE_OFFSET: usize = 24;
+
+#[derive(thiserror::Error, Debug)]
+pub enum BarMmapError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid magic bytes, not a bar file")]
+    InvalidMagic,
+    #[error("Invalid file length {len}, expected at least {expected}")]
+    InvalidLength { len: usize, expected: usize },
+    #[error("Invalid bar type: {0}")]
+    InvalidBarType(#[from] CompactDecodeError),
+    #[error("Cannot encode bar type: {0}")]
+    BarTypeEncoding(#[from] CompactEncodeError),
+    #[error("Encoded bar type of {0} bytes does not fit in the header")]
+    BarTypeTooLong(usize),
+    #[error("Capacity of {0} bars exceeded")]
+    CapacityExceeded(usize),
+    #[error("Capacity of {0} bars is too large")]
+    CapacityTooLarge(usize),
+    #[error("Invalid record at index {index}: {source}")]
+    InvalidRecord {
+        index: usize,
+        source: BarDecodeError,
+    },
+    #[error("Bar type {found} does not match the file bar type {expected}")]
+    BarTypeMismatch {
+        expected: Box<BarType>,
+        found: Box<BarType>,
+    },
+    #[error("Bar precisions ({price}, {size}) do not match the file precisions ({expected_price}, {expected_size})")]
+    PrecisionMismatch {
+        price: u8,
+        size: u8,
+        expected_price: u8,
+        expected_size: u8,
+    },
+}
+
+/// Returns the file offset of the record at `index`, or `None` on overflow.
+fn record_offset(index: usize) -> Option<usize> {
+    index
+        .checked_mul(BAR_FIXED_SIZE)?
+        .checked_add(BAR_MMAP_HEADER_SIZE)
+}
+
+/// Provides a writer of bars to a memory-mapped file, preallocated for a
+/// fixed capacity of bars.
+///
+/// The bar type and precisions of the file are taken from the first bar
+/// written, and every following bar must match them. Call
+/// [`BarMmapWriter::finish`] once all bars are written to flush the map and
+/// truncate the file to the bars written.
+#[derive(Debug)]
+pub struct BarMmapWriter {
+    file: File,
+    mmap: MmapMut,
+    capacity: usize,
+    count: usize,
+    bar_type: Option<BarType>,
+    price_precision: u8,
+    size_precision: u8,
+}
+
+impl BarMmapWriter {
+    /// Creates a new writer to the file at `path`, truncating any existing
+    /// file and preallocating space for `capacity` bars.
+    ///
+    /// # Errors
+    ///
+    /// If the `capacity` is too large to address, or the file cannot be
+    /// created, sized or mapped.
+    pub fn new(path: &Path, capacity: usize) -> Result<Self, BarMmapError> {
+        let len = record_offset(capacity).ok_or(BarMmapError::CapacityTooLarge(capacity))?;
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        file.set_len(len as u64)?;
+        // SAFETY: The file was opened for exclusive use by this writer, and
+        // must not be modified by other processes while mapped
+        let mut mmap = unsafe { MmapMut::map_mut(&file)? };
+        mmap[..BAR_MMAP_MAGIC.len()].copy_from_slice(&BAR_MMAP_MAGIC);
+        Ok(Self {
+            file,
+            mmap,
+            capacity,
+            count: 0,
+            bar_type: None,
+            price_precision: 0,
+            size_precision: 0,
+        })
+    }
+
+    /// Returns the number of bars written.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns whether no bars have been written.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Writes the `bar` as the next record of the file.
+    ///
+    /// # Errors
+    ///
+    /// If the file is at capacity, or the bar type or precisions of the `bar`
+    /// do not match those of the first bar written.
+    pub fn write(&mut self, bar: &Bar) -> Result<(), BarMmapError> {
+        if self.count == self.capacity {
+            return Err(BarMmapError::CapacityExceeded(self.capacity));
+        }
+        match self.bar_type {
+            None => self.write_header(bar)?,
+            Some(bar_type) if bar_type != bar.bar_type => {
+                return Err(BarMmapError::BarTypeMismatch {
+                    expected: Box::new(bar_type),
+                    found: Box::new(bar.bar_type),
+                });
+            }
+            Some(_) => {}
+        }
+        let price_precisions = [bar.open, bar.high, bar.low, bar.close].map(|p| p.precision);
+        if price_precisions.iter().any(|p| *p != self.price_precision)
+            || bar.volume.precision != self.size_precision
+        {
+            return Err(BarMmapError::PrecisionMismatch {
+                price: bar.close.precision,
+                size: bar.volume.precision,
+                expected_price: self.price_precision,
+                expected_size: self.size_precision,
+            });
+        }
+
+        // Unwrapping is safe as the offset is within the capacity checked on creation
+        let offset = record_offset(self.count).unwrap();
+        self.mmap[offset..offset + BAR_FIXED_SIZE].copy_from_slice(&bar.to_fixed_bytes());
+        self.count += 1;
+        self.mmap[RECORD_COUNT_OFFSET..RECORD_COUNT_OFFSET + 8]
+            .copy_from_slice(&(self.count as u64).to_le_bytes());
+        Ok(())
+    }
+
+    fn write_header(&mut self, bar: &Bar) -> Result<(), BarMmapError> {
+        let bar_type = bar.bar_type.to_compact_bytes()?;
+        if BAR_TYPE_OFFSET + bar_type.len() > BAR_MMAP_HEADER_SIZE {
+            return Err(BarMmapError::BarTypeTooLong(bar_type.len()));
+        }
+        self.mmap[BAR_TYPE_OFFSET..BAR_TYPE_OFFSET + bar_type.len()].copy_from_slice(&bar_type);
+        self.mmap[8] = bar.close.precision;
+        self.mmap[9] = bar.volume.precision;
+        self.bar_type = Some(bar.bar_type);
+        self.price_precision = bar.close.precision;
+        self.size_precision = bar.volume.precision;
+        Ok(())
+    }
+
+    /// Flushes the written bars to the file and truncates it to the header
+    /// and the bars written, returning the number of bars.
+    ///
+    /// # Errors
+    ///
+    /// If flushing or truncating the file fails.
+    pub fn finish(self) -> Result<usize, BarMmapError> {
+        self.mmap.flush()?;
+        let Self {
+            file, mmap, count, ..
+        } = self;
+        drop(mmap);
+        // Unwrapping is safe as the count is within the capacity checked on creation
+        file.set_len(record_offset(count).unwrap() as u64)?;
+        Ok(count)
+    }
+}
+
+/// Provides a reader of bars from a memory-mapped file written by a
+/// [`BarMmapWriter`], with constant time random access.
+///
+/// Iterating the reader yields the bars in order from the start of the file,
+/// with an error for any corrupt record.
+#[derive(Debug)]
+pub struct BarMmapReader {
+    mmap: Mmap,
+    bar_type: Option<BarType>,
+    price_precision: u8,
+    size_precision: u8,
+    count: usize,
+    pos: usize,
+}
+
+impl BarMmapReader {
+    /// Creates a new reader of the bar file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be opened or mapped, or is not a valid bar file,
+    /// including a record count too large for the file length.
+    pub fn new(path: &Path) -> Result<Self, BarMmapError> {
+        let file = File::open(path)?;
+        // SAFETY: The file must not be modified while mapped, as with any
+        // memory-mapped file
+        let mmap = unsafe { Mmap::map(&file)? };
+        if mmap.len() < BAR_MMAP_HEADER_SIZE {
+            return Err(BarMmapError::InvalidLength {
+                len: mmap.len(),
+                expected: BAR_MMAP_HEADER_SIZE,
+            });
+        }
+        if mmap[..BAR_MMAP_MAGIC.len()] != BAR_MMAP_MAGIC {
+            return Err(BarMmapError::InvalidMagic);
+        }
+
+        // Unwrapping is safe as the header length was checked above
+        let count = u64::from_le_bytes(
+            mmap[RECORD_COUNT_OFFSET..RECORD_COUNT_OFFSET + 8]
+                .try_into()
+                .unwrap(),
+        );
+        let Some((count, expected)) = usize::try_from(count)
+            .ok()
+            .and_then(|count| Some((count, record_offset(count)?)))
+        else {
+            return Err(BarMmapError::InvalidLength {
+                len: mmap.len(),
+                expected: usize::MAX,
+            });
+        };
+        if mmap.len() < expected {
+            return Err(BarMmapError::InvalidLength {
+                len: mmap.len(),
+                expected,
+            });
+        }
+        let bar_type = if count == 0 {
+            None
+        } else {
+            let len = u16::from_le_bytes([mmap[BAR_TYPE_OFFSET], mmap[BAR_TYPE_OFFSET + 1]]);
+            let end = (BAR_TYPE_OFFSET + len as usize + 7).min(BAR_MMAP_HEADER_SIZE);
+            Some(BarType::from_compact_bytes(&mmap[BAR_TYPE_OFFSET..end])?)
+        };
+
+        Ok(Self {
+            price_precision: mmap[8],
+            size_precision: mmap[9],
+            mmap,
+            bar_type,
+            count,
+            pos: 0,
+        })
+    }
+
+    /// Returns the bar type of the file, or `None` if the file has no bars.
+    #[must_use]
+    pub fn bar_type(&self) -> Option<BarType> {
+        self.bar_type
+    }
+
+    /// Returns the price precision of the bars in the file.
+    #[must_use]
+    pub fn price_precision(&self) -> u8 {
+        self.price_precision
+    }
+
+    /// Returns the size precision of the bars in the file.
+    #[must_use]
+    pub fn size_precision(&self) -> u8 {
+        self.size_precision
+    }
+
+    /// Returns the number of bars in the file.
+    #[must_use]
+    pub fn record_count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the bar at `index`, an error if the record is corrupt, or
+    /// `None` if the index is out of range.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<Result<Bar, BarMmapError>> {
+        if index >= self.count {
+            return None;
+        }
+        // Unwrapping is safe as the file length was checked for the record count
+        let offset = record_offset(index).unwrap();
+        // Unwrapping is safe as the record slice is exactly `BAR_FIXED_SIZE` bytes
+        let data = self.mmap[offset..offset + BAR_FIXED_SIZE]
+            .try_into()
+            .unwrap();
+        Some(
+            Bar::from_fixed_bytes(data, self.bar_type?)
+                .map_err(|source| BarMmapError::InvalidRecord { index, source }),
+        )
+    }
+}
+
+impl Iterator for BarMmapReader {
+    type Item = Result<Bar, BarMmapError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bar = self.get(self.pos)?;
+        self.pos += 1;
+        Some(bar)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.pos;
+        (0, Some(remaining))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::{
+        data::bar::stubs::*,
+        types::{price::Price, quantity::Quantity},
+    };
+
+    fn create_bar(bar: Bar, i: u64) -> Bar {
+        Bar {
+            close: Price::from_raw(bar.close.raw + i as i64, bar.close.precision),
+            ts_event: i,
+            ts_init: i + 1,
+            ..bar
+        }
+    }
+
+    #[rstest]
+    fn test_write_and_read_one_million_bars(bar_audusd_sim_minute_bid: Bar) {
+        let n = 1_000_000;
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("bars.bin");
+        let mut writer = BarMmapWriter::new(&path, n).unwrap();
+        for i in 0..n as u64 {
+            writer
+                .write(&create_bar(bar_audusd_sim_minute_bid, i))
+                .unwrap();
+        }
+
+        assert_eq!(writer.finish().unwrap(), n);
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().len(),
+            (BAR_MMAP_HEADER_SIZE + n * 64) as u64
+        );
+
+        let reader = BarMmapReader::new(&path).unwrap();
+        assert_eq!(reader.record_count(), n);
+        assert_eq!(reader.bar_type(), Some(bar_audusd_sim_minute_bid.bar_type));
+        assert_eq!(reader.price_precision(), 5);
+        assert_eq!(reader.size_precision(), 0);
+        assert_eq!(
+            reader.get(500_000).unwrap().unwrap(),
+            create_bar(bar_audusd_sim_minute_bid, 500_000)
+        );
+        assert!(reader.get(n).is_none());
+        assert_eq!(reader.count(), n);
+    }
+
+    #[rstest]
+    fn test_reader_iterates_in_order(bar_audusd_sim_minute_bid: Bar) {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("bars.bin");
+        let bars: Vec<Bar> = (0..3)
+            .map(|i| create_bar(bar_audusd_sim_minute_bid, i))
+            .collect();
+        let mut writer = BarMmapWriter::new(&path, 10).unwrap();
+        for bar in &bars {
+            writer.write(bar).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let read: Vec<Bar> = BarMmapReader::new(&path)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(read, bars);
+    }
+
+    #[rstest]
+    fn test_reader_corrupt_record(bar_audusd_sim_minute_bid: Bar) {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("bars.bin");
+        let mut writer = BarMmapWriter::new(&path, 3).unwrap();
+        for i in 0..3 {
+            writer
+                .write(&create_bar(bar_audusd_sim_minute_bid, i))
+                .unwrap();
+        }
+        writer.finish().unwrap();
+        let mut data = std::fs::read(&path).unwrap();
+        data[BAR_MMAP_HEADER_SIZE + BAR_FIXED_SIZE] = 0;
+        std::fs::write(&path, data).unwrap();
+
+        let mut reader = BarMmapReader::new(&path).unwrap();
+        assert!(reader.next().unwrap().is_ok());
+        assert!(matches!(
+            reader.next(),
+            Some(Err(BarMmapError::InvalidRecord {
+                index: 1,
+                source: BarDecodeError::InvalidMagic(0)
+            }))
+        ));
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().is_none());
+    }
+
+    #[rstest]
+    fn test_writer_errors(bar_audusd_sim_minute_bid: Bar) {
+        let dir = tempdir().unwrap();
+        let mut writer = BarMmapWriter::new(&dir.path().join("bars.bin"), 2).unwrap();
+        writer.write(&bar_audusd_sim_minute_bid).unwrap();
+
+        let mut other_type = bar_audusd_sim_minute_bid;
+        other_type.bar_type.aggregation_source = crate::enums::AggregationSource::Internal;
+        assert!(matches!(
+            writer.write(&other_type),
+            Err(BarMmapError::BarTypeMismatch { .. })
+        ));
+
+        let mut other_precision = bar_audusd_sim_minute_bid;
+        other_precision.volume = Quantity::from("1.5");
+        assert!(matches!(
+            writer.write(&other_precision),
+            Err(BarMmapError::PrecisionMismatch { .. })
+        ));
+
+        writer.write(&bar_audusd_sim_minute_bid).unwrap();
+        assert!(matches!(
+            writer.write(&bar_audusd_sim_minute_bid),
+            Err(BarMmapError::CapacityExceeded(2))
+        ));
+        assert_eq!(writer.len(), 2);
+    }
+
+    #[rstest]
+    fn test_write_step_overflow(bar_audusd_sim_minute_bid: Bar) {
+        let dir = tempdir().unwrap();
+        let mut writer = BarMmapWriter::new(&dir.path().join("bars.bin"), 1).unwrap();
+        let mut bar = bar_audusd_sim_minute_bid;
+        bar.bar_type.spec.step = 100_000;
+
+        assert!(matches!(
+            writer.write(&bar),
+            Err(BarMmapError::BarTypeEncoding(
+                CompactEncodeError::StepOverflow(100_000)
+            ))
+        ));
+        assert!(writer.is_empty());
+    }
+
+    #[rstest]
+    fn test_empty_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("bars.bin");
+        assert_eq!(BarMmapWriter::new(&path, 5).unwrap().finish().unwrap(), 0);
+
+        let mut reader = BarMmapReader::new(&path).unwrap();
+        assert_eq!(reader.record_count(), 0);
+        assert_eq!(reader.bar_type(), None);
+        assert!(reader.next().is_none());
+    }
+
+    #[rstest]
+    fn test_reader_invalid_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("bars.bin");
+        std::fs::write(&path, vec![0; BAR_MMAP_HEADER_SIZE]).unwrap();
+        assert!(matches!(
+            BarMmapReader::new(&path),
+            Err(BarMmapError::InvalidMagic)
+        ));
+
+        std::fs::write(&path, b"NTBARS01").unwrap();
+        assert!(matches!(
+            BarMmapReader::new(&path),
+            Err(BarMmapError::InvalidLength { len: 8, .. })
+        ));
+
+        let mut header = vec![0; BAR_MMAP_HEADER_SIZE];
+        header[..8].copy_from_slice(&BAR_MMAP_MAGIC);
+        header[RECORD_COUNT_OFFSET..RECORD_COUNT_OFFSET + 8]
+            .copy_from_slice(&u64::MAX.to_le_bytes());
+        std::fs::write(&path, header).unwrap();
+        assert!(matches!(
+            BarMmapReader::new(&path),
+            Err(BarMmapError::InvalidLength {
+                len: BAR_MMAP_HEADER_SIZE,
+                expected: usize::MAX
+            })
+        ));
+    }
+
+    #[rstest]
+    fn test_writer_capacity_too_large() {
+        let dir = tempdir().unwrap();
+        assert!(matches!(
+            BarMmapWriter::new(&dir.path().join("bars.bin"), usize::MAX),
+            Err(BarMmapError::CapacityTooLarge(usize::MAX))
+        ));
+    }
+}
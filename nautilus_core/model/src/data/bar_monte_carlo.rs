@@ -0,0 +1,142 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Monte Carlo simulation of the returns of series of [`Bar`]s.
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::bar::Bar;
+
+impl Bar {
+    /// Returns the risk of ruin of holding the instrument over the `bars`, the
+    /// fraction of `n_sims` simulated equity curves starting at
+    /// `initial_equity` which fall to or below `ruin_level` at any point.
+    ///
+    /// Each simulated curve is as long as the historical series, and is built
+    /// from a circular block bootstrap of the close-to-close returns of the
+    /// `bars`. Resampling blocks of consecutive returns (of length n^(1/3) for
+    /// n returns) rather than single returns preserves the short-term
+    /// autocorrelation structure, such as runs of losses.
+    ///
+    /// The `seed` makes the simulation reproducible. Returns 0 if there are
+    /// fewer than two bars or `n_sims` is zero, and 1 if `ruin_level` is at or
+    /// above `initial_equity`.
+    #[must_use]
+    pub fn risk_of_ruin(
+        bars: &[Bar],
+        initial_equity: f64,
+        ruin_level: f64,
+        n_sims: usize,
+        seed: u64,
+    ) -> f64 {
+        if ruin_level >= initial_equity {
+            return 1.0;
+        }
+        let returns: Vec<f64> = bars
+            .windows(2)
+            .map(|pair| pair[1].close.as_f64() / pair[0].close.as_f64() - 1.0)
+            .collect();
+        if returns.is_empty() || n_sims == 0 {
+            return 0.0;
+        }
+
+        let n = returns.len();
+        let block_len = ((n as f64).cbrt().round() as usize).max(1);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut ruined = 0;
+        for _ in 0..n_sims {
+            let mut equity = initial_equity;
+            let mut steps = 0;
+            'path: while steps < n {
+                let start = rng.gen_range(0..n);
+                for offset in 0..block_len.min(n - steps) {
+                    equity *= 1.0 + returns[(start + offset) % n];
+                    if equity <= ruin_level {
+                        ruined += 1;
+                        break 'path;
+                    }
+                }
+                steps += block_len;
+            }
+        }
+        ruined as f64 / n_sims as f64
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::bars_from_closes;
+
+    fn create_alternating_bars(n: usize, up: f64, down: f64) -> Vec<Bar> {
+        let mut close = 100.0;
+        let mut closes = vec![close];
+        for i in 0..n {
+            close *= if i % 2 == 0 { 1.0 + up } else { 1.0 - down };
+            closes.push(close);
+        }
+        bars_from_closes(&closes)
+    }
+
+    #[rstest]
+    fn test_risk_of_ruin_rising_series_is_zero() {
+        let bars = bars_from_closes(&[100.0, 101.0, 102.0, 103.0, 104.0]);
+
+        assert_eq!(Bar::risk_of_ruin(&bars, 10_000.0, 5_000.0, 500, 1), 0.0);
+    }
+
+    #[rstest]
+    fn test_risk_of_ruin_falling_series_is_one() {
+        // Every path draws the single -60% return, ending below the ruin level
+        let bars = bars_from_closes(&[100.0, 40.0]);
+
+        assert_eq!(Bar::risk_of_ruin(&bars, 10_000.0, 5_000.0, 500, 1), 1.0);
+    }
+
+    #[rstest]
+    fn test_risk_of_ruin_volatile_series_is_a_probability() {
+        // Returns of +10% and -10% lose about 1% every two bars on average
+        let bars = create_alternating_bars(100, 0.1, 0.1);
+        let risk = Bar::risk_of_ruin(&bars, 10_000.0, 7_000.0, 2_000, 42);
+
+        assert!(risk > 0.0 && risk < 1.0, "risk was {risk}");
+        // A deeper ruin level is less likely to be reached
+        assert!(Bar::risk_of_ruin(&bars, 10_000.0, 3_000.0, 2_000, 42) < risk);
+    }
+
+    #[rstest]
+    fn test_risk_of_ruin_is_reproducible_with_seed() {
+        let bars = create_alternating_bars(50, 0.05, 0.06);
+
+        assert_eq!(
+            Bar::risk_of_ruin(&bars, 1_000.0, 800.0, 300, 7),
+            Bar::risk_of_ruin(&bars, 1_000.0, 800.0, 300, 7)
+        );
+    }
+
+    #[rstest]
+    fn test_risk_of_ruin_edge_cases() {
+        let bars = bars_from_closes(&[100.0, 50.0]);
+
+        assert_eq!(Bar::risk_of_ruin(&bars, 1_000.0, 1_000.0, 10, 1), 1.0);
+        assert_eq!(Bar::risk_of_ruin(&bars[..1], 1_000.0, 500.0, 10, 1), 0.0);
+        assert_eq!(Bar::risk_of_ruin(&bars, 1_000.0, 500.0, 0, 1), 0.0);
+    }
+}
@@ -0,0 +1,226 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Conversion of [`Bar`]s to and from NATS messages.
+
+use std::str::FromStr;
+
+use nautilus_core::serialization::Serializable;
+
+use super::bar::{Bar, BarType};
+
+/// The root token of the subjects of bar messages.
+const NATS_SUBJECT_ROOT: &str = "bars";
+
+#[derive(thiserror::Error, Debug)]
+pub enum NatsError {
+    #[error("Invalid subject '{0}', expected `bars.<venue>.<symbol>.<bar specification>`")]
+    InvalidSubject(String),
+    #[error("Failed to decode MsgPack payload: {0}")]
+    Decode(#[from] rmp_serde::decode::Error),
+    #[error("Subject bar type {subject} does not match the payload bar type {payload}")]
+    BarTypeMismatch {
+        subject: Box<BarType>,
+        payload: Box<BarType>,
+    },
+}
+
+/// Returns the `value` as a single subject token, percent-encoding `%` and the
+/// characters NATS does not allow in a token (`.`, `*`, `>` and whitespace).
+fn escape_token(value: &str) -> String {
+    let mut token = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '.' | '*' | '>') || c.is_whitespace() {
+            let mut buf = [0; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                token.push_str(&format!("%{byte:02X}"));
+            }
+        } else {
+            token.push(c);
+        }
+    }
+    token
+}
+
+/// Returns the value of a subject `token` encoded by [`escape_token`].
+fn unescape_token(token: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(token.len());
+    let mut iter = token.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            if !hex.iter().all(u8::is_ascii_hexdigit) {
+                return None;
+            }
+            // Unwrapping is safe as both bytes are ASCII hex digits
+            let hex = std::str::from_utf8(&hex).unwrap();
+            bytes.push(u8::from_str_radix(hex, 16).unwrap());
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Returns the bar type of the NATS `subject`.
+fn parse_subject(subject: &str) -> Option<BarType> {
+    let rest = subject.strip_prefix(NATS_SUBJECT_ROOT)?.strip_prefix('.')?;
+    let mut tokens = rest.split('.');
+    let (Some(venue), Some(symbol), Some(spec), None) =
+        (tokens.next(), tokens.next(), tokens.next(), tokens.next())
+    else {
+        return None;
+    };
+    let venue = unescape_token(venue)?;
+    let symbol = unescape_token(symbol)?;
+    if venue.is_empty() || symbol.is_empty() {
+        return None;
+    }
+    BarType::from_str(&format!("{symbol}.{venue}-{spec}")).ok()
+}
+
+impl Bar {
+    /// Returns the NATS subject of the bar, in the hierarchy
+    /// `bars.<venue>.<symbol>.<step>-<aggregation>-<price type>-<aggregation source>`,
+    /// such as `bars.BINANCE.BTCUSDT.1-MINUTE-LAST-EXTERNAL`.
+    ///
+    /// Any `.`, `*`, `>`, whitespace or `%` in the venue or symbol is
+    /// percent-encoded so each is a single token, such as `BRK%2EB` for `BRK.B`.
+    ///
+    /// Subscribers can use wildcards over the hierarchy, such as `bars.BINANCE.>`
+    /// for all bars of a venue.
+    #[must_use]
+    pub fn to_nats_subject(&self) -> String {
+        let bar_type = &self.bar_type;
+        format!(
+            "{NATS_SUBJECT_ROOT}.{}.{}.{}-{}",
+            escape_token(&bar_type.instrument_id.venue.to_string()),
+            escape_token(&bar_type.instrument_id.symbol.to_string()),
+            bar_type.spec,
+            bar_type.aggregation_source
+        )
+    }
+
+    /// Returns the NATS message payload of the bar, the MsgPack encoded bar.
+    #[must_use]
+    pub fn to_nats_payload(&self) -> Vec<u8> {
+        // Unwrapping is safe when serializing a valid object
+        self.as_msgpack_bytes().unwrap()
+    }
+
+    /// Returns the bar decoded from a NATS message, with the bar type parsed
+    /// from the `subject`.
+    ///
+    /// # Errors
+    ///
+    /// If the `subject` is not a bar subject, the `payload` cannot be decoded,
+    /// or the bar types of the subject and payload do not match.
+    pub fn from_nats_message(subject: &str, payload: &[u8]) -> Result<(Self, BarType), NatsError> {
+        let bar_type =
+            parse_subject(subject).ok_or_else(|| NatsError::InvalidSubject(subject.to_string()))?;
+        let bar = Self::from_msgpack_bytes(payload.to_vec())?;
+        if bar.bar_type != bar_type {
+            return Err(NatsError::BarTypeMismatch {
+                subject: Box::new(bar_type),
+                payload: Box::new(bar.bar_type),
+            });
+        }
+        Ok((bar, bar_type))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::*;
+
+    #[rstest]
+    fn test_to_nats_subject(bar_audusd_sim_minute_bid: Bar) {
+        assert_eq!(
+            bar_audusd_sim_minute_bid.to_nats_subject(),
+            "bars.SIM.AUDUSD.1-MINUTE-BID-EXTERNAL"
+        );
+    }
+
+    #[rstest]
+    fn test_nats_message_round_trip(bar_audusd_sim_minute_bid: Bar) {
+        let subject = bar_audusd_sim_minute_bid.to_nats_subject();
+        let payload = bar_audusd_sim_minute_bid.to_nats_payload();
+        let (bar, bar_type) = Bar::from_nats_message(&subject, &payload).unwrap();
+
+        assert_eq!(bar, bar_audusd_sim_minute_bid);
+        assert_eq!(bar_type, bar_audusd_sim_minute_bid.bar_type);
+    }
+
+    #[rstest]
+    fn test_nats_message_round_trip_symbol_with_dot(bar_audusd_sim_minute_bid: Bar) {
+        let mut bar = bar_audusd_sim_minute_bid;
+        bar.bar_type = BarType::from_str("BRK.B.XNYS-1-DAY-LAST-EXTERNAL").unwrap();
+        let subject = bar.to_nats_subject();
+
+        assert_eq!(subject, "bars.XNYS.BRK%2EB.1-DAY-LAST-EXTERNAL");
+        let (decoded, _) = Bar::from_nats_message(&subject, &bar.to_nats_payload()).unwrap();
+        assert_eq!(decoded, bar);
+    }
+
+    #[rstest]
+    #[case("ETH/USDT", "ETH/USDT")]
+    #[case("BRK.B", "BRK%2EB")]
+    #[case("A*B>C D", "A%2AB%3EC%20D")]
+    #[case("100%", "100%25")]
+    fn test_escape_token(#[case] value: &str, #[case] expected: &str) {
+        assert_eq!(escape_token(value), expected);
+        assert_eq!(unescape_token(expected).as_deref(), Some(value));
+    }
+
+    #[rstest]
+    #[case("quotes.SIM.AUDUSD.1-MINUTE-BID-EXTERNAL")]
+    #[case("bars.SIM")]
+    #[case("bars.SIM.AUDUSD")]
+    #[case("bars.SIM.AUDUSD.1-FORTNIGHT-BID-EXTERNAL")]
+    #[case("bars.XNYS.BRK.B.1-DAY-LAST-EXTERNAL")]
+    #[case("bars.XNYS.BRK%2.1-DAY-LAST-EXTERNAL")]
+    #[case("bars.XNYS.BRK%ZZB.1-DAY-LAST-EXTERNAL")]
+    fn test_from_nats_message_invalid_subject(
+        bar_audusd_sim_minute_bid: Bar,
+        #[case] subject: &str,
+    ) {
+        let payload = bar_audusd_sim_minute_bid.to_nats_payload();
+
+        assert!(matches!(
+            Bar::from_nats_message(subject, &payload),
+            Err(NatsError::InvalidSubject(_))
+        ));
+    }
+
+    #[rstest]
+    fn test_from_nats_message_mismatched_bar_type(bar_audusd_sim_minute_bid: Bar) {
+        let payload = bar_audusd_sim_minute_bid.to_nats_payload();
+
+        assert!(matches!(
+            Bar::from_nats_message("bars.SIM.AUDUSD.5-MINUTE-BID-EXTERNAL", &payload),
+            Err(NatsError::BarTypeMismatch { .. })
+        ));
+        assert!(matches!(
+            Bar::from_nats_message("bars.SIM.AUDUSD.1-MINUTE-BID-EXTERNAL", b"invalid"),
+            Err(NatsError::Decode(_))
+        ));
+    }
+}
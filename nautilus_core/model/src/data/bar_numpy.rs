@@ -0,0 +1,231 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Conversion of [`Bar`]s to and from NumPy structured arrays.
+
+use nautilus_core::python::to_pyvalue_err;
+use numpy::get_array_module;
+use pyo3::{
+    prelude::*,
+    types::{IntoPyDict, PyBytes},
+};
+
+use super::bar::{Bar, BarType};
+use crate::types::{fixed::check_fixed_precision, price::Price, quantity::Quantity};
+
+/// The NumPy dtype of a packed little-endian [`Bar`] record.
+const BAR_NUMPY_DTYPE: &str = "[('open', '<i8'), ('high', '<i8'), ('low', '<i8'), \
+     ('close', '<i8'), ('volume', '<u8'), ('ts_event', '<u8'), ('ts_init', '<u8'), \
+     ('price_precision', '|u1'), ('size_precision', '|u1')]";
+
+/// The size in bytes of a packed [`Bar`] record.
+const RECORD_SIZE: usize = 7 * 8 + 2;
+
+impl Bar {
+    /// Returns the NumPy structured array dtype used for bars, as a list of
+    /// `(name, format)` field tuples.
+    #[must_use]
+    pub fn to_numpy_dtype() -> &'static str {
+        BAR_NUMPY_DTYPE
+    }
+}
+
+fn numpy_dtype(py: Python<'_>) -> PyResult<&PyAny> {
+    let fields = py.eval(BAR_NUMPY_DTYPE, None, None)?;
+    get_array_module(py)?.call_method1("dtype", (fields,))
+}
+
+fn read_u64(record: &[u8], offset: usize) -> u64 {
+    // Unwrapping is safe as records are sliced to `RECORD_SIZE`
+    u64::from_le_bytes(record[offset..offset + 8].try_into().unwrap())
+}
+
+fn read_i64(record: &[u8], offset: usize) -> i64 {
+    // Unwrapping is safe as records are sliced to `RECORD_SIZE`
+    i64::from_le_bytes(record[offset..offset + 8].try_into().unwrap())
+}
+
+/// Provides an encoder of bars to NumPy structured arrays.
+pub struct BarNumpyEncoder;
+
+impl BarNumpyEncoder {
+    /// Returns a new NumPy structured array of the given `bars`, with the
+    /// dtype of [`Bar::to_numpy_dtype`].
+    ///
+    /// The price precision of each record is taken from the bar close.
+    pub fn encode(py: Python<'_>, bars: &[Bar]) -> PyResult<PyObject> {
+        let mut buffer = Vec::with_capacity(bars.len() * RECORD_SIZE);
+        for bar in bars {
+            buffer.extend_from_slice(&bar.open.raw.to_le_bytes());
+            buffer.extend_from_slice(&bar.high.raw.to_le_bytes());
+            buffer.extend_from_slice(&bar.low.raw.to_le_bytes());
+            buffer.extend_from_slice(&bar.close.raw.to_le_bytes());
+            buffer.extend_from_slice(&bar.volume.raw.to_le_bytes());
+            buffer.extend_from_slice(&bar.ts_event.to_le_bytes());
+            buffer.extend_from_slice(&bar.ts_init.to_le_bytes());
+            buffer.push(bar.close.precision);
+            buffer.push(bar.volume.precision);
+        }
+
+        let kwargs = [("dtype", numpy_dtype(py)?)].into_py_dict(py);
+        // Copy so the array owns writable memory rather than viewing the bytes
+        let array = get_array_module(py)?
+            .call_method("frombuffer", (PyBytes::new(py, &buffer),), Some(kwargs))?
+            .call_method0("copy")?;
+        Ok(array.into_py(py))
+    }
+}
+
+/// Provides a decoder of bars from NumPy structured arrays.
+pub struct BarNumpyDecoder;
+
+impl BarNumpyDecoder {
+    /// Returns the bars of the given NumPy structured array `arr`, which must
+    /// have the dtype of [`Bar::to_numpy_dtype`].
+    pub fn decode(arr: &PyAny, bar_type: BarType) -> PyResult<Vec<Bar>> {
+        let py = arr.py();
+        let dtype = numpy_dtype(py)?;
+        if !arr.getattr("dtype")?.eq(dtype)? {
+            return Err(to_pyvalue_err(format!(
+                "Invalid array dtype, expected {BAR_NUMPY_DTYPE}, was {}",
+                arr.getattr("dtype")?
+            )));
+        }
+
+        let bytes = get_array_module(py)?
+            .call_method1("ascontiguousarray", (arr,))?
+            .call_method0("tobytes")?;
+        let bytes: &PyBytes = bytes.downcast()?;
+
+        bytes
+            .as_bytes()
+            .chunks_exact(RECORD_SIZE)
+            .map(|record| {
+                let price_precision = record[56];
+                let size_precision = record[57];
+                check_fixed_precision(price_precision).map_err(to_pyvalue_err)?;
+                check_fixed_precision(size_precision).map_err(to_pyvalue_err)?;
+                Ok(Bar::new(
+                    bar_type,
+                    Price::from_raw(read_i64(record, 0), price_precision),
+                    Price::from_raw(read_i64(record, 8), price_precision),
+                    Price::from_raw(read_i64(record, 16), price_precision),
+                    Price::from_raw(read_i64(record, 24), price_precision),
+                    Quantity::from_raw(read_u64(record, 32), size_precision),
+                    read_u64(record, 40),
+                    read_u64(record, 48),
+                ))
+            })
+            .collect()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::bar_type_audusd_1_min_bid;
+
+    fn bar_strategy(bar_type: BarType) -> impl Strategy<Value = Bar> {
+        let raw_price = -1_000_000_000_000_000_000i64..1_000_000_000_000_000_000i64;
+        (
+            [
+                raw_price.clone(),
+                raw_price.clone(),
+                raw_price.clone(),
+                raw_price,
+            ],
+            any::<u64>(),
+            any::<u64>(),
+            any::<u64>(),
+            0u8..=9,
+            0u8..=9,
+        )
+            .prop_map(
+                move |(prices, volume, ts_event, ts_init, price_precision, size_precision)| {
+                    Bar::new(
+                        bar_type,
+                        Price::from_raw(prices[0], price_precision),
+                        Price::from_raw(prices[1], price_precision),
+                        Price::from_raw(prices[2], price_precision),
+                        Price::from_raw(prices[3], price_precision),
+                        Quantity::from_raw(volume, size_precision),
+                        ts_event,
+                        ts_init,
+                    )
+                },
+            )
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn test_encode_decode_round_trip(
+            bars in prop::collection::vec(bar_strategy(bar_type_audusd_1_min_bid()), 1000)
+        ) {
+            pyo3::prepare_freethreaded_python();
+
+            Python::with_gil(|py| {
+                let array = BarNumpyEncoder::encode(py, &bars).unwrap();
+                let decoded = BarNumpyDecoder::decode(array.as_ref(py), bars[0].bar_type).unwrap();
+
+                prop_assert_eq!(decoded, bars);
+                Ok(())
+            })?;
+        }
+    }
+
+    #[rstest]
+    fn test_encode_dtype(bar_type_audusd_1_min_bid: BarType) {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let array = BarNumpyEncoder::encode(py, &[]).unwrap();
+            let array = array.as_ref(py);
+            let itemsize: usize = array
+                .getattr("dtype")
+                .unwrap()
+                .getattr("itemsize")
+                .unwrap()
+                .extract()
+                .unwrap();
+
+            assert_eq!(itemsize, RECORD_SIZE);
+            assert!(BarNumpyDecoder::decode(array, bar_type_audusd_1_min_bid)
+                .unwrap()
+                .is_empty());
+        });
+    }
+
+    #[rstest]
+    fn test_decode_invalid_dtype(bar_type_audusd_1_min_bid: BarType) {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let array = get_array_module(py)
+                .unwrap()
+                .call_method1("zeros", (3, "int64"))
+                .unwrap();
+
+            assert!(BarNumpyDecoder::decode(array, bar_type_audusd_1_min_bid).is_err());
+        });
+    }
+}
@@ -0,0 +1,466 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Strategy parameter optimization over series of [`Bar`]s.
+
+use std::{fmt::Debug, ops::Range};
+
+use nautilus_core::time::UnixNanos;
+
+use super::bar::Bar;
+
+/// Provides a parameterized strategy which can be evaluated over a series of
+/// bars, for parameter optimization.
+pub trait Strategy {
+    /// The parameters of the strategy.
+    type Params: Clone + Debug;
+
+    /// Returns the candidate parameters to search.
+    fn candidate_params(&self) -> Vec<Self::Params>;
+
+    /// Returns the performance metric of the strategy run with `params` over
+    /// the `bars`, where higher is better.
+    fn evaluate(&self, params: &Self::Params, bars: &[Bar]) -> f64;
+}
+
+/// Represents the result of a single walk-forward optimization period.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WalkForwardResult<P> {
+    /// The `ts_event` of the first in-sample bar of the period.
+    pub period_start: UnixNanos,
+    /// The metric of the best parameters over the in-sample bars.
+    pub in_sample_metric: f64,
+    /// The metric of the best parameters over the following out-of-sample bars.
+    pub out_sample_metric: f64,
+    /// The parameters with the best in-sample metric.
+    pub best_params: P,
+}
+
+/// Represents a single train/test combination of a combinatorial purged
+/// cross-validation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpcvSplit<P> {
+    /// The indices of the groups held out for testing.
+    pub test_groups: Vec<usize>,
+    /// The indices of the bars used for training.
+    pub train_indices: Vec<usize>,
+    /// The parameters with the best metric over the training bars, or `None`
+    /// if every metric was NaN.
+    pub best_params: Option<P>,
+    /// The metric of the best parameters over each test group, in the order
+    /// of `test_groups`.
+    pub test_metrics: Vec<f64>,
+}
+
+/// Represents the results of a combinatorial purged cross-validation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpcvResults<P> {
+    /// The train/test combinations, in lexicographic order of test groups.
+    pub splits: Vec<CpcvSplit<P>>,
+    /// The out-of-sample metric of each group along each backtest path.
+    pub paths: Vec<Vec<f64>>,
+}
+
+impl Bar {
+    /// Returns the walk-forward optimization of the `strategy` over the `bars`.
+    ///
+    /// Each period selects the candidate parameters with the best metric over
+    /// `in_sample_bars` bars, then evaluates them over the following
+    /// `out_sample_bars` bars. Periods roll forward by `out_sample_bars` until
+    /// there are too few bars for a full period. The first candidate wins ties,
+    /// and periods where every metric is NaN are excluded.
+    #[must_use]
+    pub fn walk_forward<S: Strategy>(
+        bars: &[Bar],
+        strategy: &S,
+        in_sample_bars: usize,
+        out_sample_bars: usize,
+    ) -> Vec<WalkForwardResult<S::Params>> {
+        if in_sample_bars == 0 || out_sample_bars == 0 {
+            return Vec::new();
+        }
+        let candidates = strategy.candidate_params();
+        let period_bars = in_sample_bars + out_sample_bars;
+
+        (0..bars.len().saturating_sub(period_bars - 1))
+            .step_by(out_sample_bars)
+            .filter_map(|start| {
+                let in_sample = &bars[start..start + in_sample_bars];
+                let out_sample = &bars[start + in_sample_bars..start + period_bars];
+                let (best_params, in_sample_metric) =
+                    best_candidate(strategy, &candidates, in_sample)?;
+                Some(WalkForwardResult {
+                    period_start: in_sample[0].ts_event,
+                    in_sample_metric,
+                    out_sample_metric: strategy.evaluate(best_params, out_sample),
+                    best_params: best_params.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the (train indices, test indices) of each fold of the purged
+    /// k-fold cross-validation of the `bars`.
+    ///
+    /// The bars are split into `n_splits` contiguous test folds, the earlier
+    /// folds taking one extra bar when the split is uneven. The training set of
+    /// each fold is every other bar, excluding the `embargo_bars` bars either
+    /// side of the test fold, so that training observations overlapping the
+    /// test period cannot leak into it. Returns an empty vector if `n_splits`
+    /// is less than 2 or greater than the number of bars.
+    #[must_use]
+    pub fn purged_kfold(
+        bars: &[Bar],
+        n_splits: usize,
+        embargo_bars: usize,
+    ) -> Vec<(Vec<usize>, Vec<usize>)> {
+        let len = bars.len();
+        if n_splits < 2 || n_splits > len {
+            return Vec::new();
+        }
+        fold_ranges(len, n_splits)
+            .into_iter()
+            .map(|test| {
+                let train = purged_train_indices(len, std::slice::from_ref(&test), embargo_bars);
+                (train, test.collect())
+            })
+            .collect()
+    }
+
+    /// Returns the combinatorial purged cross-validation of the `strategy` over
+    /// the `bars`.
+    ///
+    /// The bars are split into `n_splits` contiguous groups as for
+    /// [`Bar::purged_kfold`], and every combination of `n_test_groups` groups is
+    /// held out as a test set. The best candidate parameters are selected over
+    /// the purged training bars of each combination, then evaluated over each of
+    /// its test groups. The test group metrics are assembled into
+    /// C(`n_splits`, `n_test_groups`) * `n_test_groups` / `n_splits` backtest
+    /// paths, each covering every group exactly once, for overfitting detection
+    /// such as the deflated Sharpe ratio test.
+    ///
+    /// Returns empty results if `n_splits` is less than 2 or greater than the
+    /// number of bars, or `n_test_groups` is zero or not less than `n_splits`.
+    #[must_use]
+    pub fn cpcv<S: Strategy>(
+        bars: &[Bar],
+        strategy: &S,
+        n_splits: usize,
+        n_test_groups: usize,
+        embargo_bars: usize,
+    ) -> CpcvResults<S::Params> {
+        let len = bars.len();
+        if n_splits < 2 || n_splits > len || n_test_groups == 0 || n_test_groups >= n_splits {
+            return CpcvResults {
+                splits: Vec::new(),
+                paths: Vec::new(),
+            };
+        }
+        let groups = fold_ranges(len, n_splits);
+        let candidates = strategy.candidate_params();
+
+        let splits: Vec<CpcvSplit<S::Params>> = combinations(n_splits, n_test_groups)
+            .into_iter()
+            .map(|test_groups| {
+                let tests: Vec<Range<usize>> =
+                    test_groups.iter().map(|&g| groups[g].clone()).collect();
+                let train_indices = purged_train_indices(len, &tests, embargo_bars);
+                let train: Vec<Bar> = train_indices.iter().map(|&i| bars[i]).collect();
+                let best_params =
+                    best_candidate(strategy, &candidates, &train).map(|(params, _)| params.clone());
+                let test_metrics = tests
+                    .iter()
+                    .map(|test| match &best_params {
+                        Some(params) => strategy.evaluate(params, &bars[test.clone()]),
+                        None => f64::NAN,
+                    })
+                    .collect();
+                CpcvSplit {
+                    test_groups,
+                    train_indices,
+                    best_params,
+                    test_metrics,
+                }
+            })
+            .collect();
+
+        // The p-th path takes each group's metric from the p-th split testing it
+        let mut paths: Vec<Vec<f64>> = Vec::new();
+        for group in 0..n_splits {
+            let metrics = splits.iter().filter_map(|split| {
+                split
+                    .test_groups
+                    .iter()
+                    .position(|&g| g == group)
+                    .map(|i| split.test_metrics[i])
+            });
+            for (path, metric) in metrics.enumerate() {
+                if path == paths.len() {
+                    paths.push(Vec::with_capacity(n_splits));
+                }
+                paths[path].push(metric);
+            }
+        }
+
+        CpcvResults { splits, paths }
+    }
+}
+
+/// Returns the candidate parameters with the best metric over the `bars`, and
+/// the metric, where the first candidate wins ties and NaN metrics are excluded.
+fn best_candidate<'a, S: Strategy>(
+    strategy: &S,
+    candidates: &'a [S::Params],
+    bars: &[Bar],
+) -> Option<(&'a S::Params, f64)> {
+    let mut best: Option<(&S::Params, f64)> = None;
+    for params in candidates {
+        let metric = strategy.evaluate(params, bars);
+        if best.map_or(!metric.is_nan(), |(_, best_metric)| metric > best_metric) {
+            best = Some((params, metric));
+        }
+    }
+    best
+}
+
+/// Returns the ranges of `n_splits` contiguous folds of `len` items, the
+/// earlier folds taking one extra item when the split is uneven.
+fn fold_ranges(len: usize, n_splits: usize) -> Vec<Range<usize>> {
+    let fold_size = len / n_splits;
+    let remainder = len % n_splits;
+    let mut start = 0;
+    (0..n_splits)
+        .map(|fold| {
+            let end = start + fold_size + usize::from(fold < remainder);
+            let range = start..end;
+            start = end;
+            range
+        })
+        .collect()
+}
+
+/// Returns the indices of `len` items outside the `tests` ranges and the
+/// `embargo` items either side of each.
+fn purged_train_indices(len: usize, tests: &[Range<usize>], embargo: usize) -> Vec<usize> {
+    (0..len)
+        .filter(|&i| {
+            tests.iter().all(|test| {
+                i.saturating_add(embargo) < test.start || i >= test.end.saturating_add(embargo)
+            })
+        })
+        .collect()
+}
+
+/// Returns every combination of `k` of the indices `0..n`, in lexicographic order.
+fn combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    let mut result = Vec::new();
+    let mut current: Vec<usize> = (0..k).collect();
+    loop {
+        result.push(current.clone());
+        let Some(i) = (0..k).rev().find(|&i| current[i] < n - k + i) else {
+            return result;
+        };
+        let first = current[i] + 1;
+        for (offset, index) in current[i..].iter_mut().enumerate() {
+            *index = first + offset;
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::bars_from_closes;
+
+    /// Scores each target close by the negative squared error to the mean close.
+    struct TargetCloseStrategy {
+        targets: Vec<f64>,
+    }
+
+    impl Strategy for TargetCloseStrategy {
+        type Params = f64;
+
+        fn candidate_params(&self) -> Vec<f64> {
+            self.targets.clone()
+        }
+
+        fn evaluate(&self, params: &f64, bars: &[Bar]) -> f64 {
+            let mean = bars.iter().map(|bar| bar.close.as_f64()).sum::<f64>() / bars.len() as f64;
+            -(mean - params).powi(2)
+        }
+    }
+
+    #[rstest]
+    fn test_walk_forward() {
+        let bars = bars_from_closes(&[1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 2.0, 3.0, 3.0, 3.0]);
+        let strategy = TargetCloseStrategy {
+            targets: vec![1.0, 1.5, 2.0, 3.0],
+        };
+        let results = Bar::walk_forward(&bars, &strategy, 4, 2);
+
+        // The trailing bar is too few for another full period
+        assert_eq!(results.len(), 3);
+        let expected = [
+            (bars[0].ts_event, 1.0, -1.0),
+            (bars[2].ts_event, 1.5, -0.25),
+            (bars[4].ts_event, 2.0, -1.0),
+        ];
+        for (result, (period_start, best_params, out_sample_metric)) in results.iter().zip(expected)
+        {
+            assert_eq!(result.period_start, period_start);
+            assert_eq!(result.best_params, best_params);
+            assert!(approx_eq!(
+                f64,
+                result.in_sample_metric,
+                0.0,
+                epsilon = 1e-12
+            ));
+            assert!(approx_eq!(
+                f64,
+                result.out_sample_metric,
+                out_sample_metric,
+                epsilon = 1e-12
+            ));
+        }
+    }
+
+    #[rstest]
+    fn test_walk_forward_invalid_input() {
+        let bars = bars_from_closes(&[1.0, 2.0, 3.0, 4.0]);
+        let strategy = TargetCloseStrategy {
+            targets: vec![1.0, 2.0],
+        };
+        let no_candidates = TargetCloseStrategy {
+            targets: Vec::new(),
+        };
+
+        assert!(Bar::walk_forward(&bars, &strategy, 0, 2).is_empty());
+        assert!(Bar::walk_forward(&bars, &strategy, 2, 0).is_empty());
+        assert!(Bar::walk_forward(&bars, &strategy, 3, 2).is_empty());
+        assert!(Bar::walk_forward(&bars, &no_candidates, 2, 2).is_empty());
+        assert_eq!(Bar::walk_forward(&bars, &strategy, 2, 2).len(), 1);
+    }
+
+    #[rstest]
+    fn test_purged_kfold() {
+        let bars = bars_from_closes(&[1.0; 10]);
+        let folds = Bar::purged_kfold(&bars, 3, 1);
+
+        let expected: [(Vec<usize>, Vec<usize>); 3] = [
+            (vec![5, 6, 7, 8, 9], vec![0, 1, 2, 3]),
+            (vec![0, 1, 2, 8, 9], vec![4, 5, 6]),
+            (vec![0, 1, 2, 3, 4, 5], vec![7, 8, 9]),
+        ];
+        assert_eq!(folds, expected);
+    }
+
+    #[rstest]
+    fn test_purged_kfold_tests_cover_bars_without_overlap() {
+        let bars = bars_from_closes(&[1.0; 23]);
+        let embargo_bars = 2;
+        let folds = Bar::purged_kfold(&bars, 5, embargo_bars);
+
+        let tests: Vec<usize> = folds.iter().flat_map(|(_, test)| test.clone()).collect();
+        assert_eq!(tests, (0..bars.len()).collect::<Vec<_>>());
+        for (train, test) in &folds {
+            let (first, last) = (test[0], test[test.len() - 1]);
+            assert!(train
+                .iter()
+                .all(|&i| i + embargo_bars < first || i > last + embargo_bars));
+        }
+    }
+
+    #[rstest]
+    fn test_purged_kfold_invalid_input() {
+        let bars = bars_from_closes(&[1.0, 2.0, 3.0]);
+
+        assert!(Bar::purged_kfold(&bars, 1, 0).is_empty());
+        assert!(Bar::purged_kfold(&bars, 4, 0).is_empty());
+        assert_eq!(Bar::purged_kfold(&bars, 3, 0).len(), 3);
+    }
+
+    #[rstest]
+    fn test_combinations() {
+        assert_eq!(
+            combinations(4, 2),
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3],
+            ]
+        );
+        assert_eq!(combinations(3, 3), vec![vec![0, 1, 2]]);
+    }
+
+    #[rstest]
+    fn test_cpcv() {
+        let bars = bars_from_closes(&[1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0]);
+        let strategy = TargetCloseStrategy {
+            targets: vec![1.0, 2.0, 3.0, 4.0],
+        };
+        let results = Bar::cpcv(&bars, &strategy, 4, 2, 0);
+
+        // C(4, 2) = 6 combinations, assembled into 6 * 2 / 4 = 3 paths
+        assert_eq!(results.splits.len(), 6);
+        assert_eq!(results.paths.len(), 3);
+        assert!(results.paths.iter().all(|path| path.len() == 4));
+
+        // Testing groups 0 and 1 trains on closes 3 and 4, for a mean of 3.5
+        // where the candidates 3.0 and 4.0 tie, so the first is chosen
+        let split = &results.splits[0];
+        assert_eq!(split.test_groups, vec![0, 1]);
+        assert_eq!(split.train_indices, vec![4, 5, 6, 7]);
+        assert_eq!(split.best_params, Some(3.0));
+        assert_eq!(split.test_metrics, vec![-4.0, -1.0]);
+
+        // The first path takes group 3 from its first split, testing groups 0 and 3
+        let split = &results.splits[2];
+        assert_eq!(split.test_groups, vec![0, 3]);
+        assert_eq!(results.paths[0][0], results.splits[0].test_metrics[0]);
+        assert_eq!(results.paths[0][3], split.test_metrics[1]);
+    }
+
+    #[rstest]
+    fn test_cpcv_purges_embargo_around_each_test_group() {
+        let bars = bars_from_closes(&[1.0; 12]);
+        let strategy = TargetCloseStrategy { targets: vec![1.0] };
+        let results = Bar::cpcv(&bars, &strategy, 4, 2, 1);
+
+        let split = &results.splits[1];
+        assert_eq!(split.test_groups, vec![0, 2]);
+        assert_eq!(split.train_indices, vec![4, 10, 11]);
+    }
+
+    #[rstest]
+    fn test_cpcv_invalid_input() {
+        let bars = bars_from_closes(&[1.0, 2.0, 3.0, 4.0]);
+        let strategy = TargetCloseStrategy { targets: vec![1.0] };
+
+        assert!(Bar::cpcv(&bars, &strategy, 1, 1, 0).splits.is_empty());
+        assert!(Bar::cpcv(&bars, &strategy, 5, 1, 0).splits.is_empty());
+        assert!(Bar::cpcv(&bars, &strategy, 4, 0, 0).splits.is_empty());
+        assert!(Bar::cpcv(&bars, &strategy, 4, 4, 0).paths.is_empty());
+        assert_eq!(Bar::cpcv(&bars, &strategy, 4, 1, 0).paths.len(), 1);
+    }
+}
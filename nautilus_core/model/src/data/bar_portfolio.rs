@@ -0,0 +1,1385 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Multi-asset portfolio risk and allocation computed from series of [`Bar`]s.
+
+use std::collections::HashMap;
+
+use super::bar::Bar;
+use crate::{identifiers::instrument_id::InstrumentId, position::Position};
+
+/// Returns the lower triangular Cholesky factor L of the row-major `n` x `n`
+/// matrix `values`, such that L * L^T = `values`, or `None` if the matrix is
+/// not positive definite.
+pub(super) fn cholesky(values: &[f64], n: usize) -> Option<Vec<f64>> {
+    let mut l = vec![0.0; n * n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| l[i * n + k] * l[j * n + k]).sum();
+            if i == j {
+                let diagonal = values[i * n + i] - sum;
+                // Pivots within rounding error of zero indicate a singular matrix
+                if diagonal.is_nan() || diagonal <= f64::EPSILON * n as f64 * values[i * n + i] {
+                    return None;
+                }
+                l[i * n + i] = diagonal.sqrt();
+            } else {
+                l[i * n + j] = (values[i * n + j] - sum) / l[j * n + j];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// Returns x solving L * L^T * x = `b` for the Cholesky factor `l`, by forward
+/// then back substitution.
+pub(super) fn cholesky_solve(l: &[f64], b: &[f64]) -> Vec<f64> {
+    let n = b.len();
+    let mut y: Vec<f64> = Vec::with_capacity(n);
+    for (i, b_i) in b.iter().enumerate() {
+        let sum: f64 = y
+            .iter()
+            .enumerate()
+            .map(|(k, y_k)| l[i * n + k] * y_k)
+            .sum();
+        y.push((b_i - sum) / l[i * n + i]);
+    }
+    let mut x = vec![0.0; n];
+    for (i, y_i) in y.iter().enumerate().rev() {
+        let sum: f64 = (i + 1..n).map(|k| l[k * n + i] * x[k]).sum();
+        x[i] = (y_i - sum) / l[i * n + i];
+    }
+    x
+}
+
+/// Returns the Euclidean projection of `v` onto the probability simplex, the
+/// closest vector with non-negative elements summing to 1.
+fn project_to_simplex(v: &[f64]) -> Vec<f64> {
+    let mut sorted = v.to_vec();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    let mut cumulative = 0.0;
+    let mut theta = 0.0;
+    for (j, u) in sorted.iter().enumerate() {
+        cumulative += u;
+        let candidate = (cumulative - 1.0) / (j + 1) as f64;
+        if u - candidate > 0.0 {
+            theta = candidate;
+        }
+    }
+    v.iter().map(|x| (x - theta).max(0.0)).collect()
+}
+
+/// Represents the linkage method used to merge clusters in hierarchical clustering.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LinkageMethod {
+    /// The distance between clusters is the minimum distance between their members.
+    Single,
+    /// The distance between clusters is the maximum distance between their members.
+    Complete,
+    /// The distance between clusters is the mean distance between their members.
+    Average,
+    /// Merges the clusters giving the minimum increase in within-cluster variance.
+    Ward,
+}
+
+impl LinkageMethod {
+    /// Returns the Lance-Williams distance from cluster k to the merge of
+    /// clusters a and b, given the distances and sizes of the clusters.
+    fn merged_distance(
+        self,
+        d_ak: f64,
+        d_bk: f64,
+        d_ab: f64,
+        n_a: usize,
+        n_b: usize,
+        n_k: usize,
+    ) -> f64 {
+        let (n_a, n_b, n_k) = (n_a as f64, n_b as f64, n_k as f64);
+        match self {
+            Self::Single => d_ak.min(d_bk),
+            Self::Complete => d_ak.max(d_bk),
+            Self::Average => (n_a * d_ak + n_b * d_bk) / (n_a + n_b),
+            Self::Ward => (((n_a + n_k) * d_ak.powi(2) + (n_b + n_k) * d_bk.powi(2)
+                - n_k * d_ab.powi(2))
+                / (n_a + n_b + n_k))
+                .max(0.0)
+                .sqrt(),
+        }
+    }
+}
+
+/// Returns the leaves of the agglomerative clustering of the row-major `n` x `n`
+/// `distances`, in quasi-diagonal order so that similar leaves are adjacent.
+fn cluster_order(distances: &[f64], n: usize, linkage: LinkageMethod) -> Vec<usize> {
+    // Each active cluster holds its leaves in dendrogram order
+    let mut clusters: Vec<Vec<usize>> = (0..n).map(|i| vec![i]).collect();
+    let mut dist: Vec<Vec<f64>> = distances.chunks(n).map(<[f64]>::to_vec).collect();
+    while clusters.len() > 1 {
+        let len = clusters.len();
+        // Unwrapping is safe as there are at least two clusters
+        let (a, b) = (0..len)
+            .flat_map(|i| (i + 1..len).map(move |j| (i, j)))
+            .min_by(|x, y| dist[x.0][x.1].total_cmp(&dist[y.0][y.1]))
+            .unwrap();
+
+        let d_ab = dist[a][b];
+        let merged: Vec<f64> = (0..clusters.len())
+            .map(|k| {
+                linkage.merged_distance(
+                    dist[a][k],
+                    dist[b][k],
+                    d_ab,
+                    clusters[a].len(),
+                    clusters[b].len(),
+                    clusters[k].len(),
+                )
+            })
+            .collect();
+        for (k, d) in merged.into_iter().enumerate() {
+            dist[a][k] = d;
+            dist[k][a] = d;
+        }
+        dist[a][a] = 0.0;
+
+        let leaves = clusters.remove(b);
+        clusters[a].extend(leaves);
+        dist.remove(b);
+        for row in &mut dist {
+            row.remove(b);
+        }
+    }
+    clusters.pop().unwrap_or_default()
+}
+
+/// Returns the variance of the inverse-variance weighted portfolio of the
+/// `members` of the cluster.
+fn cluster_variance(cov_matrix: &CovarianceMatrix, members: &[usize]) -> f64 {
+    let inverse_variances: Vec<f64> = members
+        .iter()
+        .map(|i| 1.0 / cov_matrix.get(*i, *i))
+        .collect();
+    let total: f64 = inverse_variances.iter().sum();
+    let weights: Vec<f64> = inverse_variances.iter().map(|w| w / total).collect();
+    members
+        .iter()
+        .zip(&weights)
+        .map(|(i, w_i)| {
+            members
+                .iter()
+                .zip(&weights)
+                .map(|(j, w_j)| w_i * cov_matrix.get(*i, *j) * w_j)
+                .sum::<f64>()
+        })
+        .sum()
+}
+
+/// Returns the hierarchical risk parity weights in the instrument order of
+/// `cov_matrix`, which must have positive variances.
+fn hrp_allocation(cov_matrix: &CovarianceMatrix, linkage: LinkageMethod) -> Vec<f64> {
+    let n = cov_matrix.len();
+    let distances: Vec<f64> = cov_matrix
+        .correlation_matrix()
+        .iter()
+        .map(|rho| ((1.0 - rho) / 2.0).max(0.0).sqrt())
+        .collect();
+    let order = cluster_order(&distances, n, linkage);
+
+    // Recursively bisect the ordered leaves, splitting the weight of each
+    // cluster between its halves in inverse proportion to their variance
+    let mut weights = vec![1.0; n];
+    let mut stack = vec![order.as_slice()];
+    while let Some(cluster) = stack.pop() {
+        if cluster.len() < 2 {
+            continue;
+        }
+        let (left, right) = cluster.split_at(cluster.len() / 2);
+        let left_variance = cluster_variance(cov_matrix, left);
+        let right_variance = cluster_variance(cov_matrix, right);
+        let alpha = 1.0 - left_variance / (left_variance + right_variance);
+        for i in left {
+            weights[*i] *= alpha;
+        }
+        for i in right {
+            weights[*i] *= 1.0 - alpha;
+        }
+        stack.push(left);
+        stack.push(right);
+    }
+    weights
+}
+
+/// Represents a square covariance matrix of instrument returns, stored row-major
+/// in the order of its instrument IDs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CovarianceMatrix {
+    instrument_ids: Vec<InstrumentId>,
+    values: Vec<f64>,
+}
+
+impl CovarianceMatrix {
+    /// Creates a new [`CovarianceMatrix`] from the row-major `values`.
+    ///
+    /// # Panics
+    ///
+    /// If `values` does not hold the square of the number of `instrument_ids`.
+    #[must_use]
+    pub fn new(instrument_ids: Vec<InstrumentId>, values: Vec<f64>) -> Self {
+        assert_eq!(
+            values.len(),
+            instrument_ids.len() * instrument_ids.len(),
+            "`values` length was not the square of the number of instruments"
+        );
+        Self {
+            instrument_ids,
+            values,
+        }
+    }
+
+    /// Returns the number of instruments (the matrix dimension).
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.instrument_ids.len()
+    }
+
+    /// Returns whether the matrix holds no instruments.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.instrument_ids.is_empty()
+    }
+
+    /// Returns the instrument IDs in row (and column) order.
+    #[must_use]
+    pub fn instrument_ids(&self) -> &[InstrumentId] {
+        &self.instrument_ids
+    }
+
+    /// Returns the row-major matrix values.
+    #[must_use]
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns the covariance at row `i` and column `j`.
+    ///
+    /// # Panics
+    ///
+    /// If `i` or `j` is out of bounds.
+    #[must_use]
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        assert!(i < self.len() && j < self.len(), "index out of bounds");
+        self.values[i * self.len() + j]
+    }
+
+    /// Returns the volatility (standard deviation) of each instrument, the
+    /// square root of the matrix diagonal.
+    #[must_use]
+    pub fn volatility_vector(&self) -> Vec<f64> {
+        (0..self.len()).map(|i| self.get(i, i).sqrt()).collect()
+    }
+
+    /// Returns the diversification ratio of the portfolio with the given
+    /// `weights`, the weighted average volatility divided by the portfolio
+    /// volatility, or `None` if the portfolio has no volatility.
+    ///
+    /// # Panics
+    ///
+    /// If the number of `weights` differs from the number of instruments.
+    #[must_use]
+    pub fn diversification_ratio(&self, weights: &[f64]) -> Option<f64> {
+        assert_eq!(
+            weights.len(),
+            self.len(),
+            "`weights` length was not the number of instruments"
+        );
+        let n = self.len();
+        let weighted_volatility: f64 = weights
+            .iter()
+            .zip(self.volatility_vector())
+            .map(|(w, vol)| w * vol)
+            .sum();
+        let variance: f64 = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| weights[i] * self.get(i, j) * weights[j])
+                    .sum::<f64>()
+            })
+            .sum();
+        if variance <= 0.0 {
+            return None;
+        }
+        Some(weighted_volatility / variance.sqrt())
+    }
+
+    /// Returns the row-major correlation matrix in the same instrument order.
+    ///
+    /// Correlations involving an instrument with zero volatility are 0.0, other
+    /// than the unit diagonal.
+    #[must_use]
+    pub fn correlation_matrix(&self) -> Vec<f64> {
+        let vols = self.volatility_vector();
+        let n = self.len();
+        (0..n * n)
+            .map(|k| {
+                let (i, j) = (k / n, k % n);
+                if i == j {
+                    1.0
+                } else if vols[i] == 0.0 || vols[j] == 0.0 {
+                    0.0
+                } else {
+                    self.values[k] / (vols[i] * vols[j])
+                }
+            })
+            .collect()
+    }
+}
+
+/// Represents the decomposition of an instrument's returns onto a set of factor
+/// returns by ordinary least squares regression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FactorDecomposition {
+    /// The regression intercept, the return not explained by the factors.
+    pub alpha: f64,
+    /// The sensitivity of the instrument's returns to each factor.
+    pub betas: HashMap<String, f64>,
+    /// The proportion of the variance of the returns explained by the factors.
+    pub r_squared: f64,
+    /// The t-statistic of each factor beta.
+    pub t_stats: HashMap<String, f64>,
+}
+
+impl Bar {
+    /// Returns the sample covariance matrix of the close to close returns of
+    /// each instrument over the last `period` bars.
+    ///
+    /// Instruments are ordered by ID and their returns are aligned by index
+    /// from the most recent bar. Returns `None` if `bars_map` is empty, `period`
+    /// is less than 2, or any instrument has fewer than `period + 1` bars.
+    #[must_use]
+    pub fn covariance_matrix(
+        bars_map: &HashMap<InstrumentId, Vec<Bar>>,
+        period: usize,
+    ) -> Option<CovarianceMatrix> {
+        if bars_map.is_empty() || period < 2 {
+            return None;
+        }
+        let mut instrument_ids: Vec<InstrumentId> = bars_map.keys().copied().collect();
+        instrument_ids.sort();
+
+        let returns = instrument_ids
+            .iter()
+            .map(|id| {
+                let bars = &bars_map[id];
+                let window = &bars[bars.len().checked_sub(period + 1)?..];
+                Some(
+                    window
+                        .windows(2)
+                        .map(|w| w[1].close.as_f64() / w[0].close.as_f64() - 1.0)
+                        .collect::<Vec<f64>>(),
+                )
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let means: Vec<f64> = returns
+            .iter()
+            .map(|r| r.iter().sum::<f64>() / period as f64)
+            .collect();
+
+        let n = instrument_ids.len();
+        let mut values = vec![0.0; n * n];
+        for i in 0..n {
+            for j in i..n {
+                let covariance = returns[i]
+                    .iter()
+                    .zip(&returns[j])
+                    .map(|(a, b)| (a - means[i]) * (b - means[j]))
+                    .sum::<f64>()
+                    / (period - 1) as f64;
+                values[i * n + j] = covariance;
+                values[j * n + i] = covariance;
+            }
+        }
+        Some(CovarianceMatrix::new(instrument_ids, values))
+    }
+
+    /// Returns the Markowitz mean-variance optimal portfolio weights, maximizing
+    /// w^T μ - (`risk_aversion` / 2) * w^T Σ w subject to the weights summing
+    /// to 1.
+    ///
+    /// The `expected_returns` μ must be in the instrument order of `cov_matrix`
+    /// Σ, and weights may be negative (short). The system is solved by Cholesky
+    /// decomposition, and an empty vector is returned if the lengths differ,
+    /// `risk_aversion` is not positive, or Σ is not positive definite.
+    #[must_use]
+    pub fn mean_variance_optimize(
+        expected_returns: &[f64],
+        cov_matrix: &CovarianceMatrix,
+        risk_aversion: f64,
+    ) -> Vec<f64> {
+        let n = cov_matrix.len();
+        if n == 0 || expected_returns.len() != n || risk_aversion.is_nan() || risk_aversion <= 0.0 {
+            return Vec::new();
+        }
+        let Some(l) = cholesky(cov_matrix.values(), n) else {
+            return Vec::new();
+        };
+
+        // w = Σ^-1 μ / λ + γ Σ^-1 1, with γ chosen so the weights sum to 1
+        let inv_mu = cholesky_solve(&l, expected_returns);
+        let inv_ones = cholesky_solve(&l, &vec![1.0; n]);
+        let gamma =
+            (1.0 - inv_mu.iter().sum::<f64>() / risk_aversion) / inv_ones.iter().sum::<f64>();
+        inv_mu
+            .iter()
+            .zip(&inv_ones)
+            .map(|(a, b)| a / risk_aversion + gamma * b)
+            .collect()
+    }
+
+    /// Returns the equal risk contribution (risk parity) portfolio weights, for
+    /// which each instrument contributes equally to the portfolio variance.
+    ///
+    /// Weights are long only, sum to 1 and are in the instrument order of
+    /// `cov_matrix`. They are found by cyclical coordinate descent on the
+    /// convex log-barrier formulation of the problem, and an empty vector is
+    /// returned if the matrix is empty or has a non-positive variance.
+    #[must_use]
+    pub fn risk_parity_weights(cov_matrix: &CovarianceMatrix) -> Vec<f64> {
+        const MAX_ITERATIONS: usize = 1_000;
+        const TOLERANCE: f64 = 1e-12;
+
+        let n = cov_matrix.len();
+        if n == 0 || (0..n).any(|i| cov_matrix.get(i, i).is_nan() || cov_matrix.get(i, i) <= 0.0) {
+            return Vec::new();
+        }
+
+        // Minimize y^T Σ y / 2 - Σ ln(y_i) / n, whose first order conditions give
+        // equal risk contributions y_i * (Σ y)_i = 1 / n
+        let budget = 1.0 / n as f64;
+        let mut y: Vec<f64> = (0..n).map(|i| 1.0 / cov_matrix.get(i, i).sqrt()).collect();
+        for _ in 0..MAX_ITERATIONS {
+            let mut max_change: f64 = 0.0;
+            for i in 0..n {
+                let variance = cov_matrix.get(i, i);
+                let b: f64 = (0..n)
+                    .filter(|j| *j != i)
+                    .map(|j| cov_matrix.get(i, j) * y[j])
+                    .sum();
+                let y_i = (-b + (b * b + 4.0 * variance * budget).sqrt()) / (2.0 * variance);
+                max_change = max_change.max((y_i - y[i]).abs());
+                y[i] = y_i;
+            }
+            if max_change < TOLERANCE {
+                break;
+            }
+        }
+
+        let total: f64 = y.iter().sum();
+        y.iter().map(|y_i| y_i / total).collect()
+    }
+
+    /// Returns the global minimum variance portfolio weights, minimizing w^T Σ w
+    /// subject to the weights summing to 1, ignoring expected returns.
+    ///
+    /// Without `long_only` the closed form Σ^-1 1 / (1^T Σ^-1 1) is solved by
+    /// Cholesky decomposition and weights may be negative. With `long_only` the
+    /// weights are also constrained to be non-negative, and are found by
+    /// projected gradient descent onto the simplex. Weights are in the instrument
+    /// order of `cov_matrix`, and an empty vector is returned if Σ is empty or
+    /// not positive definite.
+    #[must_use]
+    pub fn minimum_variance_weights(cov_matrix: &CovarianceMatrix, long_only: bool) -> Vec<f64> {
+        const MAX_ITERATIONS: usize = 10_000;
+        const TOLERANCE: f64 = 1e-14;
+
+        let n = cov_matrix.len();
+        if n == 0 {
+            return Vec::new();
+        }
+        let Some(l) = cholesky(cov_matrix.values(), n) else {
+            return Vec::new();
+        };
+        let inv_ones = cholesky_solve(&l, &vec![1.0; n]);
+        let total: f64 = inv_ones.iter().sum();
+        let mut weights: Vec<f64> = inv_ones.iter().map(|w| w / total).collect();
+        if !long_only || weights.iter().all(|w| *w >= 0.0) {
+            return weights;
+        }
+
+        // Step by the inverse of the Lipschitz constant of the gradient 2 Σ w,
+        // bounding the largest eigenvalue of Σ by its maximum absolute row sum
+        let max_row_sum = (0..n)
+            .map(|i| (0..n).map(|j| cov_matrix.get(i, j).abs()).sum::<f64>())
+            .fold(0.0, f64::max);
+        let step = 1.0 / (2.0 * max_row_sum);
+        weights = project_to_simplex(&weights);
+        for _ in 0..MAX_ITERATIONS {
+            let stepped: Vec<f64> = (0..n)
+                .map(|i| {
+                    let gradient: f64 = (0..n)
+                        .map(|j| 2.0 * cov_matrix.get(i, j) * weights[j])
+                        .sum();
+                    weights[i] - step * gradient
+                })
+                .collect();
+            let projected = project_to_simplex(&stepped);
+            let max_change = projected
+                .iter()
+                .zip(&weights)
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0, f64::max);
+            weights = projected;
+            if max_change < TOLERANCE {
+                break;
+            }
+        }
+        weights
+    }
+
+    /// Returns the Lopez de Prado hierarchical risk parity (HRP) weights of each
+    /// instrument, from the covariance of returns over the last `period` bars.
+    ///
+    /// Instruments are clustered on the correlation distance sqrt((1 - ρ) / 2)
+    /// using the given `linkage`, then weight is allocated top down by recursive
+    /// bisection of the clustered order, without inverting the covariance
+    /// matrix. Weights are long only and sum to 1. An empty map is returned if
+    /// the covariance matrix cannot be computed (see [`Bar::covariance_matrix`])
+    /// or any instrument has zero variance.
+    #[must_use]
+    pub fn hrp_weights(
+        bars_map: &HashMap<InstrumentId, Vec<Bar>>,
+        period: usize,
+        linkage: LinkageMethod,
+    ) -> HashMap<InstrumentId, f64> {
+        let Some(cov_matrix) = Bar::covariance_matrix(bars_map, period) else {
+            return HashMap::new();
+        };
+        if (0..cov_matrix.len()).any(|i| cov_matrix.get(i, i) <= 0.0) {
+            return HashMap::new();
+        }
+        cov_matrix
+            .instrument_ids()
+            .iter()
+            .copied()
+            .zip(hrp_allocation(&cov_matrix, linkage))
+            .collect()
+    }
+
+    /// Returns the Choueifaty maximum diversification portfolio weights, which
+    /// maximize the diversification ratio w^T σ / sqrt(w^T Σ w).
+    ///
+    /// Weights are long only, sum to 1 and are in the instrument order of
+    /// `cov_matrix`. They are found as the long only minimum variance weights
+    /// of the correlation matrix, scaled by the inverse volatilities. An empty
+    /// vector is returned if Σ is empty or not positive definite.
+    #[must_use]
+    pub fn max_diversification_weights(cov_matrix: &CovarianceMatrix) -> Vec<f64> {
+        if cov_matrix.is_empty() || cholesky(cov_matrix.values(), cov_matrix.len()).is_none() {
+            return Vec::new();
+        }
+        let correlation = CovarianceMatrix::new(
+            cov_matrix.instrument_ids().to_vec(),
+            cov_matrix.correlation_matrix(),
+        );
+        let scaled: Vec<f64> = Bar::minimum_variance_weights(&correlation, true)
+            .iter()
+            .zip(cov_matrix.volatility_vector())
+            .map(|(w, vol)| w / vol)
+            .collect();
+        let total: f64 = scaled.iter().sum();
+        scaled.iter().map(|w| w / total).collect()
+    }
+
+    /// Returns the rebalancing trades (target minus current weight) for each
+    /// instrument if any of the `portfolio_weights` has drifted from its target
+    /// weight by more than `threshold`, otherwise `None`.
+    ///
+    /// When rebalancing, all weights are traded back to target, so for an equal
+    /// weight portfolio of n instruments the targets are each 1 / n.
+    ///
+    /// # Panics
+    ///
+    /// If the number of `portfolio_weights` differs from `target_weights`.
+    #[must_use]
+    pub fn rebalance_signal(
+        portfolio_weights: &[f64],
+        target_weights: &[f64],
+        threshold: f64,
+    ) -> Option<Vec<f64>> {
+        assert_eq!(
+            portfolio_weights.len(),
+            target_weights.len(),
+            "`portfolio_weights` length was not the `target_weights` length"
+        );
+        let trades: Vec<f64> = target_weights
+            .iter()
+            .zip(portfolio_weights)
+            .map(|(target, current)| target - current)
+            .collect();
+        if trades.iter().any(|trade| trade.abs() > threshold) {
+            Some(trades)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the decomposition of the close to close returns of the
+    /// `instrument` bars onto the `factors` returns (such as MKT, SMB, HML and
+    /// MOM), regressing the returns on the factors by ordinary least squares.
+    ///
+    /// The factor returns are aligned by index with the instrument returns from
+    /// the most recent bar, using the longest history common to all series.
+    /// Returns `None` if there are no `factors`, too few returns to estimate
+    /// the t-statistics, or the returns or any factor have no dispersion, or
+    /// the factors are collinear. A perfect fit gives infinite t-statistics.
+    #[must_use]
+    pub fn factor_decompose(
+        instrument: &[Bar],
+        factors: &HashMap<String, Vec<f64>>,
+    ) -> Option<FactorDecomposition> {
+        let mut names: Vec<&String> = factors.keys().collect();
+        names.sort();
+        let returns: Vec<f64> = instrument
+            .windows(2)
+            .map(|w| w[1].close.as_f64() / w[0].close.as_f64() - 1.0)
+            .collect();
+        let m = names
+            .iter()
+            .map(|name| factors[*name].len())
+            .fold(returns.len(), usize::min);
+        // The regressors are an intercept and each factor
+        let k = names.len() + 1;
+        if names.is_empty() || m <= k {
+            return None;
+        }
+        let y = &returns[returns.len() - m..];
+        let columns: Vec<&[f64]> = names
+            .iter()
+            .map(|name| &factors[*name][factors[*name].len() - m..])
+            .collect();
+        let regressor = |t: usize, j: usize| if j == 0 { 1.0 } else { columns[j - 1][t] };
+
+        // Solve the normal equations X^T X b = X^T y
+        let mut xtx = vec![0.0; k * k];
+        let mut xty = vec![0.0; k];
+        for (t, y_t) in y.iter().enumerate() {
+            for (i, xty_i) in xty.iter_mut().enumerate() {
+                *xty_i += regressor(t, i) * y_t;
+                for j in 0..k {
+                    xtx[i * k + j] += regressor(t, i) * regressor(t, j);
+                }
+            }
+        }
+        let l = cholesky(&xtx, k)?;
+        // Rounding can leave a tiny positive pivot for collinear regressors
+        if (0..k).any(|j| l[j * k + j].powi(2) < 1e-12 * xtx[j * k + j]) {
+            return None;
+        }
+        let coefficients = cholesky_solve(&l, &xty);
+
+        let mean = y.iter().sum::<f64>() / m as f64;
+        let total: f64 = y.iter().map(|y_t| (y_t - mean).powi(2)).sum();
+        if total == 0.0 {
+            return None;
+        }
+        let residual: f64 = y
+            .iter()
+            .enumerate()
+            .map(|(t, y_t)| {
+                let fitted: f64 = (0..k).map(|j| coefficients[j] * regressor(t, j)).sum();
+                (y_t - fitted).powi(2)
+            })
+            .sum();
+        let residual_variance = residual / (m - k) as f64;
+
+        let mut betas = HashMap::with_capacity(names.len());
+        let mut t_stats = HashMap::with_capacity(names.len());
+        for (i, name) in names.iter().enumerate() {
+            let j = i + 1;
+            // The variance of each coefficient is σ² times the diagonal of (X^T X)^-1
+            let mut unit = vec![0.0; k];
+            unit[j] = 1.0;
+            let standard_error = (residual_variance * cholesky_solve(&l, &unit)[j]).sqrt();
+            betas.insert((*name).clone(), coefficients[j]);
+            t_stats.insert((*name).clone(), coefficients[j] / standard_error);
+        }
+        Some(FactorDecomposition {
+            alpha: coefficients[0],
+            betas,
+            r_squared: 1.0 - residual / total,
+            t_stats,
+        })
+    }
+
+    /// Returns the profit and loss of the `position` under each historical
+    /// shock scenario, such as the 2008 or 2020 crises, in the settlement
+    /// currency of the position.
+    ///
+    /// Each scenario applies the cumulative return of its bars, from the open
+    /// of the first bar to the close of the last, to the average open price of
+    /// the position. Inverse instruments are valued as quantity * multiplier *
+    /// (1 / open - 1 / shocked). Scenarios without bars are excluded.
+    #[must_use]
+    pub fn stress_test(
+        position: &Position,
+        scenarios: &HashMap<String, Vec<Bar>>,
+    ) -> HashMap<String, f64> {
+        let quantity = position.signed_qty * position.multiplier.as_f64();
+        let open = position.avg_px_open;
+        scenarios
+            .iter()
+            .filter_map(|(name, bars)| {
+                let (first, last) = (bars.first()?, bars.last()?);
+                let shocked = open * last.close.as_f64() / first.open.as_f64();
+                let pnl = if position.is_inverse {
+                    quantity * (1.0 / open - 1.0 / shocked)
+                } else {
+                    quantity * (shocked - open)
+                };
+                Some((name.clone(), pnl))
+            })
+            .collect()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{data::bar::stubs::bars_from_closes, position::stubs::position_audusd};
+
+    fn create_bars_map(series: &[(&str, &[f64])]) -> HashMap<InstrumentId, Vec<Bar>> {
+        series
+            .iter()
+            .map(|(id, closes)| (InstrumentId::from(*id), bars_from_closes(closes)))
+            .collect()
+    }
+
+    #[rstest]
+    fn test_covariance_matrix() {
+        // Returns: AAA [0.1, -0.1, 0.1], BBB [0.2, -0.2, 0.2], CCC [-0.1, 0.1, -0.1]
+        let bars_map = create_bars_map(&[
+            ("CCC.SIM", &[5.0, 4.5, 4.95, 4.455]),
+            ("AAA.SIM", &[1.0, 1.0, 1.1, 0.99, 1.089]),
+            ("BBB.SIM", &[1.0, 1.2, 0.96, 1.152]),
+        ]);
+        let cov = Bar::covariance_matrix(&bars_map, 3).unwrap();
+
+        assert_eq!(
+            cov.instrument_ids(),
+            [
+                InstrumentId::from("AAA.SIM"),
+                InstrumentId::from("BBB.SIM"),
+                InstrumentId::from("CCC.SIM"),
+            ]
+        );
+        // Sample variance of [0.1, -0.1, 0.1] is 0.04 / 3
+        let var_a = 0.04 / 3.0;
+        let expected = [
+            var_a,
+            2.0 * var_a,
+            -var_a,
+            2.0 * var_a,
+            4.0 * var_a,
+            -2.0 * var_a,
+            -var_a,
+            -2.0 * var_a,
+            var_a,
+        ];
+        for (value, expected) in cov.values().iter().zip(expected) {
+            assert!(approx_eq!(f64, *value, expected, epsilon = 1e-9));
+        }
+    }
+
+    #[rstest]
+    fn test_correlation_matrix_and_volatility_vector() {
+        let bars_map = create_bars_map(&[
+            ("AAA.SIM", &[1.0, 1.1, 0.99, 1.089]),
+            ("BBB.SIM", &[1.0, 1.2, 0.96, 1.152]),
+            ("CCC.SIM", &[1.0, 1.0, 1.0, 1.0]),
+        ]);
+        let cov = Bar::covariance_matrix(&bars_map, 3).unwrap();
+        let vols = cov.volatility_vector();
+        let corr = cov.correlation_matrix();
+
+        let vol_a = (0.04_f64 / 3.0).sqrt();
+        assert!(approx_eq!(f64, vols[0], vol_a, epsilon = 1e-9));
+        assert!(approx_eq!(f64, vols[1], 2.0 * vol_a, epsilon = 1e-9));
+        assert_eq!(vols[2], 0.0);
+        let expected = [1.0, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+        for (value, expected) in corr.iter().zip(expected) {
+            assert!(approx_eq!(f64, *value, expected, epsilon = 1e-9));
+        }
+    }
+
+    #[rstest]
+    fn test_covariance_matrix_insufficient_data() {
+        let bars_map = create_bars_map(&[("AAA.SIM", &[1.0, 1.1, 1.2]), ("BBB.SIM", &[1.0, 1.1])]);
+
+        assert!(Bar::covariance_matrix(&bars_map, 2).is_none());
+        assert!(Bar::covariance_matrix(&bars_map, 1).is_none());
+        assert!(Bar::covariance_matrix(&HashMap::new(), 2).is_none());
+    }
+
+    #[rstest]
+    #[should_panic(expected = "`values` length was not the square")]
+    fn test_covariance_matrix_new_invalid_values() {
+        let _ = CovarianceMatrix::new(vec![InstrumentId::from("AAA.SIM")], vec![1.0, 0.0]);
+    }
+
+    #[rstest]
+    fn test_mean_variance_optimize() {
+        let ids = vec![InstrumentId::from("AAA.SIM"), InstrumentId::from("BBB.SIM")];
+        let cov = CovarianceMatrix::new(ids, vec![0.04, 0.0, 0.0, 0.01]);
+        let weights = Bar::mean_variance_optimize(&[0.1, 0.05], &cov, 2.0);
+
+        assert_eq!(weights.len(), 2);
+        assert!(approx_eq!(f64, weights[0], 0.7, epsilon = 1e-9));
+        assert!(approx_eq!(f64, weights[1], 0.3, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_mean_variance_optimize_correlated() {
+        let ids = vec![
+            InstrumentId::from("AAA.SIM"),
+            InstrumentId::from("BBB.SIM"),
+            InstrumentId::from("CCC.SIM"),
+        ];
+        let cov = CovarianceMatrix::new(
+            ids,
+            vec![0.04, 0.006, 0.002, 0.006, 0.09, 0.009, 0.002, 0.009, 0.01],
+        );
+        let expected_returns = [0.08, 0.12, 0.03];
+        let weights = Bar::mean_variance_optimize(&expected_returns, &cov, 3.0);
+
+        assert!(approx_eq!(
+            f64,
+            weights.iter().sum::<f64>(),
+            1.0,
+            epsilon = 1e-9
+        ));
+        // First order condition: μ - λ Σ w is equal across all instruments
+        let marginals: Vec<f64> = (0..3)
+            .map(|i| {
+                let risk: f64 = (0..3).map(|j| cov.get(i, j) * weights[j]).sum();
+                expected_returns[i] - 3.0 * risk
+            })
+            .collect();
+        assert!(approx_eq!(f64, marginals[0], marginals[1], epsilon = 1e-9));
+        assert!(approx_eq!(f64, marginals[0], marginals[2], epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_mean_variance_optimize_invalid_input() {
+        let ids = vec![InstrumentId::from("AAA.SIM"), InstrumentId::from("BBB.SIM")];
+        let cov = CovarianceMatrix::new(ids.clone(), vec![0.04, 0.0, 0.0, 0.01]);
+        let singular = CovarianceMatrix::new(ids, vec![0.04, 0.04, 0.04, 0.04]);
+
+        assert!(Bar::mean_variance_optimize(&[0.1], &cov, 2.0).is_empty());
+        assert!(Bar::mean_variance_optimize(&[0.1, 0.05], &cov, 0.0).is_empty());
+        assert!(Bar::mean_variance_optimize(&[0.1, 0.05], &singular, 2.0).is_empty());
+    }
+
+    #[rstest]
+    fn test_risk_parity_weights_uncorrelated() {
+        let ids = vec![InstrumentId::from("AAA.SIM"), InstrumentId::from("BBB.SIM")];
+        let cov = CovarianceMatrix::new(ids, vec![0.04, 0.0, 0.0, 0.01]);
+        let weights = Bar::risk_parity_weights(&cov);
+
+        // Weights are inversely proportional to volatility
+        assert!(approx_eq!(f64, weights[0], 1.0 / 3.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, weights[1], 2.0 / 3.0, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_risk_parity_weights_equal_risk_contributions() {
+        let ids = vec![
+            InstrumentId::from("AAA.SIM"),
+            InstrumentId::from("BBB.SIM"),
+            InstrumentId::from("CCC.SIM"),
+        ];
+        let cov = CovarianceMatrix::new(
+            ids,
+            vec![0.04, 0.006, 0.002, 0.006, 0.09, 0.009, 0.002, 0.009, 0.01],
+        );
+        let weights = Bar::risk_parity_weights(&cov);
+
+        assert!(approx_eq!(
+            f64,
+            weights.iter().sum::<f64>(),
+            1.0,
+            epsilon = 1e-9
+        ));
+        assert!(weights.iter().all(|w| *w > 0.0));
+        let contributions: Vec<f64> = (0..3)
+            .map(|i| weights[i] * (0..3).map(|j| cov.get(i, j) * weights[j]).sum::<f64>())
+            .collect();
+        assert!(approx_eq!(
+            f64,
+            contributions[0],
+            contributions[1],
+            epsilon = 1e-9
+        ));
+        assert!(approx_eq!(
+            f64,
+            contributions[0],
+            contributions[2],
+            epsilon = 1e-9
+        ));
+    }
+
+    #[rstest]
+    fn test_risk_parity_weights_invalid_input() {
+        let ids = vec![InstrumentId::from("AAA.SIM"), InstrumentId::from("BBB.SIM")];
+        let cov = CovarianceMatrix::new(ids, vec![0.04, 0.0, 0.0, 0.0]);
+
+        assert!(Bar::risk_parity_weights(&cov).is_empty());
+        assert!(Bar::risk_parity_weights(&CovarianceMatrix::new(vec![], vec![])).is_empty());
+    }
+
+    #[rstest]
+    #[case(false)]
+    #[case(true)]
+    fn test_minimum_variance_weights_uncorrelated(#[case] long_only: bool) {
+        let ids = vec![InstrumentId::from("AAA.SIM"), InstrumentId::from("BBB.SIM")];
+        let cov = CovarianceMatrix::new(ids, vec![0.04, 0.0, 0.0, 0.01]);
+        let weights = Bar::minimum_variance_weights(&cov, long_only);
+
+        // Weights are inversely proportional to variance
+        assert!(approx_eq!(f64, weights[0], 0.2, epsilon = 1e-9));
+        assert!(approx_eq!(f64, weights[1], 0.8, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_minimum_variance_weights_long_only_constraint() {
+        // Highly correlated, so the unconstrained portfolio shorts the riskier asset
+        let ids = vec![InstrumentId::from("AAA.SIM"), InstrumentId::from("BBB.SIM")];
+        let cov = CovarianceMatrix::new(ids, vec![0.04, 0.018, 0.018, 0.01]);
+        let unconstrained = Bar::minimum_variance_weights(&cov, false);
+        let long_only = Bar::minimum_variance_weights(&cov, true);
+
+        assert!(approx_eq!(
+            f64,
+            unconstrained[0],
+            -4.0 / 7.0,
+            epsilon = 1e-9
+        ));
+        assert!(approx_eq!(
+            f64,
+            unconstrained[1],
+            11.0 / 7.0,
+            epsilon = 1e-9
+        ));
+        assert!(approx_eq!(f64, long_only[0], 0.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, long_only[1], 1.0, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_minimum_variance_weights_long_only_gradient_descent() {
+        let ids = vec![
+            InstrumentId::from("AAA.SIM"),
+            InstrumentId::from("BBB.SIM"),
+            InstrumentId::from("CCC.SIM"),
+        ];
+        let cov = CovarianceMatrix::new(
+            ids,
+            vec![0.04, 0.03, 0.002, 0.03, 0.03, 0.009, 0.002, 0.009, 0.01],
+        );
+        let weights = Bar::minimum_variance_weights(&cov, true);
+
+        assert!(approx_eq!(
+            f64,
+            weights.iter().sum::<f64>(),
+            1.0,
+            epsilon = 1e-9
+        ));
+        assert!(weights.iter().all(|w| *w >= 0.0));
+        // No feasible perturbation between held assets reduces the variance
+        let variance = |w: &[f64]| -> f64 {
+            (0..3)
+                .map(|i| (0..3).map(|j| w[i] * cov.get(i, j) * w[j]).sum::<f64>())
+                .sum()
+        };
+        for (i, j) in [(0, 1), (0, 2), (1, 2), (1, 0), (2, 0), (2, 1)] {
+            if weights[i] < 1e-3 {
+                continue;
+            }
+            let mut perturbed = weights.clone();
+            perturbed[i] -= 1e-3;
+            perturbed[j] += 1e-3;
+            assert!(variance(&perturbed) >= variance(&weights));
+        }
+    }
+
+    #[rstest]
+    fn test_minimum_variance_weights_invalid_input() {
+        let ids = vec![InstrumentId::from("AAA.SIM"), InstrumentId::from("BBB.SIM")];
+        let singular = CovarianceMatrix::new(ids, vec![0.04, 0.04, 0.04, 0.04]);
+
+        assert!(Bar::minimum_variance_weights(&singular, false).is_empty());
+        assert!(Bar::minimum_variance_weights(&singular, true).is_empty());
+        assert!(
+            Bar::minimum_variance_weights(&CovarianceMatrix::new(vec![], vec![]), true).is_empty()
+        );
+    }
+
+    #[rstest]
+    fn test_linkage_merged_distance() {
+        let merged = |linkage: LinkageMethod| linkage.merged_distance(0.2, 0.6, 0.3, 1, 1, 1);
+
+        assert!(approx_eq!(
+            f64,
+            merged(LinkageMethod::Single),
+            0.2,
+            epsilon = 1e-12
+        ));
+        assert!(approx_eq!(
+            f64,
+            merged(LinkageMethod::Complete),
+            0.6,
+            epsilon = 1e-12
+        ));
+        assert!(approx_eq!(
+            f64,
+            merged(LinkageMethod::Average),
+            0.4,
+            epsilon = 1e-12
+        ));
+        assert!(approx_eq!(
+            f64,
+            merged(LinkageMethod::Ward),
+            (0.71_f64 / 3.0).sqrt(),
+            epsilon = 1e-12
+        ));
+    }
+
+    #[rstest]
+    #[case(LinkageMethod::Single)]
+    #[case(LinkageMethod::Complete)]
+    #[case(LinkageMethod::Average)]
+    #[case(LinkageMethod::Ward)]
+    fn test_hrp_allocation_clustered_blocks(#[case] linkage: LinkageMethod) {
+        // Two correlated blocks, {AAA, CCC} and {BBB, DDD}, uncorrelated with each other
+        let ids = vec![
+            InstrumentId::from("AAA.SIM"),
+            InstrumentId::from("BBB.SIM"),
+            InstrumentId::from("CCC.SIM"),
+            InstrumentId::from("DDD.SIM"),
+        ];
+        let cov = CovarianceMatrix::new(
+            ids,
+            vec![
+                0.04, 0.0, 0.016, 0.0, //
+                0.0, 0.01, 0.0, 0.008, //
+                0.016, 0.0, 0.01, 0.0, //
+                0.0, 0.008, 0.0, 0.01,
+            ],
+        );
+        let weights = hrp_allocation(&cov, linkage);
+
+        assert!(approx_eq!(
+            f64,
+            weights.iter().sum::<f64>(),
+            1.0,
+            epsilon = 1e-9
+        ));
+        assert!(weights.iter().all(|w| *w > 0.0));
+        // Within each block the weights are inverse to the variances
+        assert!(approx_eq!(
+            f64,
+            weights[0] / weights[2],
+            0.25,
+            epsilon = 1e-9
+        ));
+        assert!(approx_eq!(f64, weights[1], weights[3], epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_hrp_weights() {
+        let bars_map = create_bars_map(&[
+            ("AAA.SIM", &[1.0, 1.1, 0.99, 1.089, 1.0]),
+            ("BBB.SIM", &[1.0, 1.01, 1.0, 1.02, 1.0]),
+            ("CCC.SIM", &[1.0, 0.9, 1.0, 0.95, 1.0]),
+        ]);
+        let weights = Bar::hrp_weights(&bars_map, 4, LinkageMethod::Single);
+
+        assert_eq!(weights.len(), 3);
+        assert!(approx_eq!(
+            f64,
+            weights.values().sum::<f64>(),
+            1.0,
+            epsilon = 1e-9
+        ));
+        assert!(weights.values().all(|w| *w > 0.0));
+        // The least volatile instrument receives the largest weight
+        let max_id = weights
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(id, _)| *id);
+        assert_eq!(max_id, Some(InstrumentId::from("BBB.SIM")));
+    }
+
+    #[rstest]
+    fn test_hrp_weights_invalid_input() {
+        let flat = create_bars_map(&[("AAA.SIM", &[1.0, 1.1, 1.2]), ("BBB.SIM", &[1.0, 1.0, 1.0])]);
+        let short = create_bars_map(&[("AAA.SIM", &[1.0, 1.1, 1.2]), ("BBB.SIM", &[1.0, 1.1])]);
+
+        assert!(Bar::hrp_weights(&flat, 2, LinkageMethod::Average).is_empty());
+        assert!(Bar::hrp_weights(&short, 2, LinkageMethod::Average).is_empty());
+    }
+
+    #[rstest]
+    fn test_diversification_ratio() {
+        let ids = vec![InstrumentId::from("AAA.SIM"), InstrumentId::from("BBB.SIM")];
+        let cov = CovarianceMatrix::new(ids, vec![0.04, 0.0, 0.0, 0.04]);
+
+        // Weighted volatility 0.2 over portfolio volatility sqrt(0.02)
+        let ratio = cov.diversification_ratio(&[0.5, 0.5]).unwrap();
+        assert!(approx_eq!(f64, ratio, 2.0_f64.sqrt(), epsilon = 1e-9));
+        assert!(approx_eq!(
+            f64,
+            cov.diversification_ratio(&[1.0, 0.0]).unwrap(),
+            1.0,
+            epsilon = 1e-9
+        ));
+        assert_eq!(cov.diversification_ratio(&[0.0, 0.0]), None);
+    }
+
+    #[rstest]
+    fn test_max_diversification_weights_uncorrelated() {
+        let ids = vec![InstrumentId::from("AAA.SIM"), InstrumentId::from("BBB.SIM")];
+        let cov = CovarianceMatrix::new(ids, vec![0.04, 0.0, 0.0, 0.01]);
+        let weights = Bar::max_diversification_weights(&cov);
+
+        // Weights are inversely proportional to volatility
+        assert!(approx_eq!(f64, weights[0], 1.0 / 3.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, weights[1], 2.0 / 3.0, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_max_diversification_weights_maximizes_ratio() {
+        let ids = vec![
+            InstrumentId::from("AAA.SIM"),
+            InstrumentId::from("BBB.SIM"),
+            InstrumentId::from("CCC.SIM"),
+        ];
+        let cov = CovarianceMatrix::new(
+            ids,
+            vec![0.04, 0.03, 0.002, 0.03, 0.03, 0.009, 0.002, 0.009, 0.01],
+        );
+        let weights = Bar::max_diversification_weights(&cov);
+        let ratio = cov.diversification_ratio(&weights).unwrap();
+
+        assert!(approx_eq!(
+            f64,
+            weights.iter().sum::<f64>(),
+            1.0,
+            epsilon = 1e-9
+        ));
+        assert!(weights.iter().all(|w| *w >= 0.0));
+        assert!(ratio > cov.diversification_ratio(&[1.0 / 3.0; 3]).unwrap());
+        for (i, j) in [(0, 1), (0, 2), (1, 2), (1, 0), (2, 0), (2, 1)] {
+            if weights[i] < 1e-3 {
+                continue;
+            }
+            let mut perturbed = weights.clone();
+            perturbed[i] -= 1e-3;
+            perturbed[j] += 1e-3;
+            assert!(cov.diversification_ratio(&perturbed).unwrap() <= ratio);
+        }
+    }
+
+    #[rstest]
+    fn test_max_diversification_weights_invalid_input() {
+        let ids = vec![InstrumentId::from("AAA.SIM"), InstrumentId::from("BBB.SIM")];
+        let singular = CovarianceMatrix::new(ids, vec![0.04, 0.04, 0.04, 0.04]);
+
+        assert!(Bar::max_diversification_weights(&singular).is_empty());
+        assert!(
+            Bar::max_diversification_weights(&CovarianceMatrix::new(vec![], vec![])).is_empty()
+        );
+    }
+
+    #[rstest]
+    fn test_rebalance_signal() {
+        let target = [0.25; 4];
+        let trades = Bar::rebalance_signal(&[0.35, 0.2, 0.25, 0.2], &target, 0.05).unwrap();
+
+        let expected = [-0.1, 0.05, 0.0, 0.05];
+        for (trade, expected) in trades.iter().zip(expected) {
+            assert!(approx_eq!(f64, *trade, expected, epsilon = 1e-12));
+        }
+        assert!(approx_eq!(
+            f64,
+            trades.iter().sum::<f64>(),
+            0.0,
+            epsilon = 1e-12
+        ));
+    }
+
+    #[rstest]
+    fn test_rebalance_signal_within_threshold() {
+        let target = [0.25; 4];
+
+        assert_eq!(
+            Bar::rebalance_signal(&[0.28, 0.22, 0.25, 0.25], &target, 0.05),
+            None
+        );
+        assert_eq!(Bar::rebalance_signal(&target, &target, 0.0), None);
+        assert_eq!(Bar::rebalance_signal(&[], &[], 0.05), None);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "`portfolio_weights` length was not the `target_weights` length")]
+    fn test_rebalance_signal_mismatched_lengths() {
+        let _ = Bar::rebalance_signal(&[0.5, 0.5], &[1.0], 0.05);
+    }
+
+    fn closes_from_returns(returns: &[f64]) -> Vec<f64> {
+        // A large initial close keeps the price precision error negligible
+        let mut closes = vec![10_000.0];
+        for r in returns {
+            closes.push(closes[closes.len() - 1] * (1.0 + r));
+        }
+        closes
+    }
+
+    fn create_factors(series: &[(&str, &[f64])]) -> HashMap<String, Vec<f64>> {
+        series
+            .iter()
+            .map(|(name, returns)| ((*name).to_string(), returns.to_vec()))
+            .collect()
+    }
+
+    #[rstest]
+    fn test_factor_decompose_exact_fit() {
+        let mkt = [0.01, -0.02, 0.015, 0.005, -0.01, 0.02, -0.005, 0.0];
+        let smb = [0.002, 0.004, -0.003, 0.001, 0.0, -0.002, 0.003, -0.001];
+        let returns: Vec<f64> = mkt
+            .iter()
+            .zip(smb)
+            .map(|(m, s)| 0.001 + 1.2 * m - 0.5 * s)
+            .collect();
+        let bars = bars_from_closes(&closes_from_returns(&returns));
+        let factors = create_factors(&[("MKT", &mkt), ("SMB", &smb)]);
+        let result = Bar::factor_decompose(&bars, &factors).unwrap();
+
+        assert!(approx_eq!(f64, result.alpha, 0.001, epsilon = 1e-6));
+        assert!(approx_eq!(f64, result.betas["MKT"], 1.2, epsilon = 1e-6));
+        assert!(approx_eq!(f64, result.betas["SMB"], -0.5, epsilon = 1e-6));
+        assert!(approx_eq!(f64, result.r_squared, 1.0, epsilon = 1e-6));
+    }
+
+    #[rstest]
+    fn test_factor_decompose_with_noise() {
+        let mkt = [
+            0.01, -0.02, 0.015, 0.005, -0.01, 0.02, -0.005, 0.0, 0.012, -0.008,
+        ];
+        let hml = [
+            0.003, -0.001, 0.002, -0.004, 0.001, 0.0, 0.002, -0.002, 0.001, 0.003,
+        ];
+        let noise = [
+            0.001, -0.001, 0.0005, -0.0005, 0.001, -0.001, 0.0, 0.0005, -0.0005, 0.0,
+        ];
+        let returns: Vec<f64> = (0..mkt.len()).map(|t| 0.9 * mkt[t] + noise[t]).collect();
+        // The factor histories are longer than the instrument returns
+        let mut mkt_history = vec![0.05, -0.05];
+        mkt_history.extend(mkt);
+        let bars = bars_from_closes(&closes_from_returns(&returns));
+        let factors = create_factors(&[("MKT", &mkt_history), ("HML", &hml)]);
+        let result = Bar::factor_decompose(&bars, &factors).unwrap();
+
+        assert!(approx_eq!(f64, result.betas["MKT"], 0.9, epsilon = 0.05));
+        assert!(result.r_squared > 0.9 && result.r_squared < 1.0);
+        assert!(result.t_stats["MKT"] > 10.0);
+        assert!(result.t_stats["MKT"] > result.t_stats["HML"].abs());
+    }
+
+    #[rstest]
+    fn test_factor_decompose_invalid_input() {
+        let bars = bars_from_closes(&[1.0, 1.1, 0.99, 1.089, 1.2]);
+        let mkt = [0.01, -0.02, 0.015, 0.005];
+
+        assert_eq!(Bar::factor_decompose(&bars, &HashMap::new()), None);
+        // Too few returns for the number of regressors
+        assert_eq!(
+            Bar::factor_decompose(&bars, &create_factors(&[("MKT", &mkt[..2])])),
+            None
+        );
+        // Collinear factors
+        assert_eq!(
+            Bar::factor_decompose(&bars, &create_factors(&[("MKT", &mkt), ("SMB", &mkt)])),
+            None
+        );
+        // Constant factor is collinear with the intercept
+        assert_eq!(
+            Bar::factor_decompose(&bars, &create_factors(&[("MKT", &[0.01; 4])])),
+            None
+        );
+    }
+
+    fn create_scenarios(series: &[(&str, &[f64])]) -> HashMap<String, Vec<Bar>> {
+        series
+            .iter()
+            .map(|(name, closes)| ((*name).to_string(), bars_from_closes(closes)))
+            .collect()
+    }
+
+    #[rstest]
+    #[case(100_000.0, -22_500.0, 7_500.0)]
+    #[case(-100_000.0, 22_500.0, -7_500.0)]
+    fn test_stress_test(
+        #[case] signed_qty: f64,
+        #[case] expected_2008: f64,
+        #[case] expected_2020: f64,
+    ) {
+        let position = position_audusd(signed_qty, 0.75);
+        let scenarios = create_scenarios(&[
+            ("2008", &[1.0, 0.9, 0.8, 0.7]),
+            ("2020", &[1.0, 0.9, 1.1]),
+            ("EMPTY", &[]),
+        ]);
+        let result = Bar::stress_test(&position, &scenarios);
+
+        assert_eq!(result.len(), 2);
+        assert!(approx_eq!(
+            f64,
+            result["2008"],
+            expected_2008,
+            epsilon = 1e-6
+        ));
+        assert!(approx_eq!(
+            f64,
+            result["2020"],
+            expected_2020,
+            epsilon = 1e-6
+        ));
+    }
+
+    #[rstest]
+    fn test_stress_test_inverse() {
+        let mut position = position_audusd(100_000.0, 0.75);
+        position.is_inverse = true;
+        let scenarios = create_scenarios(&[("2020", &[1.0, 0.9, 1.1])]);
+        let result = Bar::stress_test(&position, &scenarios);
+
+        let expected = 100_000.0 * (1.0 / 0.75 - 1.0 / 0.825);
+        assert!(approx_eq!(f64, result["2020"], expected, epsilon = 1e-6));
+        assert!(result["2020"] > 0.0);
+    }
+}
@@ -0,0 +1,231 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Protobuf serialization of [`Bar`] and [`BarType`].
+
+use std::str::FromStr;
+
+use super::bar::{Bar, BarType};
+pub use crate::generated::{Bar as BarProto, BarResponse, BarType as BarTypeProto};
+use crate::types::{fixed::FIXED_PRECISION, price::Price, quantity::Quantity};
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ProtoError {
+    #[error("Missing required field '{0}'")]
+    MissingField(&'static str),
+    #[error("Invalid bar type '{0}'")]
+    InvalidBarType(String),
+    #[error("Invalid precision for field '{field}', was {precision}")]
+    InvalidPrecision { field: &'static str, precision: u32 },
+}
+
+/// Returns the precision `value` of `field` if it is within the fixed precision.
+fn precision(field: &'static str, value: Option<u32>) -> Result<u8, ProtoError> {
+    let precision = value.ok_or(ProtoError::MissingField(field))?;
+    u8::try_from(precision)
+        .ok()
+        .filter(|p| *p <= FIXED_PRECISION)
+        .ok_or(ProtoError::InvalidPrecision { field, precision })
+}
+
+impl BarType {
+    /// Returns the Protobuf message for the bar type.
+    #[must_use]
+    pub fn to_proto(&self) -> BarTypeProto {
+        BarTypeProto {
+            value: Some(self.to_string()),
+        }
+    }
+
+    /// Returns the bar type for the given Protobuf message.
+    ///
+    /// # Errors
+    ///
+    /// If the value is missing or is not a valid bar type.
+    pub fn from_proto(proto: BarTypeProto) -> Result<Self, ProtoError> {
+        let value = proto.value.ok_or(ProtoError::MissingField("value"))?;
+        BarType::from_str(&value).map_err(|_| ProtoError::InvalidBarType(value))
+    }
+}
+
+impl Bar {
+    /// Returns the Protobuf message for the bar, with prices and volume as raw
+    /// fixed-point integers.
+    #[must_use]
+    pub fn to_proto(&self) -> BarProto {
+        BarProto {
+            bar_type: Some(self.bar_type.to_proto()),
+            open: Some(self.open.raw),
+            high: Some(self.high.raw),
+            low: Some(self.low.raw),
+            close: Some(self.close.raw),
+            price_precision: Some(u32::from(self.open.precision)),
+            volume: Some(self.volume.raw),
+            size_precision: Some(u32::from(self.volume.precision)),
+            ts_event: Some(self.ts_event),
+            ts_init: Some(self.ts_init),
+        }
+    }
+
+    /// Returns the bar for the given Protobuf message.
+    ///
+    /// # Errors
+    ///
+    /// If any field is missing, or the bar type or a precision is invalid.
+    pub fn from_proto(proto: BarProto) -> Result<Self, ProtoError> {
+        let bar_type =
+            BarType::from_proto(proto.bar_type.ok_or(ProtoError::MissingField("bar_type"))?)?;
+        let price_precision = precision("price_precision", proto.price_precision)?;
+        let size_precision = precision("size_precision", proto.size_precision)?;
+        let price = |field: &'static str, raw: Option<i64>| {
+            raw.map(|raw| Price::from_raw(raw, price_precision))
+                .ok_or(ProtoError::MissingField(field))
+        };
+
+        Ok(Self::new(
+            bar_type,
+            price("open", proto.open)?,
+            price("high", proto.high)?,
+            price("low", proto.low)?,
+            price("close", proto.close)?,
+            Quantity::from_raw(
+                proto.volume.ok_or(ProtoError::MissingField("volume"))?,
+                size_precision,
+            ),
+            proto.ts_event.ok_or(ProtoError::MissingField("ts_event"))?,
+            proto.ts_init.ok_or(ProtoError::MissingField("ts_init"))?,
+        ))
+    }
+
+    /// Returns the gRPC streaming response message for the bar.
+    #[must_use]
+    pub fn to_grpc_bar_response(&self) -> BarResponse {
+        BarResponse {
+            bar: Some(self.to_proto()),
+        }
+    }
+
+    /// Returns the bar for the given gRPC streaming response message.
+    ///
+    /// # Errors
+    ///
+    /// If the bar is missing or invalid, see [`Bar::from_proto`].
+    pub fn from_grpc_bar_response(response: BarResponse) -> Result<Self, ProtoError> {
+        Self::from_proto(response.bar.ok_or(ProtoError::MissingField("bar"))?)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::bars_from_closes;
+
+    #[rstest]
+    fn test_to_proto_from_proto_round_trip() {
+        let bar = bars_from_closes(&[1.00001, 1.00005])[1];
+        let proto = bar.to_proto();
+
+        assert_eq!(Bar::from_proto(proto.clone()), Ok(bar));
+        let decoded = BarProto::decode(proto.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded, proto);
+        assert_eq!(Bar::from_proto(decoded), Ok(bar));
+    }
+
+    #[rstest]
+    fn test_grpc_bar_response_round_trip() {
+        let bar = bars_from_closes(&[1.00001, 1.00005])[1];
+        let response = bar.to_grpc_bar_response();
+
+        let decoded = BarResponse::decode(response.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(Bar::from_grpc_bar_response(decoded), Ok(bar));
+        assert_eq!(
+            Bar::from_grpc_bar_response(BarResponse { bar: None }),
+            Err(ProtoError::MissingField("bar"))
+        );
+    }
+
+    #[rstest]
+    fn test_bar_type_round_trip() {
+        let bar_type = bars_from_closes(&[1.0])[0].bar_type;
+
+        assert_eq!(BarType::from_proto(bar_type.to_proto()), Ok(bar_type));
+    }
+
+    #[rstest]
+    fn test_from_proto_missing_fields() {
+        let proto = bars_from_closes(&[1.0])[0].to_proto();
+
+        // An empty message decodes with every field missing
+        let empty = BarProto::decode(&[][..]).unwrap();
+        assert_eq!(
+            Bar::from_proto(empty),
+            Err(ProtoError::MissingField("bar_type"))
+        );
+        assert_eq!(
+            Bar::from_proto(BarProto {
+                close: None,
+                ..proto.clone()
+            }),
+            Err(ProtoError::MissingField("close"))
+        );
+        assert_eq!(
+            Bar::from_proto(BarProto {
+                ts_init: None,
+                ..proto.clone()
+            }),
+            Err(ProtoError::MissingField("ts_init"))
+        );
+        assert_eq!(
+            Bar::from_proto(BarProto {
+                bar_type: Some(BarTypeProto { value: None }),
+                ..proto
+            }),
+            Err(ProtoError::MissingField("value"))
+        );
+    }
+
+    #[rstest]
+    fn test_from_proto_invalid_fields() {
+        let proto = bars_from_closes(&[1.0])[0].to_proto();
+
+        assert_eq!(
+            Bar::from_proto(BarProto {
+                bar_type: Some(BarTypeProto {
+                    value: Some("AUDUSD.SIM-1-MINUTE".to_string())
+                }),
+                ..proto.clone()
+            }),
+            Err(ProtoError::InvalidBarType(
+                "AUDUSD.SIM-1-MINUTE".to_string()
+            ))
+        );
+        assert_eq!(
+            Bar::from_proto(BarProto {
+                price_precision: Some(10),
+                ..proto
+            }),
+            Err(ProtoError::InvalidPrecision {
+                field: "price_precision",
+                precision: 10
+            })
+        );
+    }
+}
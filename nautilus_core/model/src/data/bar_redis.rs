@@ -0,0 +1,164 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Conversion of [`Bar`]s to and from the field-value pairs of Redis Streams entries.
+
+use std::{collections::HashMap, str::FromStr};
+
+use super::bar::{Bar, BarType};
+use crate::types::{price::Price, quantity::Quantity};
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum RedisDeserializeError {
+    #[error("Missing field '{0}'")]
+    MissingField(&'static str),
+    #[error("Invalid value '{value}' for field '{field}': {reason}")]
+    InvalidField {
+        field: &'static str,
+        value: String,
+        reason: String,
+    },
+}
+
+/// Returns the value of the `field`, parsed with `parse`.
+fn parse_field<T, E: ToString>(
+    fields: &HashMap<String, String>,
+    field: &'static str,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> Result<T, RedisDeserializeError> {
+    let value = fields
+        .get(field)
+        .ok_or(RedisDeserializeError::MissingField(field))?;
+    parse(value).map_err(|e| RedisDeserializeError::InvalidField {
+        field,
+        value: value.clone(),
+        reason: e.to_string(),
+    })
+}
+
+impl Bar {
+    /// Returns the field-value pairs of the bar for a Redis Streams `XADD`
+    /// command, with the prices and volume at their precision and the
+    /// timestamps as UNIX nanoseconds.
+    #[must_use]
+    pub fn to_redis_fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("bar_type", self.bar_type.to_string()),
+            ("open", self.open.to_string()),
+            ("high", self.high.to_string()),
+            ("low", self.low.to_string()),
+            ("close", self.close.to_string()),
+            ("volume", self.volume.to_string()),
+            ("ts_event", self.ts_event.to_string()),
+            ("ts_init", self.ts_init.to_string()),
+        ]
+    }
+
+    /// Returns the bar parsed from the field-value pairs of a Redis Streams
+    /// entry, as written by [`Bar::to_redis_fields`].
+    ///
+    /// Any additional fields are ignored.
+    ///
+    /// # Errors
+    ///
+    /// If any field is missing or cannot be parsed.
+    pub fn from_redis_fields(
+        fields: &HashMap<String, String>,
+    ) -> Result<Self, RedisDeserializeError> {
+        Ok(Self::new(
+            parse_field(fields, "bar_type", BarType::from_str)?,
+            parse_field(fields, "open", Price::from_str)?,
+            parse_field(fields, "high", Price::from_str)?,
+            parse_field(fields, "low", Price::from_str)?,
+            parse_field(fields, "close", Price::from_str)?,
+            parse_field(fields, "volume", Quantity::from_str)?,
+            parse_field(fields, "ts_event", u64::from_str)?,
+            parse_field(fields, "ts_init", u64::from_str)?,
+        ))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::*;
+
+    fn to_map(fields: Vec<(&'static str, String)>) -> HashMap<String, String> {
+        fields
+            .into_iter()
+            .map(|(field, value)| (field.to_string(), value))
+            .collect()
+    }
+
+    #[rstest]
+    fn test_to_redis_fields(bar_audusd_sim_minute_bid: Bar) {
+        let fields = bar_audusd_sim_minute_bid.to_redis_fields();
+
+        assert_eq!(
+            fields,
+            vec![
+                ("bar_type", "AUDUSD.SIM-1-MINUTE-BID-EXTERNAL".to_string()),
+                ("open", "1.00001".to_string()),
+                ("high", "1.00004".to_string()),
+                ("low", "1.00002".to_string()),
+                ("close", "1.00003".to_string()),
+                ("volume", "100000".to_string()),
+                ("ts_event", "0".to_string()),
+                ("ts_init", "1".to_string()),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_redis_fields_round_trip(bar_audusd_sim_minute_bid: Bar) {
+        let mut fields = to_map(bar_audusd_sim_minute_bid.to_redis_fields());
+        fields.insert("extra".to_string(), "ignored".to_string());
+
+        assert_eq!(
+            Bar::from_redis_fields(&fields),
+            Ok(bar_audusd_sim_minute_bid)
+        );
+    }
+
+    #[rstest]
+    fn test_from_redis_fields_missing_field(bar_audusd_sim_minute_bid: Bar) {
+        let mut fields = to_map(bar_audusd_sim_minute_bid.to_redis_fields());
+        fields.remove("volume");
+
+        assert_eq!(
+            Bar::from_redis_fields(&fields),
+            Err(RedisDeserializeError::MissingField("volume"))
+        );
+    }
+
+    #[rstest]
+    fn test_from_redis_fields_invalid_field(bar_audusd_sim_minute_bid: Bar) {
+        let mut fields = to_map(bar_audusd_sim_minute_bid.to_redis_fields());
+        fields.insert("ts_event".to_string(), "-1".to_string());
+
+        assert!(matches!(
+            Bar::from_redis_fields(&fields),
+            Err(RedisDeserializeError::InvalidField {
+                field: "ts_event",
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,206 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Running statistics over a stream of bars, as an [`Iterator`] adapter.
+
+use std::collections::VecDeque;
+
+use super::bar::Bar;
+use crate::types::{
+    fixed::{FIXED_PRECISION, FIXED_SCALAR},
+    price::Price,
+};
+
+/// Represents a bar together with the rolling statistics of the bar closes
+/// over the trailing window ending at the bar.
+///
+/// The statistics are `None` until a full window of bars has been seen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BarStatsWindow {
+    /// The current bar.
+    pub bar: Bar,
+    /// The simple moving average of the closes, rounded to the close precision.
+    pub sma: Option<Price>,
+    /// The population standard deviation of the closes.
+    pub std_dev: Option<f64>,
+    /// The highest close.
+    pub max: Option<Price>,
+    /// The lowest close.
+    pub min: Option<Price>,
+}
+
+/// Provides an iterator adapter yielding each bar of the inner iterator with
+/// the rolling statistics of the closes over the trailing `window` bars.
+///
+/// The moving average, maximum and minimum are computed in fixed-point from
+/// the raw close prices, only the standard deviation is computed as `f64`.
+#[derive(Clone, Debug)]
+pub struct RunningStats<I: Iterator<Item = Bar>> {
+    inner: I,
+    window: usize,
+    closes: VecDeque<Price>,
+    raw_sum: i128,
+}
+
+impl<I: Iterator<Item = Bar>> RunningStats<I> {
+    /// Creates a new [`RunningStats`] adapter over `inner` with the given
+    /// `window` size.
+    ///
+    /// # Panics
+    ///
+    /// If `window` is zero.
+    pub fn new(inner: I, window: usize) -> Self {
+        assert!(window > 0, "Invalid window size, was zero");
+        Self {
+            inner,
+            window,
+            closes: VecDeque::with_capacity(window),
+            raw_sum: 0,
+        }
+    }
+
+    fn sma(&self, precision: u8) -> Price {
+        let mean = self.raw_sum / self.window as i128;
+        // Round half away from zero to the precision of the closes
+        let unit = 10_i128.pow(u32::from(FIXED_PRECISION.saturating_sub(precision)));
+        let rounded = (mean + mean.signum() * unit / 2) / unit * unit;
+        Price::from_raw(rounded as i64, precision)
+    }
+
+    fn std_dev(&self) -> f64 {
+        let n = self.window as f64;
+        let mean = self.raw_sum as f64 / n;
+        let sum_sq: f64 = self
+            .closes
+            .iter()
+            .map(|close| (close.raw as f64 - mean).powi(2))
+            .sum();
+        (sum_sq / n).sqrt() / FIXED_SCALAR
+    }
+}
+
+impl<I: Iterator<Item = Bar>> Iterator for RunningStats<I> {
+    type Item = BarStatsWindow;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bar = self.inner.next()?;
+        if self.closes.len() == self.window {
+            // Unwrapping is safe as the window is non-empty
+            let old = self.closes.pop_front().unwrap();
+            self.raw_sum -= i128::from(old.raw);
+        }
+        self.closes.push_back(bar.close);
+        self.raw_sum += i128::from(bar.close.raw);
+
+        if self.closes.len() < self.window {
+            return Some(BarStatsWindow {
+                bar,
+                sma: None,
+                std_dev: None,
+                max: None,
+                min: None,
+            });
+        }
+        Some(BarStatsWindow {
+            bar,
+            sma: Some(self.sma(bar.close.precision)),
+            std_dev: Some(self.std_dev()),
+            max: self.closes.iter().max().copied(),
+            min: self.closes.iter().min().copied(),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Provides bar iterator adapters in the style of the standard [`Iterator`] methods.
+pub trait BarExt: Iterator<Item = Bar> + Sized {
+    /// Returns an adapter yielding each bar with the rolling statistics of the
+    /// closes over the trailing `window` bars, see [`RunningStats`].
+    ///
+    /// # Panics
+    ///
+    /// If `window` is zero.
+    fn running_stats(self, window: usize) -> RunningStats<Self> {
+        RunningStats::new(self, window)
+    }
+}
+
+impl<I: Iterator<Item = Bar>> BarExt for I {}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::bars_from_closes;
+
+    #[rstest]
+    fn test_running_stats() {
+        let bars = bars_from_closes(&[1.0, 3.0, 2.0, 6.0, 4.0]);
+        let windows: Vec<BarStatsWindow> = bars.iter().cloned().running_stats(3).collect();
+
+        assert_eq!(windows.len(), 5);
+        for (window, bar) in windows.iter().zip(&bars) {
+            assert_eq!(window.bar, *bar);
+        }
+        for window in &windows[..2] {
+            assert_eq!(window.sma, None);
+            assert_eq!(window.std_dev, None);
+            assert_eq!(window.max, None);
+            assert_eq!(window.min, None);
+        }
+
+        // Windows [1, 3, 2], [3, 2, 6] and [2, 6, 4]
+        let expected = [
+            ("2.00000", 2.0_f64 / 3.0, "3.00000", "1.00000"),
+            ("3.66667", 26.0_f64 / 9.0, "6.00000", "2.00000"),
+            ("4.00000", 8.0_f64 / 3.0, "6.00000", "2.00000"),
+        ];
+        for (window, (sma, variance, max, min)) in windows[2..].iter().zip(expected) {
+            assert_eq!(window.sma, Some(Price::from(sma)));
+            assert!(approx_eq!(
+                f64,
+                window.std_dev.unwrap(),
+                variance.sqrt(),
+                epsilon = 1e-9
+            ));
+            assert_eq!(window.max, Some(Price::from(max)));
+            assert_eq!(window.min, Some(Price::from(min)));
+        }
+    }
+
+    #[rstest]
+    fn test_running_stats_window_of_one() {
+        let bars = bars_from_closes(&[1.5, 2.5]);
+        let windows: Vec<BarStatsWindow> = bars.into_iter().running_stats(1).collect();
+
+        assert_eq!(windows[1].sma, Some(Price::from("2.50000")));
+        assert_eq!(windows[1].std_dev, Some(0.0));
+        assert_eq!(windows[1].max, windows[1].min);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "Invalid window size")]
+    fn test_running_stats_zero_window() {
+        let _ = bars_from_closes(&[1.0]).into_iter().running_stats(0);
+    }
+}
@@ -0,0 +1,703 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Trading signals computed from one or more series of [`Bar`]s.
+
+use std::collections::HashMap;
+
+use super::bar::Bar;
+use crate::identifiers::instrument_id::InstrumentId;
+
+/// Returns the simple return of the close over the last `period` bars, or
+/// `None` if there are not enough bars.
+fn trailing_return(bars: &[Bar], period: usize) -> Option<f64> {
+    let last = bars.last()?;
+    let start = bars.get(bars.len().checked_sub(period + 1)?)?;
+    Some(last.close.as_f64() / start.close.as_f64() - 1.0)
+}
+
+/// Returns the fractional ranks [0, 1] of the given scores, where the highest
+/// score ranks 1.0 and tied scores share their average rank.
+fn fractional_ranks(scores: &[(InstrumentId, f64)]) -> HashMap<InstrumentId, f64> {
+    if scores.len() == 1 {
+        return HashMap::from([(scores[0].0, 1.0)]);
+    }
+    let mut sorted = scores.to_vec();
+    sorted.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let denominator = (sorted.len() - 1) as f64;
+
+    let mut ranks = HashMap::with_capacity(sorted.len());
+    let mut i = 0;
+    while i < sorted.len() {
+        let mut j = i;
+        while j + 1 < sorted.len() && sorted[j + 1].1 == sorted[i].1 {
+            j += 1;
+        }
+        let rank = (i + j) as f64 / 2.0 / denominator;
+        for (instrument_id, _) in &sorted[i..=j] {
+            ranks.insert(*instrument_id, rank);
+        }
+        i = j + 1;
+    }
+    ranks
+}
+
+/// Returns the z-scores of the given values using the population standard
+/// deviation, or zeros if the values have no dispersion.
+fn z_scores(values: &[(InstrumentId, f64)]) -> HashMap<InstrumentId, f64> {
+    let n = values.len() as f64;
+    let mean = values.iter().map(|(_, x)| x).sum::<f64>() / n;
+    let std = (values.iter().map(|(_, x)| (x - mean).powi(2)).sum::<f64>() / n).sqrt();
+    values
+        .iter()
+        .map(|(id, x)| {
+            let z = if std > 0.0 { (x - mean) / std } else { 0.0 };
+            (*id, z)
+        })
+        .collect()
+}
+
+/// Returns the Pearson correlation of the paired values, or `None` if there are
+/// fewer than two pairs or either series has no dispersion.
+fn pearson_correlation(pairs: &[(f64, f64)]) -> Option<f64> {
+    if pairs.len() < 2 {
+        return None;
+    }
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let (cov, var_x, var_y) = pairs
+        .iter()
+        .fold((0.0, 0.0, 0.0), |(cov, var_x, var_y), (x, y)| {
+            (
+                cov + (x - mean_x) * (y - mean_y),
+                var_x + (x - mean_x).powi(2),
+                var_y + (y - mean_y).powi(2),
+            )
+        });
+    if var_x == 0.0 || var_y == 0.0 {
+        return None;
+    }
+    Some(cov / (var_x * var_y).sqrt())
+}
+
+/// Returns the rank of each value in ascending order, starting from 1.0, with
+/// tied values assigned the average of their ranks.
+fn value_ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end + 1) as f64 / 2.0;
+        for i in &order[start..end] {
+            ranks[*i] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Returns the Spearman rank correlation of the `pairs`, or `None` if there are
+/// fewer than two pairs or either series has no dispersion.
+fn spearman_correlation(pairs: &[(f64, f64)]) -> Option<f64> {
+    let (xs, ys): (Vec<f64>, Vec<f64>) = pairs.iter().copied().unzip();
+    let ranked: Vec<(f64, f64)> = value_ranks(&xs).into_iter().zip(value_ranks(&ys)).collect();
+    pearson_correlation(&ranked)
+}
+
+/// Returns the pairs of each signal with the realized close to close return over
+/// the following `holding_period` bars, for the signals at `range` indices.
+fn signal_return_pairs(
+    signals: &[f64],
+    bars: &[Bar],
+    holding_period: usize,
+    range: std::ops::Range<usize>,
+) -> Vec<(f64, f64)> {
+    range
+        .filter(|i| signals[*i].is_finite())
+        .map(|i| {
+            let realized = bars[i + holding_period].close.as_f64() / bars[i].close.as_f64() - 1.0;
+            (signals[i], realized)
+        })
+        .collect()
+}
+
+impl Bar {
+    /// Returns the relative strength rank of each instrument by its close to
+    /// close return over the last `period` bars.
+    ///
+    /// Scores are in the range [0, 1] with the strongest instrument scoring 1.0.
+    /// Instruments with fewer than `period + 1` bars are excluded.
+    #[must_use]
+    pub fn relative_strength_rank(
+        bars_map: &HashMap<InstrumentId, Vec<Bar>>,
+        period: usize,
+    ) -> HashMap<InstrumentId, f64> {
+        let returns: Vec<(InstrumentId, f64)> = bars_map
+            .iter()
+            .filter_map(|(id, bars)| trailing_return(bars, period).map(|r| (*id, r)))
+            .collect();
+        if returns.is_empty() {
+            return HashMap::new();
+        }
+        fractional_ranks(&returns)
+    }
+
+    /// Returns the Jegadeesh-Titman cross-sectional momentum signal of each
+    /// instrument, z-scored across instruments.
+    ///
+    /// The formation return runs from `lookback` bars ago up to `holding_period`
+    /// bars ago, skipping the most recent bars to avoid short-term reversal, so
+    /// monthly bars with `lookback` 12 and `holding_period` 1 give the classic
+    /// 12-1 signal. Instruments with `lookback` or fewer bars are excluded, and
+    /// an empty map is returned if `holding_period` is not less than `lookback`.
+    #[must_use]
+    pub fn cross_sectional_momentum(
+        bars_map: &HashMap<InstrumentId, Vec<Bar>>,
+        lookback: usize,
+        holding_period: usize,
+    ) -> HashMap<InstrumentId, f64> {
+        if holding_period >= lookback {
+            return HashMap::new();
+        }
+        let returns: Vec<(InstrumentId, f64)> = bars_map
+            .iter()
+            .filter(|(_, bars)| bars.len() > lookback)
+            .filter_map(|(id, bars)| {
+                let formation = &bars[..bars.len() - holding_period];
+                trailing_return(formation, lookback - holding_period).map(|r| (*id, r))
+            })
+            .collect();
+        if returns.is_empty() {
+            return HashMap::new();
+        }
+        z_scores(&returns)
+    }
+
+    /// Returns the Moskowitz-Ooi-Pedersen time series momentum (TSMOM) position
+    /// for each bar, sign(r) * `target_vol` / σ.
+    ///
+    /// The return r is the close to close return over the last `lookback` bars,
+    /// and σ is the sample standard deviation of the last `vol_lookback` log
+    /// returns, with `target_vol` expressed in the same per-bar units. The
+    /// output is aligned with `bars`, with `None` until both windows are full
+    /// or when the volatility is zero.
+    #[must_use]
+    pub fn time_series_momentum_signal(
+        bars: &[Bar],
+        lookback: usize,
+        vol_lookback: usize,
+        target_vol: f64,
+    ) -> Vec<Option<f64>> {
+        let closes: Vec<f64> = bars.iter().map(|bar| bar.close.as_f64()).collect();
+        let log_returns: Vec<f64> = closes.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+
+        (0..closes.len())
+            .map(|i| {
+                if lookback == 0 || vol_lookback < 2 || i < lookback || i < vol_lookback {
+                    return None;
+                }
+                let past_return = closes[i] / closes[i - lookback] - 1.0;
+                let window = &log_returns[i - vol_lookback..i];
+                let mean = window.iter().sum::<f64>() / vol_lookback as f64;
+                let variance = window.iter().map(|r| (r - mean).powi(2)).sum::<f64>()
+                    / (vol_lookback - 1) as f64;
+                let vol = variance.sqrt();
+                if vol == 0.0 {
+                    return None;
+                }
+                let sign = if past_return > 0.0 {
+                    1.0
+                } else if past_return < 0.0 {
+                    -1.0
+                } else {
+                    0.0
+                };
+                Some(sign * target_vol / vol)
+            })
+            .collect()
+    }
+
+    /// Returns the annualized carry (roll yield) implied by the futures basis,
+    /// (front - back) / back / (`days_to_expiry` / 365), using the bar closes.
+    ///
+    /// Returns `None` if `days_to_expiry` is not positive or the back month
+    /// close is not positive.
+    #[must_use]
+    pub fn carry_return(front_month: &Bar, back_month: &Bar, days_to_expiry: f64) -> Option<f64> {
+        let front = front_month.close.as_f64();
+        let back = back_month.close.as_f64();
+        if days_to_expiry <= 0.0 || back <= 0.0 {
+            return None;
+        }
+        Some((front - back) / back / (days_to_expiry / 365.0))
+    }
+
+    /// Returns the rolling z-score of the pairs spread y - β * x, using the bar
+    /// closes and the hedge ratio `beta` estimated for each bar.
+    ///
+    /// Each z-score is taken against the mean and sample standard deviation of
+    /// the last `z_period` spreads. The series are aligned by index and the
+    /// output has the length of the shortest input, with `None` until the
+    /// window is full, or where the window holds a non-finite spread (such as
+    /// from a warming up `beta`) or has zero dispersion.
+    #[must_use]
+    pub fn spread_zscore(x: &[Bar], y: &[Bar], beta: &[f64], z_period: usize) -> Vec<Option<f64>> {
+        let spreads: Vec<f64> = x
+            .iter()
+            .zip(y)
+            .zip(beta)
+            .map(|((x, y), beta)| y.close.as_f64() - beta * x.close.as_f64())
+            .collect();
+
+        (0..spreads.len())
+            .map(|i| {
+                if z_period < 2 || i + 1 < z_period {
+                    return None;
+                }
+                let window = &spreads[i + 1 - z_period..=i];
+                if !window.iter().all(|s| s.is_finite()) {
+                    return None;
+                }
+                let mean = window.iter().sum::<f64>() / z_period as f64;
+                let variance =
+                    window.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (z_period - 1) as f64;
+                let std = variance.sqrt();
+                if std == 0.0 {
+                    return None;
+                }
+                Some((spreads[i] - mean) / std)
+            })
+            .collect()
+    }
+
+    /// Returns the information coefficient (IC) of the `signals`, the Pearson
+    /// correlation of each signal with the realized close to close return over
+    /// the following `holding_period` bars.
+    ///
+    /// Signals are aligned with `bars` by index, and signals without a realized
+    /// return or which are not finite are excluded. Returns `None` if
+    /// `holding_period` is zero, there are fewer than two pairs, or either
+    /// series has no dispersion.
+    #[must_use]
+    pub fn information_coefficient(
+        signals: &[f64],
+        bars: &[Bar],
+        holding_period: usize,
+    ) -> Option<f64> {
+        if holding_period == 0 {
+            return None;
+        }
+        let count = signals.len().min(bars.len().saturating_sub(holding_period));
+        pearson_correlation(&signal_return_pairs(
+            signals,
+            bars,
+            holding_period,
+            0..count,
+        ))
+    }
+
+    /// Returns the rank information coefficient (Rank IC) of the `signals`, the
+    /// Spearman rank correlation of each signal with the realized close to close
+    /// return over the following `holding_period` bars.
+    ///
+    /// Less sensitive to outlier signal values than
+    /// [`Bar::information_coefficient`], so preferred for fat-tailed return
+    /// distributions. Pairs are selected and `None` returned as for
+    /// [`Bar::information_coefficient`].
+    #[must_use]
+    pub fn rank_information_coefficient(
+        signals: &[f64],
+        bars: &[Bar],
+        holding_period: usize,
+    ) -> Option<f64> {
+        if holding_period == 0 {
+            return None;
+        }
+        let count = signals.len().min(bars.len().saturating_sub(holding_period));
+        spearman_correlation(&signal_return_pairs(
+            signals,
+            bars,
+            holding_period,
+            0..count,
+        ))
+    }
+
+    /// Returns the rolling information coefficient of the `signals` over the
+    /// last `ic_lookback` signals whose returns have been realized, for
+    /// tracking signal decay.
+    ///
+    /// The output is aligned with `bars`, up to the number of `signals`. The IC
+    /// at bar t uses only returns realized by t, so pairs the signals from
+    /// t - `holding_period` - `ic_lookback` + 1 to t - `holding_period`. Values
+    /// are `None` until the window is full, or as for
+    /// [`Bar::information_coefficient`].
+    #[must_use]
+    pub fn rolling_ic(
+        signals: &[f64],
+        bars: &[Bar],
+        holding_period: usize,
+        ic_lookback: usize,
+    ) -> Vec<Option<f64>> {
+        (0..signals.len().min(bars.len()))
+            .map(|t| {
+                if holding_period == 0 || ic_lookback < 2 || t < holding_period + ic_lookback - 1 {
+                    return None;
+                }
+                let end = t - holding_period + 1;
+                pearson_correlation(&signal_return_pairs(
+                    signals,
+                    bars,
+                    holding_period,
+                    end - ic_lookback..end,
+                ))
+            })
+            .collect()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::bars_from_closes;
+
+    fn create_bars_map(series: &[(&str, &[f64])]) -> HashMap<InstrumentId, Vec<Bar>> {
+        series
+            .iter()
+            .map(|(id, closes)| (InstrumentId::from(*id), bars_from_closes(closes)))
+            .collect()
+    }
+
+    #[rstest]
+    fn test_relative_strength_rank() {
+        let bars_map = create_bars_map(&[
+            ("AAA.SIM", &[1.0, 1.0, 1.1]),
+            ("BBB.SIM", &[1.0, 1.0, 0.9]),
+            ("CCC.SIM", &[1.0, 2.0, 1.3]),
+        ]);
+        let ranks = Bar::relative_strength_rank(&bars_map, 2);
+
+        assert_eq!(ranks[&InstrumentId::from("CCC.SIM")], 1.0);
+        assert_eq!(ranks[&InstrumentId::from("AAA.SIM")], 0.5);
+        assert_eq!(ranks[&InstrumentId::from("BBB.SIM")], 0.0);
+    }
+
+    #[rstest]
+    fn test_relative_strength_rank_ties_and_exclusions() {
+        let bars_map = create_bars_map(&[
+            ("AAA.SIM", &[1.0, 1.1]),
+            ("BBB.SIM", &[2.0, 2.2]),
+            ("CCC.SIM", &[1.0, 0.9]),
+            ("DDD.SIM", &[1.0]),
+        ]);
+        let ranks = Bar::relative_strength_rank(&bars_map, 1);
+
+        assert_eq!(ranks.len(), 3);
+        assert_eq!(ranks[&InstrumentId::from("AAA.SIM")], 0.75);
+        assert_eq!(ranks[&InstrumentId::from("BBB.SIM")], 0.75);
+        assert_eq!(ranks[&InstrumentId::from("CCC.SIM")], 0.0);
+    }
+
+    #[rstest]
+    fn test_relative_strength_rank_single_instrument() {
+        let bars_map = create_bars_map(&[("AAA.SIM", &[1.0, 1.1])]);
+        let ranks = Bar::relative_strength_rank(&bars_map, 1);
+
+        assert_eq!(ranks[&InstrumentId::from("AAA.SIM")], 1.0);
+    }
+
+    #[rstest]
+    fn test_cross_sectional_momentum() {
+        let bars_map = create_bars_map(&[
+            ("AAA.SIM", &[1.0, 1.2, 1.0]),
+            ("BBB.SIM", &[1.0, 1.0, 2.0]),
+            ("CCC.SIM", &[1.0, 0.8, 1.0]),
+        ]);
+        let signals = Bar::cross_sectional_momentum(&bars_map, 2, 1);
+
+        // Formation returns skip the last bar: 0.2, 0.0, -0.2
+        let z = 1.5_f64.sqrt();
+        assert!(approx_eq!(
+            f64,
+            signals[&InstrumentId::from("AAA.SIM")],
+            z,
+            epsilon = 1e-9
+        ));
+        assert!(approx_eq!(
+            f64,
+            signals[&InstrumentId::from("BBB.SIM")],
+            0.0,
+            epsilon = 1e-9
+        ));
+        assert!(approx_eq!(
+            f64,
+            signals[&InstrumentId::from("CCC.SIM")],
+            -z,
+            epsilon = 1e-9
+        ));
+    }
+
+    #[rstest]
+    fn test_cross_sectional_momentum_excludes_short_series() {
+        let bars_map = create_bars_map(&[("AAA.SIM", &[1.0, 1.1, 1.2]), ("BBB.SIM", &[1.0, 1.1])]);
+        let signals = Bar::cross_sectional_momentum(&bars_map, 2, 0);
+
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[&InstrumentId::from("AAA.SIM")], 0.0);
+    }
+
+    #[rstest]
+    fn test_cross_sectional_momentum_invalid_holding_period() {
+        let bars_map = create_bars_map(&[("AAA.SIM", &[1.0, 1.1, 1.2])]);
+
+        assert!(Bar::cross_sectional_momentum(&bars_map, 2, 2).is_empty());
+    }
+
+    #[rstest]
+    fn test_time_series_momentum_signal() {
+        let bars = bars_from_closes(&[1.0, 2.0, 1.0, 4.0, 8.0]);
+        let signals = Bar::time_series_momentum_signal(&bars, 2, 2, 0.1);
+        let ln2 = 2.0_f64.ln();
+
+        assert_eq!(signals.len(), 5);
+        assert_eq!(signals[..2], [None, None]);
+        // Flat return over 2 bars
+        assert_eq!(signals[2], Some(0.0));
+        // Log returns (-ln2, 2ln2) have sample std √4.5 * ln2
+        assert!(approx_eq!(
+            f64,
+            signals[3].unwrap(),
+            0.1 / (4.5_f64.sqrt() * ln2),
+            epsilon = 1e-9
+        ));
+        // Log returns (2ln2, ln2) have sample std ln2 / √2
+        assert!(approx_eq!(
+            f64,
+            signals[4].unwrap(),
+            0.1 * 2.0_f64.sqrt() / ln2,
+            epsilon = 1e-9
+        ));
+    }
+
+    #[rstest]
+    fn test_time_series_momentum_signal_zero_volatility() {
+        let bars = bars_from_closes(&[1.0, 2.0, 4.0, 8.0]);
+        let signals = Bar::time_series_momentum_signal(&bars, 1, 2, 0.1);
+
+        assert!(signals.iter().all(Option::is_none));
+    }
+
+    #[rstest]
+    fn test_time_series_momentum_signal_short_position() {
+        let bars = bars_from_closes(&[4.0, 2.0, 4.0, 1.0]);
+        let signals = Bar::time_series_momentum_signal(&bars, 3, 3, 0.1);
+
+        assert!(signals[3].unwrap() < 0.0);
+    }
+
+    #[rstest]
+    fn test_time_series_momentum_signal_invalid_windows() {
+        let bars = bars_from_closes(&[1.0, 2.0, 3.0]);
+
+        assert!(Bar::time_series_momentum_signal(&bars, 0, 2, 0.1)
+            .iter()
+            .all(Option::is_none));
+        assert!(Bar::time_series_momentum_signal(&bars, 1, 1, 0.1)
+            .iter()
+            .all(Option::is_none));
+    }
+
+    #[rstest]
+    fn test_carry_return() {
+        let bars = bars_from_closes(&[102.0, 100.0]);
+
+        // Backwardation of 2% over a quarter annualizes to ~8.1%
+        let carry = Bar::carry_return(&bars[0], &bars[1], 90.0).unwrap();
+        assert!(approx_eq!(f64, carry, 0.02 * 365.0 / 90.0, epsilon = 1e-9));
+
+        // Contango gives a negative carry
+        assert!(Bar::carry_return(&bars[1], &bars[0], 90.0).unwrap() < 0.0);
+    }
+
+    #[rstest]
+    fn test_carry_return_invalid_days_to_expiry() {
+        let bars = bars_from_closes(&[102.0, 100.0]);
+
+        assert_eq!(Bar::carry_return(&bars[0], &bars[1], 0.0), None);
+        assert_eq!(Bar::carry_return(&bars[0], &bars[1], -1.0), None);
+    }
+
+    #[rstest]
+    fn test_spread_zscore() {
+        let x = bars_from_closes(&[2.0, 2.0, 2.0, 2.0, 2.0]);
+        let y = bars_from_closes(&[1.0, 2.0, 3.0, 4.0, 2.0]);
+        let beta = [0.5; 5];
+        let result = Bar::spread_zscore(&x, &y, &beta, 3);
+
+        // Spreads: 0.0, 1.0, 2.0, 3.0, 1.0
+        assert_eq!(result.len(), 5);
+        assert_eq!(result[..2], [None, None]);
+        assert!(approx_eq!(f64, result[2].unwrap(), 1.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, result[3].unwrap(), 1.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, result[4].unwrap(), -1.0, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_spread_zscore_warming_up_beta() {
+        let x = bars_from_closes(&[1.0, 1.0, 1.0, 1.0]);
+        let y = bars_from_closes(&[1.0, 2.0, 3.0, 5.0]);
+        let beta = [f64::NAN, 1.0, 1.0, 1.0];
+        let result = Bar::spread_zscore(&x, &y, &beta, 2);
+
+        assert_eq!(result[..2], [None, None]);
+        assert!(result[2].is_some());
+        assert!(result[3].is_some());
+    }
+
+    #[rstest]
+    fn test_spread_zscore_degenerate_input() {
+        let x = bars_from_closes(&[1.0, 1.0, 1.0]);
+        let y = bars_from_closes(&[2.0, 2.0, 2.0]);
+
+        assert_eq!(Bar::spread_zscore(&x, &y, &[1.0; 3], 2), vec![None; 3]);
+        assert_eq!(Bar::spread_zscore(&x, &y, &[1.0; 3], 1), vec![None; 3]);
+        assert_eq!(Bar::spread_zscore(&x, &y, &[1.0; 2], 2).len(), 2);
+    }
+
+    #[rstest]
+    fn test_information_coefficient() {
+        // Forward returns: 0.1, -0.1, 0.2, -0.25, 0.1
+        let bars = bars_from_closes(&[1.0, 1.1, 0.99, 1.188, 0.891, 0.9801]);
+
+        let perfect = [0.1, -0.1, 0.2, -0.25, 0.1];
+        let inverse = [-1.0, 1.0, -2.0, 2.5, -1.0];
+        let ic = Bar::information_coefficient(&perfect, &bars, 1).unwrap();
+        let inverse_ic = Bar::information_coefficient(&inverse, &bars, 1).unwrap();
+
+        assert!(approx_eq!(f64, ic, 1.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, inverse_ic, -1.0, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_information_coefficient_holding_period() {
+        let bars = bars_from_closes(&[1.0, 1.0, 1.1, 1.2, 1.1, 1.0, 1.0]);
+        // Two bar forward returns: 0.1, 0.2, 0.0, -1/6, -1/11
+        let signals = [1.0, 2.0, 0.0, -1.0, -0.5, 3.0, 3.0];
+        let ic = Bar::information_coefficient(&signals, &bars, 2).unwrap();
+
+        assert!(ic > 0.9);
+        // The signals without a realized return are ignored
+        let truncated = Bar::information_coefficient(&signals[..5], &bars, 2).unwrap();
+        assert!(approx_eq!(f64, ic, truncated, epsilon = 1e-12));
+    }
+
+    #[rstest]
+    fn test_information_coefficient_degenerate_input() {
+        let bars = bars_from_closes(&[1.0, 1.1, 0.99, 1.188]);
+
+        assert_eq!(
+            Bar::information_coefficient(&[1.0, 2.0, 3.0], &bars, 0),
+            None
+        );
+        assert_eq!(
+            Bar::information_coefficient(&[1.0, 1.0, 1.0], &bars, 1),
+            None
+        );
+        assert_eq!(
+            Bar::information_coefficient(&[1.0, 2.0, 3.0], &bars, 3),
+            None
+        );
+        assert_eq!(
+            Bar::information_coefficient(&[f64::NAN, 1.0, f64::NAN], &bars, 1),
+            None
+        );
+    }
+
+    #[rstest]
+    fn test_rank_information_coefficient() {
+        // Forward returns: 0.1, -0.1, 0.2, -0.25, 0.1
+        let bars = bars_from_closes(&[1.0, 1.1, 0.99, 1.188, 0.891, 0.9801]);
+        // Same ordering as the returns, with an outlier
+        let signals = [1.0, -1.0, 100.0, -2.0, 1.0];
+        let rank_ic = Bar::rank_information_coefficient(&signals, &bars, 1).unwrap();
+        let ic = Bar::information_coefficient(&signals, &bars, 1).unwrap();
+
+        assert!(approx_eq!(f64, rank_ic, 1.0, epsilon = 1e-9));
+        assert!(ic < 0.9);
+    }
+
+    #[rstest]
+    fn test_rank_information_coefficient_inverse() {
+        let bars = bars_from_closes(&[1.0, 1.1, 0.99, 1.188, 0.891, 0.9801]);
+        let signals = [-1.0, 1.0, -100.0, 2.0, -1.0];
+        let rank_ic = Bar::rank_information_coefficient(&signals, &bars, 1).unwrap();
+
+        assert!(approx_eq!(f64, rank_ic, -1.0, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_rank_information_coefficient_degenerate_input() {
+        let bars = bars_from_closes(&[1.0, 1.1, 0.99, 1.188]);
+
+        assert_eq!(
+            Bar::rank_information_coefficient(&[1.0, 2.0, 3.0], &bars, 0),
+            None
+        );
+        assert_eq!(
+            Bar::rank_information_coefficient(&[1.0, 1.0, 1.0], &bars, 1),
+            None
+        );
+        assert_eq!(
+            Bar::rank_information_coefficient(&[1.0, 2.0, 3.0], &bars, 3),
+            None
+        );
+    }
+
+    #[rstest]
+    fn test_rolling_ic() {
+        // Forward returns: 0.1, -0.1, 0.2, -0.25, 0.1
+        let bars = bars_from_closes(&[1.0, 1.1, 0.99, 1.188, 0.891, 0.9801]);
+        let signals = [0.1, -0.1, 0.2, 0.25, -0.1, 0.0];
+        let result = Bar::rolling_ic(&signals, &bars, 1, 3);
+
+        assert_eq!(result.len(), 6);
+        assert_eq!(result[..3], [None, None, None]);
+        // Pairs 0..3 are perfectly predicted, after which the signal decays
+        assert!(approx_eq!(f64, result[3].unwrap(), 1.0, epsilon = 1e-9));
+        assert!(result[4].unwrap() < result[3].unwrap());
+        assert!(result[5].unwrap() < 0.0);
+    }
+
+    #[rstest]
+    fn test_rolling_ic_degenerate_input() {
+        let bars = bars_from_closes(&[1.0, 1.1, 0.99, 1.188]);
+        let signals = [0.1, -0.1, 0.2, 0.0];
+
+        assert_eq!(Bar::rolling_ic(&signals, &bars, 0, 2), vec![None; 4]);
+        assert_eq!(Bar::rolling_ic(&signals, &bars, 1, 1), vec![None; 4]);
+        assert_eq!(Bar::rolling_ic(&signals[..2], &bars, 1, 2).len(), 2);
+    }
+}
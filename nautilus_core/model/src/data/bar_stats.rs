@@ -0,0 +1,1567 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Statistical estimators computed over series of [`Bar`]s.
+
+use std::f64::consts::{E, LN_2, SQRT_2};
+
+use chrono::{Datelike, TimeZone, Timelike, Utc};
+use nautilus_core::{datetime::NANOSECONDS_IN_SECOND, time::UnixNanos};
+
+use super::bar::Bar;
+use crate::types::{price::Price, quantity::Quantity};
+
+/// The scale factor which makes the MAD a consistent estimator of the standard
+/// deviation for normally distributed data.
+pub const MAD_NORMAL_CONSISTENCY: f64 = 0.6745;
+
+/// The number of nanoseconds in a (UTC) day.
+const NANOSECONDS_IN_DAY: u64 = 86_400 * NANOSECONDS_IN_SECOND;
+
+/// The Euler-Mascheroni constant.
+const EULER_MASCHERONI: f64 = 0.577_215_664_901_532_9;
+
+/// Represents summary statistics for the bars of a trading session.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SessionStats {
+    /// The open price of the first bar in the session.
+    pub open: Price,
+    /// The highest price of the session.
+    pub high: Price,
+    /// The lowest price of the session.
+    pub low: Price,
+    /// The close price of the last bar in the session.
+    pub close: Price,
+    /// The total volume of the session.
+    pub volume: Quantity,
+    /// The volume weighted average of the bar typical prices (H + L + C) / 3.
+    pub vwap: f64,
+    /// The number of bars in the session.
+    pub bar_count: usize,
+}
+
+/// Represents a calendar period used to group returns for seasonality analysis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SeasonalPeriod {
+    /// Groups by day of the week (Monday to Sunday, UTC).
+    DayOfWeek,
+    /// Groups by month of the year (January to December, UTC).
+    MonthOfYear,
+    /// Groups by hour of the day (00 to 23, UTC).
+    HourOfDay,
+}
+
+const DAY_NAMES: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+impl SeasonalPeriod {
+    /// Returns the number of buckets for the period.
+    fn bucket_count(self) -> usize {
+        match self {
+            Self::DayOfWeek => 7,
+            Self::MonthOfYear => 12,
+            Self::HourOfDay => 24,
+        }
+    }
+
+    /// Returns the bucket index of the given UNIX timestamp (nanoseconds).
+    fn bucket(self, ts: UnixNanos) -> usize {
+        let datetime = Utc.timestamp_nanos(ts as i64);
+        match self {
+            Self::DayOfWeek => datetime.weekday().num_days_from_monday() as usize,
+            Self::MonthOfYear => datetime.month0() as usize,
+            Self::HourOfDay => datetime.hour() as usize,
+        }
+    }
+
+    /// Returns the display name of the given bucket index.
+    fn bucket_name(self, bucket: usize) -> String {
+        match self {
+            Self::DayOfWeek => DAY_NAMES[bucket].to_string(),
+            Self::MonthOfYear => MONTH_NAMES[bucket].to_string(),
+            Self::HourOfDay => format!("{bucket:02}"),
+        }
+    }
+}
+
+/// Returns the median of the given values, sorting them in place.
+fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        Some((values[mid - 1] + values[mid]) / 2.0)
+    } else {
+        Some(values[mid])
+    }
+}
+
+/// Returns the simple close-to-close returns for the given bars.
+fn simple_returns(bars: &[Bar]) -> Vec<f64> {
+    bars.windows(2)
+        .map(|w| w[1].close.as_f64() / w[0].close.as_f64() - 1.0)
+        .collect()
+}
+
+/// Returns the close-to-close log returns for the given bars.
+fn log_returns(bars: &[Bar]) -> Vec<f64> {
+    bars.windows(2)
+        .map(|w| (w[1].close.as_f64() / w[0].close.as_f64()).ln())
+        .collect()
+}
+
+/// Returns the log returns of the assumed intrabar price path of the bar.
+///
+/// The path visits the low before the high for up bars (O → L → H → C), and
+/// the high before the low otherwise (O → H → L → C).
+fn intrabar_log_returns(bar: &Bar) -> [f64; 3] {
+    let (open, high, low, close) = (
+        bar.open.as_f64(),
+        bar.high.as_f64(),
+        bar.low.as_f64(),
+        bar.close.as_f64(),
+    );
+    if close >= open {
+        [(low / open).ln(), (high / low).ln(), (close / high).ln()]
+    } else {
+        [(high / open).ln(), (low / high).ln(), (close / low).ln()]
+    }
+}
+
+/// Returns the standard normal cumulative distribution at `x`.
+pub(super) fn normal_cdf(x: f64) -> f64 {
+    0.5 * (1.0 + erf(x / SQRT_2))
+}
+
+/// Returns the error function of `x`, by the Abramowitz and Stegun 7.1.26
+/// approximation (maximum error 1.5e-7).
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let value = 1.0 - poly * (-x * x).exp();
+    if x < 0.0 {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Returns the standard normal quantile of the probability `p` in (0, 1), by
+/// Acklam's rational approximation (relative error 1.15e-9).
+fn normal_inv_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Represents the sample statistics of a return series which determine the
+/// distribution of its Sharpe ratio estimate.
+struct SharpeMoments {
+    count: f64,
+    sharpe: f64,
+    skewness: f64,
+    kurtosis: f64,
+}
+
+impl SharpeMoments {
+    /// Returns the statistics of the `returns`, or `None` if there are fewer
+    /// than three or they are constant.
+    fn from_returns(returns: &[f64]) -> Option<Self> {
+        if returns.len() < 3 {
+            return None;
+        }
+        let count = returns.len() as f64;
+        let mean = returns.iter().sum::<f64>() / count;
+        let moment = |k: i32| returns.iter().map(|r| (r - mean).powi(k)).sum::<f64>() / count;
+        let (m2, m3, m4) = (moment(2), moment(3), moment(4));
+        if m2 <= 0.0 {
+            return None;
+        }
+        Some(Self {
+            count,
+            sharpe: mean / (m2 * count / (count - 1.0)).sqrt(),
+            skewness: m3 / m2.powf(1.5),
+            kurtosis: m4 / m2.powi(2),
+        })
+    }
+
+    /// Returns the probability that the true Sharpe ratio exceeds the
+    /// `benchmark_sharpe`, or `None` if the estimate's variance is not positive.
+    fn probabilistic_sharpe(&self, benchmark_sharpe: f64) -> Option<f64> {
+        let variance_term =
+            1.0 - self.skewness * self.sharpe + (self.kurtosis - 1.0) / 4.0 * self.sharpe.powi(2);
+        if variance_term <= 0.0 {
+            return None;
+        }
+        Some(normal_cdf(
+            (self.sharpe - benchmark_sharpe) * (self.count - 1.0).sqrt() / variance_term.sqrt(),
+        ))
+    }
+}
+
+/// Returns the typical price (H + L + C) / 3 of the bar.
+fn typical_price(bar: &Bar) -> f64 {
+    (bar.high.as_f64() + bar.low.as_f64() + bar.close.as_f64()) / 3.0
+}
+
+/// Returns the running typical price VWAP of the bars, parallel to the bars,
+/// resetting at each bar for which `is_new_session` of the previous bar and
+/// the bar is true.
+fn running_session_vwap(bars: &[Bar], is_new_session: impl Fn(&Bar, &Bar) -> bool) -> Vec<Price> {
+    let mut notional = 0.0;
+    let mut volume = 0.0;
+    let mut typical_sum = 0.0;
+    let mut count = 0.0;
+    let mut prev: Option<&Bar> = None;
+    bars.iter()
+        .map(|bar| {
+            if prev.map_or(false, |prev| is_new_session(prev, bar)) {
+                (notional, volume, typical_sum, count) = (0.0, 0.0, 0.0, 0.0);
+            }
+            prev = Some(bar);
+            let typical = typical_price(bar);
+            notional += typical * bar.volume.as_f64();
+            volume += bar.volume.as_f64();
+            typical_sum += typical;
+            count += 1.0;
+            let vwap = if volume > 0.0 {
+                notional / volume
+            } else {
+                typical_sum / count
+            };
+            // Unwrapping is safe as the VWAP is within the range of the bar prices
+            Price::new(vwap, bar.close.precision).unwrap()
+        })
+        .collect()
+}
+
+impl Bar {
+    /// Returns the rolling median absolute deviation (MAD) of close prices over
+    /// `period` bars, where MAD = median(|close - median(close)|).
+    ///
+    /// The output is aligned with `bars`, with `None` for each position which
+    /// does not yet have a full window.
+    #[must_use]
+    pub fn median_absolute_deviation(bars: &[Bar], period: usize) -> Vec<Option<f64>> {
+        let closes: Vec<f64> = bars.iter().map(|bar| bar.close.as_f64()).collect();
+        (0..closes.len())
+            .map(|i| {
+                if period == 0 || i + 1 < period {
+                    return None;
+                }
+                let mut window = closes[i + 1 - period..=i].to_vec();
+                let center = median(&mut window)?;
+                let mut deviations: Vec<f64> = window.iter().map(|x| (x - center).abs()).collect();
+                median(&mut deviations)
+            })
+            .collect()
+    }
+
+    /// Returns the rolling MAD scaled by 1 / 0.6745, a robust estimate of the
+    /// standard deviation of close prices over `period` bars.
+    #[must_use]
+    pub fn normalized_median_absolute_deviation(bars: &[Bar], period: usize) -> Vec<Option<f64>> {
+        Self::median_absolute_deviation(bars, period)
+            .into_iter()
+            .map(|mad| mad.map(|x| x / MAD_NORMAL_CONSISTENCY))
+            .collect()
+    }
+
+    /// Returns the mean of the simple close-to-close returns after discarding
+    /// the lowest and highest `trim_percent` of returns.
+    ///
+    /// Returns `None` if `trim_percent` is not in the range [0, 0.5), or if
+    /// there are fewer than two bars.
+    #[must_use]
+    pub fn truncated_mean_return(bars: &[Bar], trim_percent: f64) -> Option<f64> {
+        if !(0.0..0.5).contains(&trim_percent) {
+            return None;
+        }
+        let mut returns = simple_returns(bars);
+        if returns.is_empty() {
+            return None;
+        }
+        returns.sort_by(|a, b| a.total_cmp(b));
+        let trim = (returns.len() as f64 * trim_percent).floor() as usize;
+        let kept = &returns[trim..returns.len() - trim];
+        Some(kept.iter().sum::<f64>() / kept.len() as f64)
+    }
+
+    /// Returns the semi-deviation of the close-to-close simple returns below
+    /// the `threshold` return, the downside counterpart of the sample standard
+    /// deviation, sqrt(Σ min(r - threshold, 0)² / (n - 1)).
+    ///
+    /// Every return contributes to the count n, as in the denominator of the
+    /// Sortino ratio. Returns `None` if there are fewer than three bars.
+    #[must_use]
+    pub fn semi_deviation(bars: &[Bar], threshold: f64) -> Option<f64> {
+        let returns = simple_returns(bars);
+        if returns.len() < 2 {
+            return None;
+        }
+        let sum: f64 = returns
+            .iter()
+            .map(|r| (r - threshold).min(0.0).powi(2))
+            .sum();
+        Some((sum / (returns.len() - 1) as f64).sqrt())
+    }
+
+    /// Returns the gain to pain ratio of the close-to-close simple returns,
+    /// the sum of the positive returns over the absolute sum of the negative
+    /// returns.
+    ///
+    /// Unlike the Sharpe ratio, variance is not penalized, only actual losses.
+    /// A ratio above 1 is generally considered acceptable and above 2 excellent.
+    /// Returns `None` if there are no losses.
+    #[must_use]
+    pub fn gain_to_pain(bars: &[Bar]) -> Option<f64> {
+        let returns = simple_returns(bars);
+        let gains: f64 = returns.iter().filter(|r| **r > 0.0).sum();
+        let losses: f64 = returns.iter().filter(|r| **r < 0.0).sum();
+        if losses == 0.0 {
+            return None;
+        }
+        Some(gains / losses.abs())
+    }
+
+    /// Returns the Ulcer Index of the closes, sqrt(mean(D²)) for the percentage
+    /// drawdown D of each close from the running maximum close.
+    ///
+    /// Squaring the drawdown of every bar penalizes deep and extended periods
+    /// underwater more than brief sharp drops. Returns NaN if there are no bars.
+    #[must_use]
+    pub fn ulcer_index(bars: &[Bar]) -> f64 {
+        if bars.is_empty() {
+            return f64::NAN;
+        }
+        let mut peak = f64::NEG_INFINITY;
+        let sum: f64 = bars
+            .iter()
+            .map(|bar| {
+                let close = bar.close.as_f64();
+                peak = peak.max(close);
+                (100.0 * (close - peak) / peak).powi(2)
+            })
+            .sum();
+        (sum / bars.len() as f64).sqrt()
+    }
+
+    /// Returns the Martin ratio, the percentage return from the first to the
+    /// last close over the [`Bar::ulcer_index`], a Sharpe ratio analog
+    /// penalizing drawdowns rather than volatility.
+    ///
+    /// Returns `None` if there are fewer than two bars or no drawdowns.
+    #[must_use]
+    pub fn martin_ratio(bars: &[Bar]) -> Option<f64> {
+        let (first, last) = (bars.first()?, bars.last()?);
+        let ulcer_index = Self::ulcer_index(bars);
+        if bars.len() < 2 || ulcer_index == 0.0 {
+            return None;
+        }
+        let total_return = 100.0 * (last.close.as_f64() / first.close.as_f64() - 1.0);
+        Some(total_return / ulcer_index)
+    }
+
+    /// Returns the realized variance, the sum of squared close-to-close log
+    /// returns, or `None` if there are fewer than two bars.
+    #[must_use]
+    pub fn realized_variance(bars: &[Bar]) -> Option<f64> {
+        let returns = log_returns(bars);
+        if returns.is_empty() {
+            return None;
+        }
+        Some(returns.iter().map(|r| r * r).sum())
+    }
+
+    /// Returns the Parkinson volatility estimate from the high-low ranges of
+    /// the bars, σ = sqrt(Σ ln(H/L)² / (4n * ln 2)).
+    ///
+    /// The estimate is per bar period and assumes a driftless price process.
+    /// Returns NaN if there are fewer than two bars.
+    #[must_use]
+    pub fn parkinson_volatility(bars: &[Bar]) -> f64 {
+        if bars.len() < 2 {
+            return f64::NAN;
+        }
+        let sum: f64 = bars
+            .iter()
+            .map(|bar| (bar.high.as_f64() / bar.low.as_f64()).ln().powi(2))
+            .sum();
+        (sum / (4.0 * bars.len() as f64 * LN_2)).sqrt()
+    }
+
+    /// Returns the Garman-Klass volatility estimate from the OHLC prices of
+    /// the bars, σ² = (1/n) * Σ [0.5 * ln(H/L)² - (2 ln 2 - 1) * ln(C/O)²].
+    ///
+    /// The estimate is per bar period and assumes a driftless price process
+    /// without opening jumps. Returns NaN if there are fewer than two bars.
+    #[must_use]
+    pub fn garman_klass_volatility(bars: &[Bar]) -> f64 {
+        if bars.len() < 2 {
+            return f64::NAN;
+        }
+        let sum: f64 = bars
+            .iter()
+            .map(|bar| {
+                let high_low = (bar.high.as_f64() / bar.low.as_f64()).ln();
+                let close_open = (bar.close.as_f64() / bar.open.as_f64()).ln();
+                0.5 * high_low.powi(2) - (2.0 * LN_2 - 1.0) * close_open.powi(2)
+            })
+            .sum();
+        (sum / bars.len() as f64).sqrt()
+    }
+
+    /// Returns the Yang-Zhang volatility estimate, combining the overnight
+    /// (previous close to open) variance, the open-to-close variance and the
+    /// Rogers-Satchell variance, σ² = σo² + k * σc² + (1 - k) * σrs².
+    ///
+    /// The first bar only provides the previous close, so the estimate is over
+    /// the n - 1 following bars, which is robust to both drift and opening
+    /// jumps. Returns NaN if there are fewer than three bars.
+    #[must_use]
+    pub fn yang_zhang_volatility(bars: &[Bar]) -> f64 {
+        if bars.len() < 3 {
+            return f64::NAN;
+        }
+        let n = (bars.len() - 1) as f64;
+        let sample_variance = |values: &[f64]| {
+            let mean = values.iter().sum::<f64>() / n;
+            values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        };
+        let overnight: Vec<f64> = bars
+            .windows(2)
+            .map(|w| (w[1].open.as_f64() / w[0].close.as_f64()).ln())
+            .collect();
+        let open_close: Vec<f64> = bars[1..]
+            .iter()
+            .map(|bar| (bar.close.as_f64() / bar.open.as_f64()).ln())
+            .collect();
+        let rogers_satchell = bars[1..]
+            .iter()
+            .map(|bar| {
+                let (open, high, low, close) = (
+                    bar.open.as_f64(),
+                    bar.high.as_f64(),
+                    bar.low.as_f64(),
+                    bar.close.as_f64(),
+                );
+                (high / close).ln() * (high / open).ln() + (low / close).ln() * (low / open).ln()
+            })
+            .sum::<f64>()
+            / n;
+        let k = 0.34 / (1.34 + (n + 1.0) / (n - 1.0));
+
+        (sample_variance(&overnight)
+            + k * sample_variance(&open_close)
+            + (1.0 - k) * rogers_satchell)
+            .sqrt()
+    }
+
+    /// Returns the probabilistic Sharpe ratio (PSR) of the close-to-close
+    /// simple returns, the probability that the true Sharpe ratio exceeds the
+    /// per-period `benchmark_sharpe` (Bailey & López de Prado), where
+    ///
+    /// PSR = Φ((SR - SR*) * sqrt(T - 1) / sqrt(1 - γ3 * SR + (γ4 - 1) / 4 * SR²),
+    ///
+    /// for the per-period Sharpe ratio SR, skewness γ3 and kurtosis γ4 of the T
+    /// returns, and the benchmark Sharpe ratio SR*.
+    ///
+    /// Returns `None` if there are fewer than four bars or the returns are constant.
+    #[must_use]
+    pub fn probabilistic_sharpe(bars: &[Bar], benchmark_sharpe: f64) -> Option<f64> {
+        let moments = SharpeMoments::from_returns(&simple_returns(bars))?;
+        moments.probabilistic_sharpe(benchmark_sharpe)
+    }
+
+    /// Returns the probability that the Sharpe ratio of the close-to-close
+    /// simple returns is due to chance, given that the best of `trials` strategy
+    /// configurations is reported (Bailey & López de Prado).
+    ///
+    /// This is 1 - DSR, for the deflated Sharpe ratio (DSR), which is the
+    /// probabilistic Sharpe ratio against the expected maximum Sharpe ratio of
+    /// `trials` independent unskilled trials,
+    ///
+    /// SR0 = sqrt(1 / (T - 1)) * ((1 - γ) * Φ⁻¹(1 - 1/N) + γ * Φ⁻¹(1 - 1/(N * e))),
+    ///
+    /// for the number of returns T and the Euler-Mascheroni constant γ, see
+    /// [`Bar::probabilistic_sharpe`].
+    ///
+    /// Returns `None` if `trials` is zero, there are fewer than four bars, or
+    /// the returns are constant.
+    #[must_use]
+    pub fn deflated_sharpe(bars: &[Bar], trials: usize) -> Option<f64> {
+        if trials == 0 {
+            return None;
+        }
+        let moments = SharpeMoments::from_returns(&simple_returns(bars))?;
+        let expected_max_sharpe = if trials == 1 {
+            0.0
+        } else {
+            let n = trials as f64;
+            (1.0 / (moments.count - 1.0)).sqrt()
+                * ((1.0 - EULER_MASCHERONI) * normal_inv_cdf(1.0 - 1.0 / n)
+                    + EULER_MASCHERONI * normal_inv_cdf(1.0 - 1.0 / (n * E)))
+        };
+        moments
+            .probabilistic_sharpe(expected_max_sharpe)
+            .map(|psr| 1.0 - psr)
+    }
+
+    /// Returns the minimum backtest length, in bars, for an observed annualized
+    /// Sharpe ratio of `target_sharpe` to be significantly positive at the
+    /// `alpha` significance level (Bailey & López de Prado), where
+    ///
+    /// MinTRL = 1 + (1 - γ3 * SR + (γ4 - 1) / 4 * SR²) * (z(1 - α) / SR)²,
+    ///
+    /// for the per-bar Sharpe ratio SR = `target_sharpe` / sqrt(`bars_per_year`),
+    /// and the `skewness` γ3 and (non-excess) `kurtosis` γ4 of the returns.
+    ///
+    /// Returns NaN if `target_sharpe` or `bars_per_year` are not positive, or
+    /// `alpha` is not in the range (0, 1).
+    #[must_use]
+    pub fn min_backtest_length(
+        target_sharpe: f64,
+        alpha: f64,
+        bars_per_year: f64,
+        skewness: f64,
+        kurtosis: f64,
+    ) -> f64 {
+        if !(target_sharpe > 0.0 && bars_per_year > 0.0 && alpha > 0.0 && alpha < 1.0) {
+            return f64::NAN;
+        }
+        let sharpe = target_sharpe / bars_per_year.sqrt();
+        let z = normal_inv_cdf(1.0 - alpha);
+        1.0 + (1.0 - skewness * sharpe + (kurtosis - 1.0) / 4.0 * sharpe.powi(2))
+            * (z / sharpe).powi(2)
+    }
+
+    /// Returns the bipower variation (BPV) of the close-to-close log returns,
+    /// where BPV = (π/2) * Σ |r(t-1)| * |r(t)|.
+    ///
+    /// BPV estimates the continuous component of the variance and is robust to
+    /// jumps. Returns `None` if there are fewer than three bars.
+    #[must_use]
+    pub fn bipower_variation(bars: &[Bar]) -> Option<f64> {
+        let returns = log_returns(bars);
+        if returns.len() < 2 {
+            return None;
+        }
+        let sum: f64 = returns.windows(2).map(|w| w[0].abs() * w[1].abs()).sum();
+        Some(std::f64::consts::FRAC_PI_2 * sum)
+    }
+
+    /// Returns the bipower variation along with the relative jump statistic
+    /// (RV - BPV) / RV, the share of realized variance attributable to jumps.
+    ///
+    /// Returns `None` if there are fewer than three bars or the realized
+    /// variance is zero.
+    #[must_use]
+    pub fn jump_test(bars: &[Bar]) -> Option<(f64, f64)> {
+        let bpv = Self::bipower_variation(bars)?;
+        let rv = Self::realized_variance(bars)?;
+        if rv == 0.0 {
+            return None;
+        }
+        Some((bpv, (rv - bpv) / rv))
+    }
+
+    /// Returns the half-life of mean reversion of the close prices, in bars,
+    /// from an Ornstein-Uhlenbeck fit.
+    ///
+    /// The price changes are regressed on the lagged prices by OLS,
+    /// Δp(t) = α + β * p(t-1) + ε, so that β = -λ for the mean reversion speed λ
+    /// of dp = λ(μ - p)dt + σdW, and the half-life is ln(2) / λ = -ln(2) / β.
+    ///
+    /// Returns `None` if there are fewer than three bars, the lagged prices are
+    /// constant, or the series is not mean reverting (β >= 0).
+    #[must_use]
+    pub fn half_life_ou(bars: &[Bar]) -> Option<f64> {
+        if bars.len() < 3 {
+            return None;
+        }
+        let (lagged, deltas): (Vec<f64>, Vec<f64>) = bars
+            .windows(2)
+            .map(|w| {
+                let prev = w[0].close.as_f64();
+                (prev, w[1].close.as_f64() - prev)
+            })
+            .unzip();
+
+        let n = lagged.len() as f64;
+        let mean_x = lagged.iter().sum::<f64>() / n;
+        let mean_y = deltas.iter().sum::<f64>() / n;
+        let (cov, var) = lagged
+            .iter()
+            .zip(&deltas)
+            .fold((0.0, 0.0), |(cov, var), (x, y)| {
+                (
+                    cov + (x - mean_x) * (y - mean_y),
+                    var + (x - mean_x).powi(2),
+                )
+            });
+        if var == 0.0 {
+            return None;
+        }
+
+        let beta = cov / var;
+        if beta >= 0.0 {
+            return None;
+        }
+        Some(-std::f64::consts::LN_2 / beta)
+    }
+
+    /// Returns the realized skewness of the bar, estimated from its intrabar
+    /// price path using the Amaya-Christoffersen formula
+    /// RSkew = √N * Σ r³ / RV^(3/2).
+    ///
+    /// Returns 0.0 if the bar has no range.
+    #[must_use]
+    pub fn realized_skewness_ohlc(&self) -> f64 {
+        let returns = intrabar_log_returns(self);
+        let rv: f64 = returns.iter().map(|r| r.powi(2)).sum();
+        if rv == 0.0 {
+            return 0.0;
+        }
+        let n = returns.len() as f64;
+        n.sqrt() * returns.iter().map(|r| r.powi(3)).sum::<f64>() / rv.powf(1.5)
+    }
+
+    /// Returns the realized kurtosis of the bar, estimated from its intrabar
+    /// price path using the Amaya-Christoffersen formula
+    /// RKurt = N * Σ r⁴ / RV².
+    ///
+    /// Returns 0.0 if the bar has no range.
+    #[must_use]
+    pub fn realized_kurtosis_ohlc(&self) -> f64 {
+        let returns = intrabar_log_returns(self);
+        let rv: f64 = returns.iter().map(|r| r.powi(2)).sum();
+        if rv == 0.0 {
+            return 0.0;
+        }
+        let n = returns.len() as f64;
+        n * returns.iter().map(|r| r.powi(4)).sum::<f64>() / rv.powi(2)
+    }
+
+    /// Returns the developing VWAP of the typical prices of the bars, parallel
+    /// to the bars, which resets at each bar for which `session_boundary_fn`
+    /// of its `ts_event` is true.
+    ///
+    /// Until a session has volume its VWAP is the mean typical price.
+    #[must_use]
+    pub fn developing_session_vwap(
+        bars: &[Bar],
+        session_boundary_fn: impl Fn(UnixNanos) -> bool,
+    ) -> Vec<Price> {
+        running_session_vwap(bars, |_, bar| session_boundary_fn(bar.ts_event))
+    }
+
+    /// Returns the developing VWAP of the bars, parallel to the bars, which
+    /// resets at the first bar of each UTC day.
+    #[must_use]
+    pub fn daily_vwap(bars: &[Bar]) -> Vec<Price> {
+        running_session_vwap(bars, |prev, bar| {
+            prev.ts_event / NANOSECONDS_IN_DAY != bar.ts_event / NANOSECONDS_IN_DAY
+        })
+    }
+
+    /// Returns the summary statistics of the bars with a `ts_event` within the
+    /// inclusive session range [`session_open_nanos`, `session_close_nanos`].
+    ///
+    /// When the session has no volume the VWAP is the mean typical price.
+    /// Returns `None` if no bars fall within the session.
+    #[must_use]
+    pub fn session_stats(
+        bars: &[Bar],
+        session_open_nanos: UnixNanos,
+        session_close_nanos: UnixNanos,
+    ) -> Option<SessionStats> {
+        let mut session = bars
+            .iter()
+            .filter(|bar| (session_open_nanos..=session_close_nanos).contains(&bar.ts_event));
+        let first = session.next()?;
+
+        let mut stats = SessionStats {
+            open: first.open,
+            high: first.high,
+            low: first.low,
+            close: first.close,
+            volume: first.volume,
+            vwap: 0.0,
+            bar_count: 1,
+        };
+        let mut typical_sum = typical_price(first);
+        let mut notional = typical_price(first) * first.volume.as_f64();
+
+        for bar in session {
+            stats.high = stats.high.max(bar.high);
+            stats.low = stats.low.min(bar.low);
+            stats.close = bar.close;
+            stats.volume += bar.volume;
+            stats.bar_count += 1;
+            typical_sum += typical_price(bar);
+            notional += typical_price(bar) * bar.volume.as_f64();
+        }
+
+        stats.vwap = if stats.volume.is_zero() {
+            typical_sum / stats.bar_count as f64
+        } else {
+            notional / stats.volume.as_f64()
+        };
+        Some(stats)
+    }
+
+    /// Returns the true range of the bar given the previous bar, where
+    /// TR = max(high - low, |high - prev_close|, |low - prev_close|).
+    #[must_use]
+    pub fn true_range(&self, prev: &Bar) -> Price {
+        let high_low = self.high.raw - self.low.raw;
+        let high_close = (self.high.raw - prev.close.raw).abs();
+        let low_close = (self.low.raw - prev.close.raw).abs();
+        Price::from_raw(high_low.max(high_close).max(low_close), self.high.precision)
+    }
+
+    /// Returns the average true range (ATR) over `period` bars using Wilder's
+    /// smoothing ATR(t) = (ATR(t-1) * (period - 1) + TR(t)) / period.
+    ///
+    /// The first bar contributes its high-low range, and the ATR is seeded with
+    /// the simple mean of the first `period` true ranges, so the output has
+    /// `period - 1` fewer elements than `bars`. The calculation is performed in
+    /// fixed-point, with an empty vec returned if `period` is zero or greater
+    /// than the number of bars.
+    #[must_use]
+    pub fn average_true_range(bars: &[Bar], period: usize) -> Vec<Price> {
+        if period == 0 || bars.len() < period {
+            return Vec::new();
+        }
+        let precision = bars[0].high.precision;
+        let true_ranges: Vec<i64> = std::iter::once(bars[0].high.raw - bars[0].low.raw)
+            .chain(bars.windows(2).map(|w| w[1].true_range(&w[0]).raw))
+            .collect();
+
+        // Accumulate in i128 so large prices or long periods cannot overflow,
+        // the ATR is an average of i64 true ranges so always fits back in an i64
+        let period_raw = period as i128;
+        let mut atr = true_ranges[..period]
+            .iter()
+            .map(|tr| i128::from(*tr))
+            .sum::<i128>()
+            / period_raw;
+        let mut result = Vec::with_capacity(bars.len() - period + 1);
+        result.push(Price::from_raw(atr as i64, precision));
+        for tr in &true_ranges[period..] {
+            atr = (atr * (period_raw - 1) + i128::from(*tr)) / period_raw;
+            result.push(Price::from_raw(atr as i64, precision));
+        }
+        result
+    }
+
+    /// Returns the mean simple return and its t-statistic for each calendar
+    /// bucket of `period`, as `(period_name, mean_return, t_statistic)`.
+    ///
+    /// Each close-to-close return is assigned to the bucket of the later bar's
+    /// `ts_event` (UTC). Buckets without returns are omitted, and the output is
+    /// in calendar order. The t-statistic is `mean / (std_dev / sqrt(n))` using
+    /// the sample standard deviation, and is NaN where it is undefined (fewer
+    /// than two returns, or zero dispersion).
+    #[must_use]
+    pub fn seasonality_strength(bars: &[Bar], period: SeasonalPeriod) -> Vec<(String, f64, f64)> {
+        let mut buckets: Vec<Vec<f64>> = vec![Vec::new(); period.bucket_count()];
+        for (ret, bar) in simple_returns(bars).into_iter().zip(bars.iter().skip(1)) {
+            buckets[period.bucket(bar.ts_event)].push(ret);
+        }
+
+        buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, returns)| !returns.is_empty())
+            .map(|(bucket, returns)| {
+                let n = returns.len() as f64;
+                let mean = returns.iter().sum::<f64>() / n;
+                let t_statistic = if returns.len() < 2 {
+                    f64::NAN
+                } else {
+                    let variance =
+                        returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+                    let std_err = (variance / n).sqrt();
+                    if std_err > 0.0 {
+                        mean / std_err
+                    } else {
+                        f64::NAN
+                    }
+                };
+                (period.bucket_name(bucket), mean, t_statistic)
+            })
+            .collect()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::bars_from_closes;
+
+    #[rstest]
+    fn test_median_absolute_deviation() {
+        let bars = bars_from_closes(&[1.0, 2.0, 3.0, 4.0, 100.0]);
+        let result = Bar::median_absolute_deviation(&bars, 5);
+
+        assert_eq!(result.len(), 5);
+        assert!(result[..4].iter().all(Option::is_none));
+        assert!(approx_eq!(f64, result[4].unwrap(), 1.0, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_median_absolute_deviation_rolling_window() {
+        let bars = bars_from_closes(&[1.0, 2.0, 4.0, 8.0]);
+        let result = Bar::median_absolute_deviation(&bars, 2);
+
+        assert_eq!(result[0], None);
+        assert!(approx_eq!(f64, result[1].unwrap(), 0.5, epsilon = 1e-9));
+        assert!(approx_eq!(f64, result[2].unwrap(), 1.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, result[3].unwrap(), 2.0, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_median_absolute_deviation_zero_period() {
+        let bars = bars_from_closes(&[1.0, 2.0]);
+        let result = Bar::median_absolute_deviation(&bars, 0);
+
+        assert_eq!(result, vec![None, None]);
+    }
+
+    #[rstest]
+    fn test_normalized_median_absolute_deviation() {
+        let bars = bars_from_closes(&[1.0, 2.0, 3.0, 4.0, 100.0]);
+        let result = Bar::normalized_median_absolute_deviation(&bars, 5);
+
+        assert!(approx_eq!(
+            f64,
+            result[4].unwrap(),
+            1.0 / MAD_NORMAL_CONSISTENCY,
+            epsilon = 1e-9
+        ));
+    }
+
+    #[rstest]
+    fn test_truncated_mean_return() {
+        // Returns: 0.1, -0.5, 1.0, 0.0, 0.1
+        let bars = bars_from_closes(&[1.0, 1.1, 0.55, 1.1, 1.1, 1.21]);
+        let result = Bar::truncated_mean_return(&bars, 0.2).unwrap();
+
+        assert!(approx_eq!(f64, result, 0.2 / 3.0, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_truncated_mean_return_without_trim_is_mean() {
+        let bars = bars_from_closes(&[1.0, 2.0, 1.0]);
+        let result = Bar::truncated_mean_return(&bars, 0.0).unwrap();
+
+        assert!(approx_eq!(f64, result, 0.25, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    #[case(-0.1)]
+    #[case(0.5)]
+    #[case(f64::NAN)]
+    fn test_truncated_mean_return_invalid_trim_percent(#[case] trim_percent: f64) {
+        let bars = bars_from_closes(&[1.0, 2.0, 1.0]);
+
+        assert_eq!(Bar::truncated_mean_return(&bars, trim_percent), None);
+    }
+
+    #[rstest]
+    fn test_truncated_mean_return_insufficient_bars() {
+        let bars = bars_from_closes(&[1.0]);
+
+        assert_eq!(Bar::truncated_mean_return(&bars, 0.1), None);
+    }
+
+    #[rstest]
+    #[case(0.0, (0.01_f64 + 0.04) / 3.0)]
+    #[case(-0.15, 0.0025 / 3.0)]
+    #[case(1.0, (0.81_f64 + 1.21 + 0.25 + 1.44) / 3.0)]
+    fn test_semi_deviation(#[case] threshold: f64, #[case] expected_variance: f64) {
+        // Returns of +10%, -10%, +50% and -20%
+        let bars = bars_from_closes(&[100.0, 110.0, 99.0, 148.5, 118.8]);
+
+        assert!(approx_eq!(
+            f64,
+            Bar::semi_deviation(&bars, threshold).unwrap(),
+            expected_variance.sqrt(),
+            epsilon = 1e-12
+        ));
+    }
+
+    #[rstest]
+    fn test_semi_deviation_no_downside() {
+        let bars = bars_from_closes(&[100.0, 110.0, 121.0]);
+
+        assert_eq!(Bar::semi_deviation(&bars, 0.0), Some(0.0));
+    }
+
+    #[rstest]
+    fn test_semi_deviation_insufficient_bars() {
+        let bars = bars_from_closes(&[100.0, 110.0]);
+
+        assert_eq!(Bar::semi_deviation(&bars, 0.0), None);
+    }
+
+    #[rstest]
+    fn test_gain_to_pain() {
+        // Returns of +10%, -10%, +50% and -20%
+        let bars = bars_from_closes(&[100.0, 110.0, 99.0, 148.5, 118.8]);
+
+        assert!(approx_eq!(
+            f64,
+            Bar::gain_to_pain(&bars).unwrap(),
+            0.6 / 0.3,
+            epsilon = 1e-12
+        ));
+    }
+
+    #[rstest]
+    fn test_gain_to_pain_no_losses() {
+        let bars = bars_from_closes(&[100.0, 110.0, 110.0, 121.0]);
+
+        assert_eq!(Bar::gain_to_pain(&bars), None);
+        assert_eq!(Bar::gain_to_pain(&bars[..1]), None);
+    }
+
+    #[rstest]
+    fn test_ulcer_index() {
+        // Drawdowns of 0%, 0%, -10%, -20%, 0% and 0%
+        let bars = bars_from_closes(&[100.0, 110.0, 99.0, 88.0, 110.0, 121.0]);
+        let expected = ((100.0_f64 + 400.0) / 6.0).sqrt();
+
+        assert!(approx_eq!(
+            f64,
+            Bar::ulcer_index(&bars),
+            expected,
+            epsilon = 1e-9
+        ));
+        assert!(approx_eq!(
+            f64,
+            Bar::martin_ratio(&bars).unwrap(),
+            21.0 / expected,
+            epsilon = 1e-9
+        ));
+    }
+
+    #[rstest]
+    fn test_ulcer_index_without_drawdowns() {
+        let bars = bars_from_closes(&[100.0, 110.0, 121.0]);
+
+        assert_eq!(Bar::ulcer_index(&bars), 0.0);
+        assert_eq!(Bar::martin_ratio(&bars), None);
+        assert!(Bar::ulcer_index(&[]).is_nan());
+        assert_eq!(Bar::martin_ratio(&bars[..1]), None);
+    }
+
+    #[rstest]
+    fn test_realized_variance() {
+        let bars = bars_from_closes(&[1.0, 2.0, 1.0]);
+        let expected = 2.0 * 2.0_f64.ln().powi(2);
+
+        assert!(approx_eq!(
+            f64,
+            Bar::realized_variance(&bars).unwrap(),
+            expected,
+            epsilon = 1e-12
+        ));
+    }
+
+    #[rstest]
+    fn test_bipower_variation() {
+        let bars = bars_from_closes(&[1.0, 2.0, 1.0]);
+        let expected = std::f64::consts::FRAC_PI_2 * 2.0_f64.ln().powi(2);
+
+        assert!(approx_eq!(
+            f64,
+            Bar::bipower_variation(&bars).unwrap(),
+            expected,
+            epsilon = 1e-12
+        ));
+    }
+
+    #[rstest]
+    fn test_bipower_variation_insufficient_bars() {
+        let bars = bars_from_closes(&[1.0, 2.0]);
+
+        assert_eq!(Bar::bipower_variation(&bars), None);
+        assert_eq!(Bar::jump_test(&bars), None);
+    }
+
+    #[rstest]
+    fn test_jump_test_detects_jump() {
+        let smooth = bars_from_closes(&[1.0, 1.01, 1.0, 1.01, 1.0, 1.01, 1.0]);
+        let jumpy = bars_from_closes(&[1.0, 1.01, 1.0, 1.5, 1.5, 1.51, 1.5]);
+        let (_, smooth_jump) = Bar::jump_test(&smooth).unwrap();
+        let (_, jumpy_jump) = Bar::jump_test(&jumpy).unwrap();
+
+        assert!(jumpy_jump > 0.5);
+        assert!(jumpy_jump > smooth_jump);
+    }
+
+    #[rstest]
+    fn test_jump_test_flat_prices() {
+        let bars = bars_from_closes(&[1.0, 1.0, 1.0]);
+
+        assert_eq!(Bar::jump_test(&bars), None);
+    }
+
+    fn create_ohlc_bar(open: f64, high: f64, low: f64, close: f64) -> Bar {
+        let mut bar = bars_from_closes(&[close])[0];
+        bar.open = Price::new(open, 5).unwrap();
+        bar.high = Price::new(high, 5).unwrap();
+        bar.low = Price::new(low, 5).unwrap();
+        bar
+    }
+
+    fn create_volatility_bars() -> Vec<Bar> {
+        [
+            (100.0, 102.0, 99.0, 101.0),
+            (101.5, 103.0, 100.5, 102.0),
+            (101.8, 102.5, 100.0, 100.5),
+            (100.2, 101.0, 98.5, 99.0),
+            (99.5, 100.8, 99.0, 100.4),
+        ]
+        .iter()
+        .map(|&(open, high, low, close)| create_ohlc_bar(open, high, low, close))
+        .collect()
+    }
+
+    #[rstest]
+    fn test_parkinson_volatility() {
+        let bars = create_volatility_bars();
+        assert!(approx_eq!(
+            f64,
+            Bar::parkinson_volatility(&bars),
+            0.014_851_091_605_054_944,
+            epsilon = 1e-12
+        ));
+    }
+
+    #[rstest]
+    fn test_garman_klass_volatility() {
+        let bars = create_volatility_bars();
+        assert!(approx_eq!(
+            f64,
+            Bar::garman_klass_volatility(&bars),
+            0.016_309_439_016_612_11,
+            epsilon = 1e-12
+        ));
+    }
+
+    #[rstest]
+    fn test_yang_zhang_volatility() {
+        let bars = create_volatility_bars();
+        assert!(approx_eq!(
+            f64,
+            Bar::yang_zhang_volatility(&bars),
+            0.015_126_573_537_036_885,
+            epsilon = 1e-12
+        ));
+    }
+
+    #[rstest]
+    fn test_ohlc_volatility_insufficient_bars() {
+        let bars = create_volatility_bars();
+
+        assert!(Bar::parkinson_volatility(&[]).is_nan());
+        assert!(Bar::parkinson_volatility(&bars[..1]).is_nan());
+        assert!(Bar::garman_klass_volatility(&[]).is_nan());
+        assert!(Bar::garman_klass_volatility(&bars[..1]).is_nan());
+        assert!(Bar::yang_zhang_volatility(&bars[..2]).is_nan());
+        assert!(!Bar::yang_zhang_volatility(&bars[..3]).is_nan());
+    }
+
+    #[rstest]
+    #[case(0.0, 0.5)]
+    #[case(1.0, 0.841_344_746)]
+    #[case(-1.96, 0.024_997_895)]
+    fn test_normal_cdf(#[case] x: f64, #[case] expected: f64) {
+        assert!(approx_eq!(f64, normal_cdf(x), expected, epsilon = 1e-6));
+    }
+
+    #[rstest]
+    #[case(0.5, 0.0)]
+    #[case(0.975, 1.959_963_985)]
+    #[case(0.01, -2.326_347_874)]
+    fn test_normal_inv_cdf(#[case] p: f64, #[case] expected: f64) {
+        assert!(approx_eq!(f64, normal_inv_cdf(p), expected, epsilon = 1e-8));
+    }
+
+    #[rstest]
+    #[case(1, 0.032_774_860_175_914_466)]
+    #[case(10, 0.298_790_740_154_883_8)]
+    #[case(100, 0.606_291_110_308_409_2)]
+    fn test_deflated_sharpe(#[case] trials: usize, #[case] expected: f64) {
+        let bars = bars_from_closes(&[
+            100.0, 101.0, 100.5, 102.0, 103.0, 102.5, 104.0, 105.5, 105.0, 107.0,
+        ]);
+
+        assert!(approx_eq!(
+            f64,
+            Bar::deflated_sharpe(&bars, trials).unwrap(),
+            expected,
+            epsilon = 1e-6
+        ));
+    }
+
+    #[rstest]
+    #[case(0.0, 0.967_225_139_824_085_5)]
+    #[case(0.5, 0.745_912_682_425_763_4)]
+    #[case(1.0, 0.302_185_470_959_063_6)]
+    fn test_probabilistic_sharpe(#[case] benchmark_sharpe: f64, #[case] expected: f64) {
+        let bars = bars_from_closes(&[
+            100.0, 101.0, 100.5, 102.0, 103.0, 102.5, 104.0, 105.5, 105.0, 107.0,
+        ]);
+
+        assert!(approx_eq!(
+            f64,
+            Bar::probabilistic_sharpe(&bars, benchmark_sharpe).unwrap(),
+            expected,
+            epsilon = 1e-6
+        ));
+    }
+
+    #[rstest]
+    fn test_probabilistic_sharpe_invalid_input() {
+        let bars = bars_from_closes(&[100.0, 101.0, 100.5]);
+        let flat = bars_from_closes(&[100.0, 100.0, 100.0, 100.0]);
+
+        assert_eq!(Bar::probabilistic_sharpe(&bars, 0.0), None);
+        assert_eq!(Bar::probabilistic_sharpe(&flat, 0.0), None);
+    }
+
+    #[rstest]
+    fn test_deflated_sharpe_invalid_input() {
+        let bars = bars_from_closes(&[100.0, 101.0, 100.5, 102.0]);
+        let flat = bars_from_closes(&[100.0, 100.0, 100.0, 100.0]);
+
+        assert_eq!(Bar::deflated_sharpe(&bars, 0), None);
+        assert_eq!(Bar::deflated_sharpe(&bars[..3], 1), None);
+        assert_eq!(Bar::deflated_sharpe(&flat, 1), None);
+        assert!(Bar::deflated_sharpe(&bars, 1).is_some());
+    }
+
+    #[rstest]
+    #[case(1.0, 0.05, 252.0, 0.0, 3.0, 684.149_722_159_091_5)]
+    #[case(2.0, 0.01, 252.0, -1.0, 6.0, 391.669_797_554_156_9)]
+    fn test_min_backtest_length(
+        #[case] target_sharpe: f64,
+        #[case] alpha: f64,
+        #[case] bars_per_year: f64,
+        #[case] skewness: f64,
+        #[case] kurtosis: f64,
+        #[case] expected: f64,
+    ) {
+        let length =
+            Bar::min_backtest_length(target_sharpe, alpha, bars_per_year, skewness, kurtosis);
+        assert!(approx_eq!(f64, length, expected, epsilon = 1e-5));
+    }
+
+    #[rstest]
+    #[case(0.0, 0.05, 252.0)]
+    #[case(1.0, 0.0, 252.0)]
+    #[case(1.0, 1.0, 252.0)]
+    #[case(1.0, 0.05, 0.0)]
+    #[case(f64::NAN, 0.05, 252.0)]
+    fn test_min_backtest_length_invalid_input(
+        #[case] target_sharpe: f64,
+        #[case] alpha: f64,
+        #[case] bars_per_year: f64,
+    ) {
+        assert!(Bar::min_backtest_length(target_sharpe, alpha, bars_per_year, 0.0, 3.0).is_nan());
+    }
+
+    #[rstest]
+    fn test_realized_skewness_ohlc() {
+        // Path O -> L -> H -> C has log legs (-x, 2x, -x) where x = ln(2)
+        let bar = create_ohlc_bar(1.0, 2.0, 0.5, 1.0);
+
+        assert!(approx_eq!(
+            f64,
+            bar.realized_skewness_ohlc(),
+            std::f64::consts::FRAC_1_SQRT_2,
+            epsilon = 1e-9
+        ));
+    }
+
+    #[rstest]
+    fn test_realized_skewness_ohlc_sign() {
+        let up = create_ohlc_bar(1.0, 1.5, 0.99, 1.49);
+        let down = create_ohlc_bar(1.0, 1.01, 0.6, 0.61);
+
+        assert!(up.realized_skewness_ohlc() > 0.0);
+        assert!(down.realized_skewness_ohlc() < 0.0);
+    }
+
+    #[rstest]
+    fn test_realized_kurtosis_ohlc() {
+        // Log legs (-x, 2x, -x) gives 3 * 18x⁴ / (6x²)² = 1.5
+        let bar = create_ohlc_bar(1.0, 2.0, 0.5, 1.0);
+
+        assert!(approx_eq!(
+            f64,
+            bar.realized_kurtosis_ohlc(),
+            1.5,
+            epsilon = 1e-9
+        ));
+    }
+
+    #[rstest]
+    fn test_realized_moments_ohlc_flat_bar() {
+        let bar = create_ohlc_bar(1.0, 1.0, 1.0, 1.0);
+
+        assert_eq!(bar.realized_skewness_ohlc(), 0.0);
+        assert_eq!(bar.realized_kurtosis_ohlc(), 0.0);
+    }
+
+    #[rstest]
+    fn test_session_stats() {
+        let mut bars = bars_from_closes(&[1.0, 2.0, 4.0, 3.0, 5.0]);
+        bars[2].volume = Quantity::from("300000");
+        let session_open = bars[1].ts_event;
+        let session_close = bars[3].ts_event;
+        let stats = Bar::session_stats(&bars, session_open, session_close).unwrap();
+
+        assert_eq!(stats.open, Price::from("1.00000"));
+        assert_eq!(stats.high, Price::from("4.00000"));
+        assert_eq!(stats.low, Price::from("1.00000"));
+        assert_eq!(stats.close, Price::from("3.00000"));
+        assert_eq!(stats.volume, Quantity::from("500000"));
+        assert_eq!(stats.bar_count, 3);
+        // Typical prices 5/3, 10/3, 10/3 weighted 1:3:1
+        assert!(approx_eq!(f64, stats.vwap, 3.0, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_session_stats_zero_volume() {
+        let mut bars = bars_from_closes(&[1.0, 1.0]);
+        for bar in &mut bars {
+            bar.volume = Quantity::from("0");
+        }
+        let stats = Bar::session_stats(&bars, 0, u64::MAX).unwrap();
+
+        assert!(approx_eq!(f64, stats.vwap, 1.0, epsilon = 1e-9));
+    }
+
+    #[rstest]
+    fn test_session_stats_no_bars_in_session() {
+        let bars = bars_from_closes(&[1.0, 2.0]);
+
+        assert_eq!(Bar::session_stats(&bars, 0, 1), None);
+    }
+
+    /// Returns 48 hourly bars over two UTC days, closing at 1.0 then 2.0 on
+    /// the first day and at 10.0 on the second, with the volume of each bar
+    /// equal to its index plus one.
+    fn create_two_day_hourly_bars() -> Vec<Bar> {
+        let closes: Vec<f64> = (0..48)
+            .map(|i| match i {
+                0..=11 => 1.0,
+                12..=23 => 2.0,
+                _ => 10.0,
+            })
+            .collect();
+        let mut bars = bars_from_closes(&closes);
+        for (i, bar) in bars.iter_mut().enumerate() {
+            bar.open = bar.close;
+            bar.high = bar.close;
+            bar.low = bar.close;
+            bar.volume = Quantity::from(i as i64 + 1);
+            bar.ts_event = i as u64 * 3_600_000_000_000;
+            bar.ts_init = bar.ts_event;
+        }
+        bars
+    }
+
+    #[rstest]
+    fn test_daily_vwap_resets_at_utc_midnight() {
+        let bars = create_two_day_hourly_bars();
+        let vwap = Bar::daily_vwap(&bars);
+
+        assert_eq!(vwap.len(), 48);
+        assert_eq!(vwap[0], Price::from("1.00000"));
+        assert_eq!(vwap[11], Price::from("1.00000"));
+        // Volumes 1..=12 at 1.0 (78) and 13..=24 at 2.0 (222): (78 + 444) / 300
+        assert_eq!(vwap[23], Price::from("1.74000"));
+        assert_eq!(vwap[24], Price::from("10.00000"));
+        assert_eq!(vwap[47], Price::from("10.00000"));
+    }
+
+    #[rstest]
+    fn test_developing_session_vwap_with_custom_boundary() {
+        let bars = create_two_day_hourly_bars();
+        let noon = 12 * 3_600_000_000_000;
+        let vwap = Bar::developing_session_vwap(&bars, |ts| ts == noon);
+
+        assert_eq!(vwap[11], Price::from("1.00000"));
+        assert_eq!(vwap[12], Price::from("2.00000"));
+        // The second session runs across midnight: (222 * 2 + 876 * 10) / 1098
+        assert_eq!(vwap[47], Price::from("8.38251"));
+    }
+
+    #[rstest]
+    fn test_developing_session_vwap_zero_volume() {
+        let mut bars = bars_from_closes(&[1.0, 2.0, 3.0]);
+        for bar in &mut bars {
+            bar.high = bar.close;
+            bar.low = bar.close;
+            bar.volume = Quantity::from(0);
+        }
+        let vwap = Bar::developing_session_vwap(&bars, |_| false);
+
+        assert_eq!(
+            vwap,
+            vec![
+                Price::from("1.00000"),
+                Price::from("1.50000"),
+                Price::from("2.00000")
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_true_range() {
+        let prev = create_ohlc_bar(10.0, 11.0, 9.5, 10.5);
+        let gap_up = create_ohlc_bar(12.0, 13.0, 11.5, 12.5);
+        let inside = create_ohlc_bar(10.5, 10.8, 10.0, 10.2);
+        let gap_down = create_ohlc_bar(9.0, 9.2, 8.0, 8.5);
+
+        // max(1.5, 2.5, 1.0)
+        assert_eq!(gap_up.true_range(&prev), Price::from("2.50000"));
+        // max(0.8, 0.3, 0.5)
+        assert_eq!(inside.true_range(&prev), Price::from("0.80000"));
+        // max(1.2, 1.3, 2.5)
+        assert_eq!(gap_down.true_range(&prev), Price::from("2.50000"));
+    }
+
+    #[rstest]
+    fn test_average_true_range() {
+        let bars = vec![
+            create_ohlc_bar(10.0, 11.0, 9.0, 10.0),
+            create_ohlc_bar(10.0, 12.0, 10.0, 11.0),
+            create_ohlc_bar(11.0, 11.0, 7.0, 8.0),
+            create_ohlc_bar(8.0, 9.0, 8.0, 8.0),
+        ];
+        let atr = Bar::average_true_range(&bars, 2);
+
+        // True ranges: 2.0, 2.0, 4.0, 1.0
+        assert_eq!(
+            atr,
+            vec![
+                Price::from("2.00000"),
+                Price::from("3.00000"),
+                Price::from("2.00000"),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_average_true_range_flat_series() {
+        let bars = bars_from_closes(&[1.0; 30]);
+        let atr = Bar::average_true_range(&bars, 14);
+
+        assert_eq!(atr.len(), 17);
+        assert!(atr.iter().all(|price| price.as_f64().abs() < 1e-9));
+    }
+
+    #[rstest]
+    fn test_average_true_range_large_prices() {
+        let bars = vec![create_ohlc_bar(5e9, 9e9, 1e9, 5e9); 20];
+        let atr = Bar::average_true_range(&bars, 14);
+
+        assert_eq!(atr.len(), 7);
+        assert!(atr
+            .iter()
+            .all(|price| *price == Price::new(8e9, 5).unwrap()));
+    }
+
+    #[rstest]
+    fn test_average_true_range_insufficient_bars() {
+        let bars = bars_from_closes(&[1.0, 2.0]);
+
+        assert!(Bar::average_true_range(&bars, 3).is_empty());
+        assert!(Bar::average_true_range(&bars, 0).is_empty());
+    }
+
+    #[rstest]
+    fn test_half_life_ou_exponential_decay() {
+        // p(t) = 100 + 16 * 0.5^t, so that Δp = -0.5 * (p(t-1) - 100) exactly
+        let closes: Vec<f64> = (0..6).map(|t| 100.0 + 16.0 * 0.5f64.powi(t)).collect();
+        let bars = bars_from_closes(&closes);
+        let half_life = Bar::half_life_ou(&bars).unwrap();
+
+        assert!(approx_eq!(
+            f64,
+            half_life,
+            2.0 * std::f64::consts::LN_2,
+            epsilon = 1e-9
+        ));
+    }
+
+    #[rstest]
+    fn test_half_life_ou_trending_series() {
+        let bars = bars_from_closes(&[1.0, 2.0, 4.0, 8.0, 16.0]);
+
+        assert!(Bar::half_life_ou(&bars).is_none());
+    }
+
+    #[rstest]
+    fn test_half_life_ou_degenerate_input() {
+        assert!(Bar::half_life_ou(&bars_from_closes(&[1.0, 2.0])).is_none());
+        assert!(Bar::half_life_ou(&bars_from_closes(&[1.0; 5])).is_none());
+    }
+
+    #[rstest]
+    fn test_seasonality_strength_day_of_week() {
+        // Daily bars from Thursday 1970-01-01, with the two Monday returns at +2% and +4%
+        let mut closes = vec![100.0; 15];
+        for close in &mut closes[4..11] {
+            *close = 102.0;
+        }
+        for close in &mut closes[11..] {
+            *close = 106.08;
+        }
+        let mut bars = bars_from_closes(&closes);
+        for (i, bar) in bars.iter_mut().enumerate() {
+            bar.ts_event = i as UnixNanos * 86_400_000_000_000;
+            bar.ts_init = bar.ts_event;
+        }
+        let result = Bar::seasonality_strength(&bars, SeasonalPeriod::DayOfWeek);
+
+        let names: Vec<&str> = result.iter().map(|(name, _, _)| name.as_str()).collect();
+        assert_eq!(names, DAY_NAMES.to_vec());
+        let (_, monday_mean, monday_t) = &result[0];
+        assert!(approx_eq!(f64, *monday_mean, 0.03, epsilon = 1e-9));
+        assert!(approx_eq!(f64, *monday_t, 3.0, epsilon = 1e-6));
+        assert!(result[1..]
+            .iter()
+            .all(|(_, mean, t)| *mean == 0.0 && t.is_nan()));
+    }
+
+    #[rstest]
+    fn test_seasonality_strength_hour_of_day() {
+        let bars = bars_from_closes(&[1.0, 2.0, 1.0, 2.0]);
+        let result = Bar::seasonality_strength(&bars, SeasonalPeriod::HourOfDay);
+
+        assert_eq!(result.len(), 1);
+        let (name, mean, t_statistic) = &result[0];
+        assert_eq!(name, "00");
+        assert!(approx_eq!(f64, *mean, 0.5, epsilon = 1e-9));
+        assert!(approx_eq!(
+            f64,
+            *t_statistic,
+            0.5 / (0.75f64 / 3.0).sqrt(),
+            epsilon = 1e-9
+        ));
+    }
+
+    #[rstest]
+    fn test_seasonality_strength_month_of_year() {
+        let mut bars = bars_from_closes(&[1.0, 1.1]);
+        // 1970-02-01 00:00:00 UTC
+        bars[1].ts_event = 31 * 86_400_000_000_000;
+        let result = Bar::seasonality_strength(&bars, SeasonalPeriod::MonthOfYear);
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].0, "February");
+        assert!(result[0].2.is_nan());
+    }
+
+    #[rstest]
+    fn test_seasonality_strength_insufficient_bars() {
+        let bars = bars_from_closes(&[1.0]);
+
+        assert!(Bar::seasonality_strength(&bars, SeasonalPeriod::DayOfWeek).is_empty());
+    }
+}
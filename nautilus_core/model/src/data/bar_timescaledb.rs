@@ -0,0 +1,138 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Batch insertion of [`Bar`]s into TimescaleDB hypertables.
+
+use std::io::Write;
+
+use nautilus_core::datetime::unix_nanos_to_iso8601;
+
+use super::bar::Bar;
+
+/// The SQL creating the `bars` table and converting it to a hypertable
+/// partitioned on `time`, with columns in the order of the rows written by
+/// [`bars_to_timescaledb_copy`].
+///
+/// The `time` column holds the `ts_event` timestamp at the microsecond
+/// resolution of PostgreSQL, with the full nanosecond timestamps kept in the
+/// `ts_event` and `ts_init` columns.
+const TIMESCALEDB_CREATE_HYPERTABLE_SQL: &str = "CREATE TABLE IF NOT EXISTS bars (
+    time TIMESTAMPTZ NOT NULL,
+    bar_type TEXT NOT NULL,
+    open DOUBLE PRECISION NOT NULL,
+    high DOUBLE PRECISION NOT NULL,
+    low DOUBLE PRECISION NOT NULL,
+    close DOUBLE PRECISION NOT NULL,
+    volume DOUBLE PRECISION NOT NULL,
+    ts_event BIGINT NOT NULL,
+    ts_init BIGINT NOT NULL
+);
+SELECT create_hypertable('bars', 'time', if_not_exists => TRUE);";
+
+impl Bar {
+    /// Returns the SQL creating the TimescaleDB `bars` hypertable.
+    #[must_use]
+    pub fn timescaledb_create_hypertable_sql() -> &'static str {
+        TIMESCALEDB_CREATE_HYPERTABLE_SQL
+    }
+}
+
+/// Returns the value with backslashes, tabs and line breaks escaped for the
+/// PostgreSQL `COPY` text format.
+fn escape_copy_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Writes the `bars` to the `writer` as rows of the PostgreSQL `COPY` text
+/// format, for `COPY bars FROM STDIN`.
+///
+/// Each row is the tab-separated columns of the table created by
+/// [`Bar::timescaledb_create_hypertable_sql`], terminated by a newline.
+///
+/// # Errors
+///
+/// If writing to the `writer` fails.
+pub fn bars_to_timescaledb_copy(bars: &[Bar], writer: &mut impl Write) -> std::io::Result<()> {
+    for bar in bars {
+        writeln!(
+            writer,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            unix_nanos_to_iso8601(bar.ts_event),
+            escape_copy_text(&bar.bar_type.to_string()),
+            bar.open,
+            bar.high,
+            bar.low,
+            bar.close,
+            bar.volume,
+            bar.ts_event,
+            bar.ts_init,
+        )?;
+    }
+    Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::*;
+
+    #[rstest]
+    fn test_bars_to_timescaledb_copy(bar_audusd_sim_minute_bid: Bar) {
+        let mut second = bar_audusd_sim_minute_bid;
+        second.ts_event = 60_000_000_000;
+        second.ts_init = 60_000_000_001;
+        let mut buffer = Vec::new();
+        bars_to_timescaledb_copy(&[bar_audusd_sim_minute_bid, second], &mut buffer).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "1970-01-01T00:00:00.000000000Z\tAUDUSD.SIM-1-MINUTE-BID-EXTERNAL\t\
+             1.00001\t1.00004\t1.00002\t1.00003\t100000\t0\t1\n\
+             1970-01-01T00:01:00.000000000Z\tAUDUSD.SIM-1-MINUTE-BID-EXTERNAL\t\
+             1.00001\t1.00004\t1.00002\t1.00003\t100000\t60000000000\t60000000001\n"
+        );
+    }
+
+    #[rstest]
+    fn test_bars_to_timescaledb_copy_empty() {
+        let mut buffer = Vec::new();
+        bars_to_timescaledb_copy(&[], &mut buffer).unwrap();
+
+        assert!(buffer.is_empty());
+    }
+
+    #[rstest]
+    fn test_escape_copy_text() {
+        assert_eq!(escape_copy_text("a\\b\tc\nd\re"), "a\\\\b\\tc\\nd\\re");
+    }
+
+    #[rstest]
+    fn test_timescaledb_create_hypertable_sql() {
+        let sql = Bar::timescaledb_create_hypertable_sql();
+
+        assert!(sql.starts_with("CREATE TABLE IF NOT EXISTS bars"));
+        assert!(sql.contains("time TIMESTAMPTZ NOT NULL"));
+        assert!(sql.contains("SELECT create_hypertable('bars', 'time'"));
+    }
+}
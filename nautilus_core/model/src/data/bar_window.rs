@@ -0,0 +1,180 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use super::bar::Bar;
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum BarWindowError {
+    #[error("Invalid window size, was zero")]
+    ZeroWindow,
+    #[error("Invalid window size {window}, greater than the number of bars {len}")]
+    WindowTooLarge { window: usize, len: usize },
+}
+
+fn check_window(window: usize, len: usize) -> Result<(), BarWindowError> {
+    if window == 0 {
+        return Err(BarWindowError::ZeroWindow);
+    }
+    if window > len {
+        return Err(BarWindowError::WindowTooLarge { window, len });
+    }
+    Ok(())
+}
+
+/// Provides an iterator over trailing look-back windows of a bar series.
+///
+/// Each yielded slice contains `window` bars, advancing one bar at a time.
+#[derive(Clone, Debug)]
+pub struct BarWindow<'a> {
+    bars: &'a [Bar],
+    window: usize,
+    pos: usize,
+}
+
+impl<'a> BarWindow<'a> {
+    /// Creates a new [`BarWindow`] over `bars` with the given `window` size.
+    pub fn new(bars: &'a [Bar], window: usize) -> Result<Self, BarWindowError> {
+        check_window(window, bars.len())?;
+        Ok(Self {
+            bars,
+            window,
+            pos: 0,
+        })
+    }
+}
+
+impl<'a> Iterator for BarWindow<'a> {
+    type Item = &'a [Bar];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.pos + self.window;
+        if end > self.bars.len() {
+            return None;
+        }
+        let slice = &self.bars[self.pos..end];
+        self.pos += 1;
+        Some(slice)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.bars.len() + 1).saturating_sub(self.pos + self.window);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for BarWindow<'a> {}
+
+/// Provides mutable access to trailing look-back windows of a bar series.
+///
+/// As successive windows overlap this cannot implement [`Iterator`], instead
+/// call [`BarWindowMut::next_window`] until it returns `None`.
+#[derive(Debug)]
+pub struct BarWindowMut<'a> {
+    bars: &'a mut Vec<Bar>,
+    window: usize,
+    pos: usize,
+}
+
+impl<'a> BarWindowMut<'a> {
+    /// Creates a new [`BarWindowMut`] over `bars` with the given `window` size.
+    pub fn new(bars: &'a mut Vec<Bar>, window: usize) -> Result<Self, BarWindowError> {
+        check_window(window, bars.len())?;
+        Ok(Self {
+            bars,
+            window,
+            pos: 0,
+        })
+    }
+
+    /// Returns the next window of bars, or `None` when fewer than `window`
+    /// bars remain.
+    pub fn next_window(&mut self) -> Option<&mut [Bar]> {
+        let end = self.pos + self.window;
+        if end > self.bars.len() {
+            return None;
+        }
+        let start = self.pos;
+        self.pos += 1;
+        Some(&mut self.bars[start..end])
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{data::bar::stubs::bars_from_closes, types::quantity::Quantity};
+
+    #[rstest]
+    fn test_bar_window() {
+        let bars = bars_from_closes(&[1.0, 2.0, 3.0, 4.0]);
+        let windows: Vec<&[Bar]> = BarWindow::new(&bars, 3).unwrap().collect();
+
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0], &bars[0..3]);
+        assert_eq!(windows[1], &bars[1..4]);
+    }
+
+    #[rstest]
+    fn test_bar_window_len() {
+        let bars = bars_from_closes(&[1.0, 2.0, 3.0, 4.0]);
+        let mut window = BarWindow::new(&bars, 2).unwrap();
+
+        assert_eq!(window.len(), 3);
+        window.next();
+        assert_eq!(window.len(), 2);
+    }
+
+    #[rstest]
+    fn test_bar_window_full_length() {
+        let bars = bars_from_closes(&[1.0, 2.0]);
+
+        assert_eq!(BarWindow::new(&bars, 2).unwrap().count(), 1);
+    }
+
+    #[rstest]
+    fn test_bar_window_invalid_size() {
+        let bars = bars_from_closes(&[1.0, 2.0]);
+
+        assert_eq!(
+            BarWindow::new(&bars, 0).unwrap_err(),
+            BarWindowError::ZeroWindow
+        );
+        assert_eq!(
+            BarWindow::new(&bars, 3).unwrap_err(),
+            BarWindowError::WindowTooLarge { window: 3, len: 2 }
+        );
+    }
+
+    #[rstest]
+    fn test_bar_window_mut() {
+        let mut bars = bars_from_closes(&[1.0, 2.0, 3.0]);
+        let mut windows = BarWindowMut::new(&mut bars, 2).unwrap();
+        let mut count = 0;
+        while let Some(window) = windows.next_window() {
+            window[1].volume = Quantity::from("1");
+            count += 1;
+        }
+
+        assert_eq!(count, 2);
+        assert_eq!(bars[0].volume, Quantity::from("100000"));
+        assert_eq!(bars[1].volume, Quantity::from("1"));
+        assert_eq!(bars[2].volume, Quantity::from("1"));
+    }
+}
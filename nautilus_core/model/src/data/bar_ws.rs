@@ -0,0 +1,188 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Conversion of [`Bar`]s to and from JSON WebSocket messages.
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+use super::bar::Bar;
+use crate::identifiers::instrument_id::InstrumentId;
+
+#[derive(thiserror::Error, Debug)]
+pub enum WsJsonError {
+    #[error("Failed to parse JSON message: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Unexpected message type, expected '{0}'")]
+    UnexpectedType(&'static str),
+    #[error("Message instrument ID {message} does not match the bar instrument ID {bar}")]
+    InstrumentIdMismatch {
+        message: InstrumentId,
+        bar: InstrumentId,
+    },
+}
+
+/// The envelope of bar WebSocket messages.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum WsMessage<'a> {
+    Bar {
+        instrument_id: InstrumentId,
+        data: Bar,
+    },
+    Bars {
+        data: Cow<'a, [Bar]>,
+    },
+}
+
+impl Bar {
+    /// Returns the bar as a JSON WebSocket message, of the form
+    /// `{"type":"bar","instrument_id":"...","data":{...}}`.
+    #[must_use]
+    pub fn to_ws_json(&self) -> String {
+        let message = WsMessage::Bar {
+            instrument_id: self.bar_type.instrument_id,
+            data: *self,
+        };
+        // Unwrapping is safe when serializing a valid object
+        serde_json::to_string(&message).unwrap()
+    }
+
+    /// Returns the bar parsed from a JSON WebSocket message, as returned by
+    /// [`Bar::to_ws_json`].
+    ///
+    /// # Errors
+    ///
+    /// If the message is not a valid bar message, or its instrument ID does not
+    /// match the bar.
+    pub fn from_ws_json(s: &str) -> Result<Self, WsJsonError> {
+        match serde_json::from_str(s)? {
+            WsMessage::Bar {
+                instrument_id,
+                data,
+            } => {
+                if instrument_id != data.bar_type.instrument_id {
+                    return Err(WsJsonError::InstrumentIdMismatch {
+                        message: instrument_id,
+                        bar: data.bar_type.instrument_id,
+                    });
+                }
+                Ok(data)
+            }
+            WsMessage::Bars { .. } => Err(WsJsonError::UnexpectedType("bar")),
+        }
+    }
+
+    /// Returns the `bars` as a single JSON WebSocket message, of the form
+    /// `{"type":"bars","data":[{...},...]}`, for streaming multiple bars per
+    /// message. The bars may be of different instruments.
+    #[must_use]
+    pub fn to_ws_json_batch(bars: &[Self]) -> String {
+        let message = WsMessage::Bars {
+            data: Cow::Borrowed(bars),
+        };
+        // Unwrapping is safe when serializing valid objects
+        serde_json::to_string(&message).unwrap()
+    }
+
+    /// Returns the bars parsed from a JSON WebSocket message, as returned by
+    /// [`Bar::to_ws_json_batch`].
+    ///
+    /// # Errors
+    ///
+    /// If the message is not a valid batch bars message.
+    pub fn from_ws_json_batch(s: &str) -> Result<Vec<Self>, WsJsonError> {
+        match serde_json::from_str(s)? {
+            WsMessage::Bars { data } => Ok(data.into_owned()),
+            WsMessage::Bar { .. } => Err(WsJsonError::UnexpectedType("bars")),
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::*;
+
+    #[rstest]
+    fn test_to_ws_json(bar_audusd_sim_minute_bid: Bar) {
+        let json = bar_audusd_sim_minute_bid.to_ws_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert!(json.starts_with(r#"{"type":"bar","instrument_id":"AUDUSD.SIM","data":{"#));
+        assert_eq!(value["data"]["open"], "1.00001");
+        assert_eq!(
+            value["data"]["bar_type"],
+            "AUDUSD.SIM-1-MINUTE-BID-EXTERNAL"
+        );
+    }
+
+    #[rstest]
+    fn test_ws_json_round_trip(bar_audusd_sim_minute_bid: Bar) {
+        let json = bar_audusd_sim_minute_bid.to_ws_json();
+
+        assert_eq!(Bar::from_ws_json(&json).unwrap(), bar_audusd_sim_minute_bid);
+    }
+
+    #[rstest]
+    fn test_ws_json_batch_round_trip() {
+        let bars = bars_from_closes(&[1.0, 1.1, 1.2]);
+        let json = Bar::to_ws_json_batch(&bars);
+
+        assert!(json.starts_with(r#"{"type":"bars","data":[{"#));
+        assert_eq!(Bar::from_ws_json_batch(&json).unwrap(), bars);
+        assert!(Bar::from_ws_json_batch(&Bar::to_ws_json_batch(&[]))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[rstest]
+    fn test_from_ws_json_unexpected_type(bar_audusd_sim_minute_bid: Bar) {
+        let single = bar_audusd_sim_minute_bid.to_ws_json();
+        let batch = Bar::to_ws_json_batch(&[bar_audusd_sim_minute_bid]);
+
+        assert!(matches!(
+            Bar::from_ws_json(&batch),
+            Err(WsJsonError::UnexpectedType("bar"))
+        ));
+        assert!(matches!(
+            Bar::from_ws_json_batch(&single),
+            Err(WsJsonError::UnexpectedType("bars"))
+        ));
+        assert!(matches!(
+            Bar::from_ws_json(r#"{"type":"quote"}"#),
+            Err(WsJsonError::Json(_))
+        ));
+    }
+
+    #[rstest]
+    fn test_from_ws_json_mismatched_instrument_id(bar_audusd_sim_minute_bid: Bar) {
+        let json = bar_audusd_sim_minute_bid.to_ws_json().replace(
+            r#""instrument_id":"AUDUSD.SIM""#,
+            r#""instrument_id":"EURUSD.SIM""#,
+        );
+
+        assert!(matches!(
+            Bar::from_ws_json(&json),
+            Err(WsJsonError::InstrumentIdMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,188 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Conversion of [`Bar`]s to and from ZeroMQ multi-part messages.
+
+use std::str::FromStr;
+
+use nautilus_core::serialization::Serializable;
+
+use super::bar::{Bar, BarType};
+
+/// The number of frames of a bar message.
+const ZMQ_FRAME_COUNT: usize = 3;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ZmqError {
+    #[error("Invalid frame count {0}, expected {ZMQ_FRAME_COUNT} frames")]
+    InvalidFrameCount(usize),
+    #[error("Invalid topic frame: {0}")]
+    InvalidTopic(String),
+    #[error("Invalid metadata frame of {0} bytes, expected 2 bytes")]
+    InvalidMetadata(usize),
+    #[error("Failed to decode MsgPack data frame: {0}")]
+    Decode(#[from] rmp_serde::decode::Error),
+    #[error("Topic bar type {topic} does not match the data bar type {data}")]
+    BarTypeMismatch {
+        topic: Box<BarType>,
+        data: Box<BarType>,
+    },
+    #[error("Metadata precisions {expected:?} do not match the data precisions {actual:?}")]
+    PrecisionMismatch {
+        expected: (u8, u8),
+        actual: (u8, u8),
+    },
+}
+
+impl Bar {
+    /// Returns the bar as the frames of a ZeroMQ multi-part message,
+    /// `[topic, metadata, data]`.
+    ///
+    /// - The topic frame is the bar type string, so subscribers can filter on a
+    ///   prefix such as `AUDUSD.SIM-1-MINUTE`.
+    /// - The metadata frame is the price precision then the size precision, one
+    ///   byte each.
+    /// - The data frame is the MsgPack encoded bar.
+    #[must_use]
+    pub fn to_zmq_frames(&self) -> [Vec<u8>; ZMQ_FRAME_COUNT] {
+        [
+            self.bar_type.to_string().into_bytes(),
+            vec![self.open.precision, self.volume.precision],
+            // Unwrapping is safe when serializing a valid object
+            self.as_msgpack_bytes().unwrap(),
+        ]
+    }
+
+    /// Returns the bar decoded from the `frames` of a ZeroMQ multi-part message,
+    /// as returned by [`Bar::to_zmq_frames`].
+    ///
+    /// # Errors
+    ///
+    /// If there are not exactly three frames, a frame is invalid, or the topic
+    /// and metadata do not match the decoded bar.
+    pub fn from_zmq_frames<T: AsRef<[u8]>>(frames: &[T]) -> Result<Self, ZmqError> {
+        let [topic, metadata, data] = frames else {
+            return Err(ZmqError::InvalidFrameCount(frames.len()));
+        };
+        let topic = std::str::from_utf8(topic.as_ref())
+            .map_err(|e| ZmqError::InvalidTopic(e.to_string()))?;
+        let bar_type =
+            BarType::from_str(topic).map_err(|e| ZmqError::InvalidTopic(e.to_string()))?;
+        let &[price_precision, size_precision] = metadata.as_ref() else {
+            return Err(ZmqError::InvalidMetadata(metadata.as_ref().len()));
+        };
+
+        let bar = Self::from_msgpack_bytes(data.as_ref().to_vec())?;
+        if bar.bar_type != bar_type {
+            return Err(ZmqError::BarTypeMismatch {
+                topic: Box::new(bar_type),
+                data: Box::new(bar.bar_type),
+            });
+        }
+        let actual = (bar.open.precision, bar.volume.precision);
+        if actual != (price_precision, size_precision) {
+            return Err(ZmqError::PrecisionMismatch {
+                expected: (price_precision, size_precision),
+                actual,
+            });
+        }
+        Ok(bar)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::bar::stubs::*;
+
+    #[rstest]
+    fn test_to_zmq_frames(bar_audusd_sim_minute_bid: Bar) {
+        let [topic, metadata, data] = bar_audusd_sim_minute_bid.to_zmq_frames();
+
+        assert_eq!(topic, b"AUDUSD.SIM-1-MINUTE-BID-EXTERNAL");
+        assert_eq!(metadata, vec![5, 0]);
+        assert_eq!(data, bar_audusd_sim_minute_bid.as_msgpack_bytes().unwrap());
+    }
+
+    #[rstest]
+    fn test_zmq_frames_round_trip(bar_audusd_sim_minute_bid: Bar) {
+        let frames = bar_audusd_sim_minute_bid.to_zmq_frames();
+
+        assert_eq!(
+            Bar::from_zmq_frames(&frames).unwrap(),
+            bar_audusd_sim_minute_bid
+        );
+    }
+
+    #[rstest]
+    fn test_from_zmq_frames_invalid_frame_count(bar_audusd_sim_minute_bid: Bar) {
+        let frames = bar_audusd_sim_minute_bid.to_zmq_frames();
+
+        assert!(matches!(
+            Bar::from_zmq_frames(&frames[..2]),
+            Err(ZmqError::InvalidFrameCount(2))
+        ));
+    }
+
+    #[rstest]
+    fn test_from_zmq_frames_invalid_frames(bar_audusd_sim_minute_bid: Bar) {
+        let [topic, metadata, data] = bar_audusd_sim_minute_bid.to_zmq_frames();
+
+        let frames = [b"AUDUSD".to_vec(), metadata.clone(), data.clone()];
+        assert!(matches!(
+            Bar::from_zmq_frames(&frames),
+            Err(ZmqError::InvalidTopic(_))
+        ));
+        let frames = [topic.clone(), vec![5], data.clone()];
+        assert!(matches!(
+            Bar::from_zmq_frames(&frames),
+            Err(ZmqError::InvalidMetadata(1))
+        ));
+        let frames = [topic, metadata, b"invalid".to_vec()];
+        assert!(matches!(
+            Bar::from_zmq_frames(&frames),
+            Err(ZmqError::Decode(_))
+        ));
+    }
+
+    #[rstest]
+    fn test_from_zmq_frames_mismatches(bar_audusd_sim_minute_bid: Bar) {
+        let [_, metadata, data] = bar_audusd_sim_minute_bid.to_zmq_frames();
+
+        let frames = [
+            b"AUDUSD.SIM-5-MINUTE-BID-EXTERNAL".to_vec(),
+            metadata,
+            data.clone(),
+        ];
+        assert!(matches!(
+            Bar::from_zmq_frames(&frames),
+            Err(ZmqError::BarTypeMismatch { .. })
+        ));
+        let frames = [
+            b"AUDUSD.SIM-1-MINUTE-BID-EXTERNAL".to_vec(),
+            vec![2, 0],
+            data,
+        ];
+        assert!(matches!(
+            Bar::from_zmq_frames(&frames),
+            Err(ZmqError::PrecisionMismatch { .. })
+        ));
+    }
+}
@@ -130,6 +130,7 @@ impl OrderBookDelta {
                 price,
                 size,
                 order_id,
+                ts_received: 0,
             }
         };
 
@@ -383,7 +384,7 @@ mod tests {
 
         Python::with_gil(|py| {
             let dict_string = delta.as_dict(py).unwrap().to_string();
-            let expected_string = r#"{'type': 'OrderBookDelta', 'instrument_id': 'AAPL.NASDAQ', 'action': 'ADD', 'order': {'side': 'BUY', 'price': '100.00', 'size': '10', 'order_id': 123456}, 'flags': 0, 'sequence': 1, 'ts_event': 1, 'ts_init': 2}"#;
+            let expected_string = r#"{'type': 'OrderBookDelta', 'instrument_id': 'AAPL.NASDAQ', 'action': 'ADD', 'order': {'side': 'BUY', 'price': '100.00', 'size': '10', 'order_id': 123456, 'ts_received': 0}, 'flags': 0, 'sequence': 1, 'ts_event': 1, 'ts_init': 2}"#;
             assert_eq!(dict_string, expected_string);
         });
     }
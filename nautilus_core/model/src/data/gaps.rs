@@ -0,0 +1,419 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use nautilus_core::time::UnixNanos;
+
+use super::bar::Bar;
+use crate::types::{price::Price, quantity::Quantity};
+
+/// The direction of a price gap between two bars.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GapDirection {
+    /// The bar opened above the previous close.
+    Up,
+    /// The bar opened below the previous close.
+    Down,
+}
+
+/// Represents a price gap between the close of a bar and the open of the next.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GapInfo {
+    /// The absolute size of the gap.
+    pub gap_size: Price,
+    /// The absolute size of the gap as a percentage of the previous close.
+    pub gap_percent: f64,
+    /// The direction of the gap.
+    pub gap_direction: GapDirection,
+    /// If the gap exceeds twice the average true range (ATR) of the preceding bars.
+    pub is_runaway: bool,
+}
+
+/// Represents a gap of one or more missing bars in a time based bar series.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BarGap {
+    /// The UNIX timestamp (nanoseconds) of the first missing bar.
+    pub expected_ts: UnixNanos,
+    /// The index of the last bar before the gap.
+    pub after_bar_index: usize,
+    /// The UNIX timestamp (nanoseconds) of the first bar after the gap.
+    pub actual_ts: UnixNanos,
+    /// The bar period (nanoseconds) of the series.
+    pub period_nanos: u64,
+}
+
+impl BarGap {
+    /// Returns the number of consecutive bars missing in the gap.
+    #[must_use]
+    pub fn missing_count(&self) -> usize {
+        ((self.actual_ts - self.expected_ts) / self.period_nanos) as usize
+    }
+}
+
+/// Represents a bar series with its gaps filled by synthetic bars.
+///
+/// The synthetic bars keep the bar type of the series, so they are routed and
+/// aggregated with it, and are instead identified by their indices.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FilledBars {
+    /// The bar series, including the synthetic bars.
+    pub bars: Vec<Bar>,
+    /// The indices of the synthetic bars in `bars`, in ascending order.
+    pub synthetic_indices: Vec<usize>,
+}
+
+impl FilledBars {
+    /// Returns whether the bar at `index` is a synthetic bar.
+    #[must_use]
+    pub fn is_synthetic(&self, index: usize) -> bool {
+        self.synthetic_indices.binary_search(&index).is_ok()
+    }
+
+    /// Returns the synthetic bars of the series.
+    #[must_use]
+    pub fn synthetic(&self) -> Vec<Bar> {
+        self.synthetic_indices
+            .iter()
+            .map(|&i| self.bars[i])
+            .collect()
+    }
+}
+
+/// Returns the gaps in the given time based bar series, where successive bars
+/// are more than one bar period apart.
+///
+/// The period is derived from the bar type specification of the first bar.
+/// Returns an empty vec for non-time based aggregations, or when fewer than
+/// two bars are given.
+#[must_use]
+pub fn detect_bar_gaps(bars: &[Bar]) -> Vec<BarGap> {
+    let Some(period_nanos) = series_period_nanos(bars) else {
+        return Vec::new();
+    };
+
+    bars.windows(2)
+        .enumerate()
+        .filter(|(_, w)| w[1].ts_event > w[0].ts_event + period_nanos)
+        .map(|(i, w)| BarGap {
+            expected_ts: w[0].ts_event + period_nanos,
+            after_bar_index: i,
+            actual_ts: w[1].ts_event,
+            period_nanos,
+        })
+        .collect()
+}
+
+/// Returns the bar series with up to `max_gap` synthetic bars inserted after
+/// the last bar before each gap, carrying its close forward.
+///
+/// Each synthetic bar has `open`, `high`, `low` and `close` equal to the
+/// previous close, zero volume, and the bar type of the series. Non-time based
+/// series are returned unchanged.
+#[must_use]
+pub fn forward_fill(bars: &[Bar], max_gap: usize) -> FilledBars {
+    fill_gaps(bars, max_gap, |gap, prev, _| {
+        (1..=gap.missing_count().min(max_gap) as u64)
+            .map(|i| synthetic_bar(prev, prev.close, prev.ts_event + i * gap.period_nanos))
+            .collect()
+    })
+}
+
+/// Returns the bar series with up to `max_gap` synthetic bars inserted before
+/// the first bar after each gap, carrying its open backward.
+///
+/// Each synthetic bar has `open`, `high`, `low` and `close` equal to the next
+/// open, zero volume, and the bar type of the series. Non-time based series are
+/// returned unchanged.
+#[must_use]
+pub fn back_fill(bars: &[Bar], max_gap: usize) -> FilledBars {
+    fill_gaps(bars, max_gap, |gap, _, next| {
+        (1..=gap.missing_count().min(max_gap) as u64)
+            .rev()
+            .map(|i| synthetic_bar(next, next.open, next.ts_event - i * gap.period_nanos))
+            .collect()
+    })
+}
+
+/// Returns the bar period (nanoseconds) of a time based bar series, derived
+/// from the bar type specification of the first bar.
+fn series_period_nanos(bars: &[Bar]) -> Option<u64> {
+    let spec = bars.first()?.bar_type.spec;
+    spec.aggregation
+        .period_nanos()
+        .map(|nanos| nanos * spec.step as u64)
+        .filter(|&nanos| nanos > 0)
+}
+
+/// Returns the bar series with the bars returned by `fill` for each gap, given
+/// the bars either side of it, inserted into the gap.
+fn fill_gaps(
+    bars: &[Bar],
+    max_gap: usize,
+    fill: impl Fn(&BarGap, &Bar, &Bar) -> Vec<Bar>,
+) -> FilledBars {
+    let gaps = detect_bar_gaps(bars);
+    if gaps.is_empty() || max_gap == 0 {
+        return FilledBars {
+            bars: bars.to_vec(),
+            synthetic_indices: Vec::new(),
+        };
+    }
+    let mut filled = Vec::with_capacity(bars.len() + gaps.len() * max_gap);
+    let mut synthetic_indices = Vec::new();
+    let mut start = 0;
+    for gap in &gaps {
+        let index = gap.after_bar_index;
+        filled.extend_from_slice(&bars[start..=index]);
+        let synthetic = fill(gap, &bars[index], &bars[index + 1]);
+        synthetic_indices.extend(filled.len()..filled.len() + synthetic.len());
+        filled.extend(synthetic);
+        start = index + 1;
+    }
+    filled.extend_from_slice(&bars[start..]);
+    FilledBars {
+        bars: filled,
+        synthetic_indices,
+    }
+}
+
+/// Returns a zero volume synthetic bar of the bar type of `source`, with every
+/// price equal to `price`.
+fn synthetic_bar(source: &Bar, price: Price, ts: UnixNanos) -> Bar {
+    Bar::new(
+        source.bar_type,
+        price,
+        price,
+        price,
+        price,
+        Quantity::from_raw(0, source.volume.precision),
+        ts,
+        ts,
+    )
+}
+
+impl Bar {
+    /// Returns the gap between the close of `prev` and the open of `current`,
+    /// or `None` if `current` opened at the previous close.
+    ///
+    /// The gap is flagged as a runaway gap when it exceeds twice the `atr`, the
+    /// average true range of the bars up to and including `prev`, such as the
+    /// last value of [`Bar::average_true_range`].
+    #[must_use]
+    pub fn overnight_gap(prev: &Bar, current: &Bar, atr: Price) -> Option<GapInfo> {
+        let (gap_size, gap_direction) = match current.open.cmp(&prev.close) {
+            std::cmp::Ordering::Greater => (current.open - prev.close, GapDirection::Up),
+            std::cmp::Ordering::Less => (prev.close - current.open, GapDirection::Down),
+            std::cmp::Ordering::Equal => return None,
+        };
+
+        Some(GapInfo {
+            gap_size,
+            gap_percent: gap_size.as_f64() / prev.close.as_f64() * 100.0,
+            gap_direction,
+            is_runaway: gap_size.as_f64() > 2.0 * atr.as_f64(),
+        })
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{data::bar::stubs::bars_from_closes, enums::BarAggregation};
+
+    const MINUTE: u64 = 60_000_000_000;
+
+    fn create_bars_at_minutes(minutes: &[u64]) -> Vec<Bar> {
+        let mut bars = bars_from_closes(&vec![1.0; minutes.len()]);
+        for (bar, minute) in bars.iter_mut().zip(minutes) {
+            bar.ts_event = minute * MINUTE;
+            bar.ts_init = bar.ts_event;
+        }
+        bars
+    }
+
+    #[rstest]
+    fn test_detect_bar_gaps() {
+        let bars = create_bars_at_minutes(&[0, 1, 2, 4, 5, 6, 8, 9, 10, 11]);
+        let gaps = detect_bar_gaps(&bars);
+
+        assert_eq!(gaps.len(), 2);
+        assert_eq!(gaps[0].after_bar_index, 2);
+        assert_eq!(gaps[0].expected_ts, 3 * MINUTE);
+        assert_eq!(gaps[0].missing_count(), 1);
+        assert_eq!(gaps[1].after_bar_index, 5);
+        assert_eq!(gaps[1].expected_ts, 7 * MINUTE);
+        assert_eq!(gaps[1].missing_count(), 1);
+    }
+
+    #[rstest]
+    fn test_detect_bar_gaps_missing_count_for_consecutive_gaps() {
+        let bars = create_bars_at_minutes(&[0, 1, 5]);
+        let gaps = detect_bar_gaps(&bars);
+
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].expected_ts, 2 * MINUTE);
+        assert_eq!(gaps[0].missing_count(), 3);
+    }
+
+    #[rstest]
+    fn test_detect_bar_gaps_no_gaps() {
+        let bars = create_bars_at_minutes(&[0, 1, 2, 3]);
+
+        assert!(detect_bar_gaps(&bars).is_empty());
+    }
+
+    #[rstest]
+    fn test_detect_bar_gaps_non_time_aggregation() {
+        let mut bars = create_bars_at_minutes(&[0, 1, 5]);
+        for bar in &mut bars {
+            bar.bar_type.spec.aggregation = BarAggregation::Tick;
+        }
+
+        assert!(detect_bar_gaps(&bars).is_empty());
+    }
+
+    #[rstest]
+    fn test_detect_bar_gaps_single_bar() {
+        let bars = create_bars_at_minutes(&[0]);
+
+        assert!(detect_bar_gaps(&bars).is_empty());
+    }
+
+    #[rstest]
+    fn test_forward_fill() {
+        let mut bars = create_bars_at_minutes(&[0, 1, 2, 3, 4, 8, 9, 10, 11, 12]);
+        bars[4].close = Price::from("1.50000");
+        let filled = forward_fill(&bars, 2);
+
+        // The 3 missing bars are capped at `max_gap`
+        assert_eq!(filled.bars.len(), 12);
+        assert_eq!(filled.synthetic_indices, [5, 6]);
+        let synthetic = filled.synthetic();
+        for (bar, minute) in synthetic.iter().zip([5, 6]) {
+            assert_eq!(bar.bar_type, bars[4].bar_type);
+            assert_eq!(bar.ts_event, minute * MINUTE);
+            assert_eq!(bar.open, Price::from("1.50000"));
+            assert_eq!(bar.high, bar.open);
+            assert_eq!(bar.low, bar.open);
+            assert_eq!(bar.close, bar.open);
+            assert!(bar.volume.is_zero());
+        }
+        assert!(!filled.is_synthetic(4));
+        assert!(filled.is_synthetic(5));
+        assert_eq!(filled.bars[7], bars[5]);
+        assert!(!filled.is_synthetic(7));
+    }
+
+    #[rstest]
+    fn test_back_fill() {
+        let mut bars = create_bars_at_minutes(&[0, 1, 2, 3, 4, 8, 9, 10, 11, 12]);
+        bars[5].open = Price::from("2.00000");
+        let filled = back_fill(&bars, 2);
+
+        assert_eq!(filled.bars.len(), 12);
+        assert_eq!(filled.bars[4], bars[4]);
+        assert_eq!(filled.synthetic_indices, [5, 6]);
+        for (bar, minute) in filled.bars[5..7].iter().zip([6, 7]) {
+            assert_eq!(bar.bar_type, bars[5].bar_type);
+            assert_eq!(bar.ts_event, minute * MINUTE);
+            assert_eq!(bar.close, Price::from("2.00000"));
+            assert!(bar.volume.is_zero());
+        }
+        assert_eq!(filled.bars[7], bars[5]);
+    }
+
+    #[rstest]
+    fn test_fill_across_multiple_gaps() {
+        let bars = create_bars_at_minutes(&[0, 2, 3, 6]);
+        let filled = forward_fill(&bars, 5);
+
+        assert_eq!(filled.bars.len(), 7);
+        assert_eq!(filled.synthetic_indices, [1, 4, 5]);
+        assert_eq!(filled.bars[6], bars[3]);
+    }
+
+    #[rstest]
+    fn test_fill_without_gaps_or_max_gap() {
+        let bars = create_bars_at_minutes(&[0, 1, 2, 5]);
+
+        for filled in [
+            forward_fill(&bars[..3], 2),
+            forward_fill(&bars, 0),
+            back_fill(&bars, 0),
+        ] {
+            assert_eq!(filled.bars, bars[..filled.bars.len()]);
+            assert!(filled.synthetic_indices.is_empty());
+        }
+    }
+
+    fn create_daily_bars() -> Vec<Bar> {
+        let mut bars = bars_from_closes(&[100.0, 101.0, 99.0, 100.0, 102.0]);
+        for bar in &mut bars {
+            bar.high = Price::new(bar.close.as_f64() + 1.0, 5).unwrap();
+            bar.low = Price::new(bar.close.as_f64() - 1.0, 5).unwrap();
+        }
+        bars
+    }
+
+    #[rstest]
+    fn test_overnight_gap_up() {
+        let bars = create_daily_bars();
+        // True ranges: 2.0, 2.0, 3.0, 2.0, 3.0
+        let atr = *Bar::average_true_range(&bars, 5).last().unwrap();
+        assert_eq!(atr, Price::from("2.40000"));
+        let mut current = bars[4];
+        current.open = Price::from("107.00000");
+        let gap = Bar::overnight_gap(&bars[4], &current, atr).unwrap();
+
+        assert_eq!(gap.gap_size, Price::from("5.00000"));
+        assert!(approx_eq!(
+            f64,
+            gap.gap_percent,
+            5.0 / 102.0 * 100.0,
+            epsilon = 1e-9
+        ));
+        assert_eq!(gap.gap_direction, GapDirection::Up);
+        assert!(gap.is_runaway);
+    }
+
+    #[rstest]
+    fn test_overnight_gap_down_within_atr() {
+        let bars = create_daily_bars();
+        let atr = *Bar::average_true_range(&bars, 5).last().unwrap();
+        let mut current = bars[4];
+        // Exceeds twice the 2.0 range of the previous bar, but not twice the ATR
+        current.open = Price::from("97.50000");
+        let gap = Bar::overnight_gap(&bars[4], &current, atr).unwrap();
+
+        assert_eq!(gap.gap_size, Price::from("4.50000"));
+        assert_eq!(gap.gap_direction, GapDirection::Down);
+        assert!(!gap.is_runaway);
+    }
+
+    #[rstest]
+    fn test_overnight_gap_none() {
+        let bars = bars_from_closes(&[100.0, 101.0]);
+
+        assert_eq!(
+            Bar::overnight_gap(&bars[0], &bars[1], Price::from("1.00000")),
+            None
+        );
+    }
+}
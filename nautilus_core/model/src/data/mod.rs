@@ -13,12 +13,57 @@
 //  limitations under the License.
 // -------------------------------------------------------------------------------------------------
 
+pub mod aggregation;
 pub mod bar;
 #[cfg(feature = "ffi")]
 pub mod bar_api;
+#[cfg(feature = "avro")]
+pub mod bar_avro;
+#[cfg(feature = "optimize")]
+pub mod bar_bayesian;
+#[cfg(feature = "tokio")]
+pub mod bar_broadcast;
+pub mod bar_checksum;
+#[cfg(feature = "clickhouse")]
+pub mod bar_clickhouse;
+pub mod bar_compact;
+pub mod bar_env;
+#[cfg(feature = "excel")]
+pub mod bar_excel;
+pub mod bar_fixed;
+#[cfg(feature = "optimize")]
+pub mod bar_genetic;
+#[cfg(feature = "grpc")]
+pub mod bar_grpc;
+#[cfg(feature = "kafka")]
+pub mod bar_kafka;
+#[cfg(feature = "mmap")]
+pub mod bar_mmap;
+#[cfg(feature = "monte_carlo")]
+pub mod bar_monte_carlo;
+#[cfg(feature = "nats")]
+pub mod bar_nats;
+#[cfg(feature = "numpy")]
+pub mod bar_numpy;
+pub mod bar_optimize;
+pub mod bar_portfolio;
+#[cfg(feature = "protobuf")]
+pub mod bar_proto;
+#[cfg(feature = "redis")]
+pub mod bar_redis;
+pub mod bar_running;
+pub mod bar_signals;
+pub mod bar_stats;
+#[cfg(feature = "timescaledb")]
+pub mod bar_timescaledb;
+pub mod bar_window;
+pub mod bar_ws;
+#[cfg(feature = "zmq")]
+pub mod bar_zmq;
 pub mod delta;
 #[cfg(feature = "ffi")]
 pub mod delta_api;
+pub mod gaps;
 pub mod order;
 #[cfg(feature = "ffi")]
 pub mod order_api;
@@ -19,7 +19,7 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use nautilus_core::{python::to_pyvalue_err, serialization::Serializable};
+use nautilus_core::{python::to_pyvalue_err, serialization::Serializable, time::UnixNanos};
 use pyo3::{prelude::*, pyclass::CompareOp, types::PyDict};
 use serde::{Deserialize, Serialize};
 
@@ -41,6 +41,7 @@ pub const NULL_ORDER: BookOrder = BookOrder {
         precision: 0,
     },
     order_id: 0,
+    ts_received: 0,
 };
 
 /// Represents an order in a book.
@@ -56,6 +57,9 @@ pub struct BookOrder {
     pub size: Quantity,
     /// The order ID.
     pub order_id: u64,
+    /// The UNIX timestamp (nanoseconds) when the order was last received by the book.
+    #[serde(default)]
+    pub ts_received: UnixNanos,
 }
 
 impl BookOrder {
@@ -66,9 +70,16 @@ impl BookOrder {
             price,
             size,
             order_id,
+            ts_received: 0,
         }
     }
 
+    /// Returns the nanoseconds elapsed since the order was last received, as at `now`.
+    #[must_use]
+    pub fn age(&self, now: UnixNanos) -> u64 {
+        now.saturating_sub(self.ts_received)
+    }
+
     #[must_use]
     pub fn to_book_price(&self) -> BookPrice {
         BookPrice::new(self.price, self.side)
@@ -79,6 +90,18 @@ impl BookOrder {
         self.price.as_f64() * self.size.as_f64()
     }
 
+    /// Returns whether the order rests on the bid side of the book.
+    #[must_use]
+    pub fn is_bid(&self) -> bool {
+        self.side == OrderSide::Buy
+    }
+
+    /// Returns whether the order rests on the ask side of the book.
+    #[must_use]
+    pub fn is_ask(&self) -> bool {
+        self.side == OrderSide::Sell
+    }
+
     #[must_use]
     pub fn signed_size(&self) -> f64 {
         match self.side {
@@ -208,6 +231,16 @@ impl BookOrder {
         self.signed_size()
     }
 
+    #[pyo3(name = "is_bid")]
+    fn py_is_bid(&self) -> bool {
+        self.is_bid()
+    }
+
+    #[pyo3(name = "is_ask")]
+    fn py_is_ask(&self) -> bool {
+        self.is_ask()
+    }
+
     /// Return a dictionary representation of the object.
     pub fn as_dict(&self, py: Python<'_>) -> PyResult<Py<PyDict>> {
         // Serialize object to JSON bytes
@@ -291,6 +324,17 @@ mod tests {
         assert_eq!(order.size, size);
         assert_eq!(order.side, side);
         assert_eq!(order.order_id, order_id);
+        assert_eq!(order.ts_received, 0);
+    }
+
+    #[rstest]
+    fn test_age() {
+        let mut order = create_stub_book_order();
+        order.ts_received = 1_000;
+
+        assert_eq!(order.age(1_000), 0);
+        assert_eq!(order.age(3_500), 2_500);
+        assert_eq!(order.age(500), 0);
     }
 
     #[rstest]
@@ -320,6 +364,17 @@ mod tests {
         assert_eq!(exposure, price.as_f64() * size.as_f64());
     }
 
+    #[rstest]
+    #[case(OrderSide::Buy, true, false)]
+    #[case(OrderSide::Sell, false, true)]
+    #[case(OrderSide::NoOrderSide, false, false)]
+    fn test_is_bid_is_ask(#[case] side: OrderSide, #[case] is_bid: bool, #[case] is_ask: bool) {
+        let order = BookOrder::new(side, Price::from("100.00"), Quantity::from("10"), 123456);
+
+        assert_eq!(order.is_bid(), is_bid);
+        assert_eq!(order.is_ask(), is_ask);
+    }
+
     #[rstest]
     fn test_signed_size() {
         let price = Price::from("100.00");
@@ -423,8 +478,7 @@ mod tests {
 
         Python::with_gil(|py| {
             let dict_string = delta.as_dict(py).unwrap().to_string();
-            let expected_string =
-                r#"{'side': 'BUY', 'price': '100.00', 'size': '10', 'order_id': 123456}"#;
+            let expected_string = r#"{'side': 'BUY', 'price': '100.00', 'size': '10', 'order_id': 123456, 'ts_received': 0}"#;
             assert_eq!(dict_string, expected_string);
         });
     }
@@ -296,6 +296,29 @@ pub enum BarAggregation {
     /// Based on time intervals with month granularity.
     #[pyo3(name = "MONTH")]
     Month = 16,
+    /// Based on the price range reaching a number of price increments.
+    #[pyo3(name = "RANGE")]
+    Range = 17,
+    /// Based on the price moving a number of price increments from the last brick close.
+    #[pyo3(name = "RENKO")]
+    Renko = 18,
+}
+
+impl BarAggregation {
+    /// Returns the duration (nanoseconds) of a single step of the aggregation, or
+    /// `None` if the aggregation is not time based or has no fixed duration.
+    #[must_use]
+    pub fn period_nanos(&self) -> Option<u64> {
+        match self {
+            Self::Millisecond => Some(1_000_000),
+            Self::Second => Some(1_000_000_000),
+            Self::Minute => Some(60_000_000_000),
+            Self::Hour => Some(3_600_000_000_000),
+            Self::Day => Some(86_400_000_000_000),
+            Self::Week => Some(604_800_000_000_000),
+            _ => None,
+        }
+    }
 }
 
 /// The type of order book action for an order book event.
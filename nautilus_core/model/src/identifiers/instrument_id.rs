@@ -14,7 +14,7 @@
 // -------------------------------------------------------------------------------------------------
 
 use std::{
-    collections::hash_map::DefaultHasher,
+    collections::{hash_map::DefaultHasher, HashMap},
     ffi::c_char,
     fmt::{Debug, Display, Formatter},
     hash::{Hash, Hasher},
@@ -22,6 +22,7 @@ use std::{
 };
 
 use anyhow::Result;
+use lazy_static::lazy_static;
 use nautilus_core::string::{cstr_to_string, str_to_cstr};
 use pyo3::prelude::*;
 use serde::{Deserialize, Deserializer, Serialize};
@@ -37,6 +38,29 @@ pub struct InstrumentId {
     pub venue: Venue,
 }
 
+/// The Bloomberg security types (exchange code and yellow key) and the venues
+/// they map to.
+const BLOOMBERG_VENUES: [(&str, &str); 6] = [
+    ("US Equity", "NASDAQ"),
+    ("LN Equity", "LSE"),
+    ("JP Equity", "TSE"),
+    ("Curncy", "FX"),
+    ("Comdty", "CME"),
+    ("Index", "INDEX"),
+];
+
+/// The standard Reuters Instrument Code (RIC) suffixes and the venues they map to.
+const RIC_VENUES: [(&str, &str); 4] = [
+    (".O", "NASDAQ"),
+    (".N", "NYSE"),
+    (".L", "LSE"),
+    ("=X", "FX"),
+];
+
+lazy_static! {
+    static ref RIC_REGISTRY: RicRegistry = RicRegistry::with_standard_suffixes();
+}
+
 #[derive(thiserror::Error, Debug)]
 #[error("Error parsing `InstrumentId` from '{input}'")]
 pub struct InstrumentIdParseError {
@@ -51,6 +75,159 @@ impl InstrumentId {
     pub fn is_synthetic(&self) -> bool {
         self.venue.is_synthetic()
     }
+
+    /// Returns the instrument ID parsed from a Bloomberg ticker, such as
+    /// `AAPL US Equity` or `EURUSD Curncy`.
+    ///
+    /// The security type maps to a venue, such as `US Equity` to `NASDAQ` and
+    /// `Curncy` to `FX`. The security name maps to a normalised symbol, with
+    /// currency pairs as `EUR/USD` and share class separators as `BRK.B`.
+    ///
+    /// # Errors
+    ///
+    /// If the security type is unknown or the security name is invalid.
+    pub fn from_bloomberg_ticker(ticker: &str) -> Result<Self, InstrumentIdParseError> {
+        let error = || InstrumentIdParseError {
+            input: ticker.to_string(),
+        };
+        let (name, security_type) = ticker
+            .trim()
+            .split_once(char::is_whitespace)
+            .ok_or_else(error)?;
+        let security_type = security_type
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let (security_type, venue) = BLOOMBERG_VENUES
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(&security_type))
+            .ok_or_else(error)?;
+
+        let name = name.to_ascii_uppercase();
+        let symbol = match *security_type {
+            "Curncy" if name.len() == 6 && name.is_ascii() => {
+                format!("{}/{}", &name[..3], &name[3..])
+            }
+            _ => name.replace('/', "."),
+        };
+        Ok(Self {
+            symbol: Symbol::new(&symbol).map_err(|_| error())?,
+            venue: Venue::new(venue).map_err(|_| error())?,
+        })
+    }
+
+    /// Returns the Bloomberg ticker of the instrument ID, the reverse of
+    /// [`InstrumentId::from_bloomberg_ticker`].
+    ///
+    /// Returns `None` if the venue is not a known Bloomberg venue.
+    #[must_use]
+    pub fn to_bloomberg_ticker(&self) -> Option<String> {
+        let venue = self.venue.value.as_str();
+        let (security_type, _) = BLOOMBERG_VENUES.iter().find(|(_, v)| *v == venue)?;
+        let symbol = self.symbol.value.as_str();
+        let name = match *security_type {
+            "Curncy" => symbol.replace('/', ""),
+            _ => symbol.replace('.', "/"),
+        };
+        Some(format!("{name} {security_type}"))
+    }
+
+    /// Returns the instrument ID parsed from a Reuters Instrument Code (RIC),
+    /// such as `AAPL.O` or `EURUSD=X`, using the standard exchange suffixes.
+    ///
+    /// # Errors
+    ///
+    /// If the RIC has no known exchange suffix, see [`RicRegistry::parse`].
+    pub fn from_ric(ric: &str) -> Result<Self, InstrumentIdParseError> {
+        RIC_REGISTRY.parse(ric)
+    }
+
+    /// Returns the Reuters Instrument Code (RIC) of the instrument ID, using
+    /// the standard exchange suffixes.
+    ///
+    /// Returns `None` if the venue has no known exchange suffix.
+    #[must_use]
+    pub fn to_ric(&self) -> Option<String> {
+        RIC_REGISTRY.format(self)
+    }
+}
+
+/// Provides mappings between Reuters Instrument Code (RIC) exchange suffixes,
+/// such as `.O` or `=X`, and venues.
+///
+/// The registry of [`RicRegistry::with_standard_suffixes`] contains the standard
+/// suffixes, and any registry can be extended with custom mappings with
+/// [`RicRegistry::register`].
+#[derive(Clone, Debug, Default)]
+pub struct RicRegistry {
+    venues: HashMap<String, Venue>,
+    suffixes: HashMap<Venue, String>,
+}
+
+impl RicRegistry {
+    /// Creates a new registry without any mappings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            venues: HashMap::new(),
+            suffixes: HashMap::new(),
+        }
+    }
+
+    /// Creates a new registry with the standard exchange suffixes.
+    #[must_use]
+    pub fn with_standard_suffixes() -> Self {
+        let mut registry = Self::new();
+        for (suffix, venue) in RIC_VENUES {
+            // Safety: using valid venue constants
+            registry.register(suffix, Venue::new(venue).unwrap());
+        }
+        registry
+    }
+
+    /// Registers the mapping between the exchange `suffix` (including its
+    /// separator, such as `.O`) and the `venue`, replacing any existing mappings
+    /// of either.
+    pub fn register(&mut self, suffix: &str, venue: Venue) {
+        if let Some(old_venue) = self.venues.insert(suffix.to_string(), venue) {
+            self.suffixes.remove(&old_venue);
+        }
+        if let Some(old_suffix) = self.suffixes.insert(venue, suffix.to_string()) {
+            if old_suffix != suffix {
+                self.venues.remove(&old_suffix);
+            }
+        }
+    }
+
+    /// Returns the instrument ID parsed from the `ric`, using the longest
+    /// registered suffix it ends with.
+    ///
+    /// # Errors
+    ///
+    /// If the `ric` has no registered suffix, or its symbol is empty.
+    pub fn parse(&self, ric: &str) -> Result<InstrumentId, InstrumentIdParseError> {
+        let error = || InstrumentIdParseError {
+            input: ric.to_string(),
+        };
+        let (symbol, venue) = self
+            .venues
+            .iter()
+            .filter_map(|(suffix, venue)| Some((ric.strip_suffix(suffix.as_str())?, venue)))
+            .min_by_key(|(symbol, _)| symbol.len())
+            .ok_or_else(error)?;
+        Ok(InstrumentId {
+            symbol: Symbol::new(symbol).map_err(|_| error())?,
+            venue: *venue,
+        })
+    }
+
+    /// Returns the RIC of the `instrument_id`, or `None` if its venue has no
+    /// registered suffix.
+    #[must_use]
+    pub fn format(&self, instrument_id: &InstrumentId) -> Option<String> {
+        let suffix = self.suffixes.get(&instrument_id.venue)?;
+        Some(format!("{}{suffix}", instrument_id.symbol))
+    }
 }
 
 impl FromStr for InstrumentId {
@@ -194,7 +371,7 @@ mod tests {
     use super::InstrumentId;
     use crate::identifiers::{
         instrument_id::{
-            instrument_id_new_from_cstr, instrument_id_to_cstr, InstrumentIdParseError,
+            instrument_id_new_from_cstr, instrument_id_to_cstr, InstrumentIdParseError, RicRegistry,
         },
         symbol::Symbol,
         venue::Venue,
@@ -234,6 +411,112 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case("AAPL US Equity", "AAPL.NASDAQ")]
+    #[case("BRK/B US Equity", "BRK.B.NASDAQ")]
+    #[case("VOD LN Equity", "VOD.LSE")]
+    #[case("EURUSD Curncy", "EUR/USD.FX")]
+    #[case("  eurusd  curncy ", "EUR/USD.FX")]
+    #[case("SPX Index", "SPX.INDEX")]
+    fn test_from_bloomberg_ticker(#[case] ticker: &str, #[case] expected: &str) {
+        let instrument_id = InstrumentId::from_bloomberg_ticker(ticker).unwrap();
+
+        assert_eq!(instrument_id.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("AAPL")]
+    #[case("AAPL XX Equity")]
+    #[case("US0378331005 Corp")]
+    fn test_from_bloomberg_ticker_unknown_security_type(#[case] ticker: &str) {
+        let result = InstrumentId::from_bloomberg_ticker(ticker);
+
+        assert!(matches!(result, Err(InstrumentIdParseError { .. })));
+    }
+
+    #[rstest]
+    #[case("AAPL US Equity")]
+    #[case("BRK/B US Equity")]
+    #[case("EURUSD Curncy")]
+    fn test_bloomberg_ticker_round_trip(#[case] ticker: &str) {
+        let instrument_id = InstrumentId::from_bloomberg_ticker(ticker).unwrap();
+
+        assert_eq!(instrument_id.to_bloomberg_ticker().as_deref(), Some(ticker));
+    }
+
+    #[rstest]
+    fn test_to_bloomberg_ticker_unknown_venue() {
+        let instrument_id = InstrumentId::from("ETH/USDT.BINANCE");
+
+        assert_eq!(instrument_id.to_bloomberg_ticker(), None);
+    }
+
+    #[rstest]
+    fn test_from_ric() {
+        let instrument_id = InstrumentId::from_ric("AAPL.O").unwrap();
+
+        assert_eq!(
+            instrument_id,
+            InstrumentId::new(Symbol::new("AAPL").unwrap(), Venue::new("NASDAQ").unwrap())
+        );
+        assert_eq!(
+            InstrumentId::from_ric("EURUSD=X").unwrap(),
+            InstrumentId::from("EURUSD.FX")
+        );
+    }
+
+    #[rstest]
+    #[case("AAPL.O")]
+    #[case("IBM.N")]
+    #[case("VOD.L")]
+    #[case("EURUSD=X")]
+    fn test_ric_round_trip(#[case] ric: &str) {
+        let instrument_id = InstrumentId::from_ric(ric).unwrap();
+
+        assert_eq!(instrument_id.to_ric().as_deref(), Some(ric));
+    }
+
+    #[rstest]
+    #[case("AAPL")]
+    #[case("AAPL.XX")]
+    #[case(".O")]
+    fn test_from_ric_invalid(#[case] ric: &str) {
+        assert!(matches!(
+            InstrumentId::from_ric(ric),
+            Err(InstrumentIdParseError { .. })
+        ));
+    }
+
+    #[rstest]
+    fn test_to_ric_unknown_venue() {
+        assert_eq!(InstrumentId::from("ETH/USDT.BINANCE").to_ric(), None);
+    }
+
+    #[rstest]
+    fn test_ric_registry_custom_suffix() {
+        let mut registry = RicRegistry::with_standard_suffixes();
+        registry.register(".T", Venue::new("TSE").unwrap());
+        registry.register(".OQ", Venue::new("NASDAQ").unwrap());
+
+        let instrument_id = registry.parse("7203.T").unwrap();
+        assert_eq!(instrument_id, InstrumentId::from("7203.TSE"));
+        assert_eq!(registry.format(&instrument_id).as_deref(), Some("7203.T"));
+        // Registering a venue again replaces its previous suffix
+        let instrument_id = registry.parse("AAPL.OQ").unwrap();
+        assert_eq!(instrument_id, InstrumentId::from("AAPL.NASDAQ"));
+        assert_eq!(registry.format(&instrument_id).as_deref(), Some("AAPL.OQ"));
+        assert!(registry.parse("AAPL.O").is_err());
+    }
+
+    #[rstest]
+    fn test_ric_registry_new_is_empty() {
+        assert!(RicRegistry::new().parse("AAPL.O").is_err());
+        assert!(RicRegistry::default().parse("AAPL.O").is_err());
+        assert!(RicRegistry::with_standard_suffixes()
+            .parse("AAPL.O")
+            .is_ok());
+    }
+
     #[rstest]
     fn test_string_reprs() {
         let id = InstrumentId::from("ETH/USDT.BINANCE");
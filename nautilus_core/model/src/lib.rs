@@ -19,10 +19,13 @@ extern crate lazy_static;
 
 use pyo3::{prelude::*, PyResult, Python};
 
+pub mod analytics;
 pub mod currencies;
 pub mod data;
 pub mod enums;
 pub mod events;
+#[cfg(feature = "protobuf")]
+pub mod generated;
 pub mod identifiers;
 pub mod instruments;
 pub mod macros;
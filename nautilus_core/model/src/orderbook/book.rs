@@ -13,6 +13,9 @@
 //  limitations under the License.
 // -------------------------------------------------------------------------------------------------
 
+use std::collections::HashMap;
+
+use nautilus_core::time::UnixNanos;
 use tabled::{settings::Style, Table, Tabled};
 use thiserror::Error;
 
@@ -41,6 +44,21 @@ pub enum InvalidBookOperation {
     PreProcessOrder(BookType),
     #[error("Invalid book operation: cannot add for {0} book")]
     Add(BookType),
+    #[error("Invalid book operation: snapshot sequence {0} is before book sequence {1}")]
+    StaleSnapshot(u64, u64),
+}
+
+/// Represents a full snapshot of the orders in a book.
+#[derive(Clone, Debug)]
+pub struct OrderBookSnapshot {
+    /// The bid orders in the book.
+    pub bids: Vec<BookOrder>,
+    /// The ask orders in the book.
+    pub asks: Vec<BookOrder>,
+    /// The message sequence number assigned at the venue.
+    pub sequence: u64,
+    /// The UNIX timestamp (nanoseconds) when the snapshot event occurred.
+    pub ts_event: UnixNanos,
 }
 
 #[derive(Error, Debug)]
@@ -100,6 +118,7 @@ impl OrderBook {
         }
 
         self.increment(ts_event, sequence);
+        self.stamp_level(&order);
     }
 
     pub fn update(&mut self, order: BookOrder, ts_event: u64, sequence: u64) {
@@ -119,6 +138,7 @@ impl OrderBook {
         }
 
         self.increment(ts_event, sequence);
+        self.stamp_level(&order);
     }
 
     pub fn delete(&mut self, order: BookOrder, ts_event: u64, sequence: u64) {
@@ -154,14 +174,130 @@ impl OrderBook {
     }
 
     pub fn apply_delta(&mut self, delta: OrderBookDelta) {
+        let mut order = delta.order;
+        order.ts_received = delta.ts_init;
         match delta.action {
-            BookAction::Add => self.add(delta.order, delta.ts_event, delta.sequence),
-            BookAction::Update => self.update(delta.order, delta.ts_event, delta.sequence),
+            BookAction::Add => self.add(order, delta.ts_event, delta.sequence),
+            BookAction::Update => self.update(order, delta.ts_event, delta.sequence),
             BookAction::Delete => self.delete(delta.order, delta.ts_event, delta.sequence),
             BookAction::Clear => self.clear(delta.ts_event, delta.sequence),
         }
     }
 
+    /// Removes all orders from both sides of the book which were last received
+    /// more than `max_age_ns` before `now`.
+    pub fn remove_stale(&mut self, max_age_ns: u64, now: UnixNanos) {
+        self.bids.remove_stale(max_age_ns, now);
+        self.asks.remove_stale(max_age_ns, now);
+    }
+
+    /// Replaces the entire state of the book with the given `snapshot`.
+    ///
+    /// # Panics
+    ///
+    /// If the snapshot sequence is before the current book sequence.
+    pub fn apply_snapshot(&mut self, snapshot: OrderBookSnapshot) {
+        if snapshot.sequence < self.sequence {
+            panic!(
+                "{}",
+                InvalidBookOperation::StaleSnapshot(snapshot.sequence, self.sequence)
+            );
+        }
+
+        self.bids.clear();
+        self.asks.clear();
+        for order in snapshot.bids.into_iter().chain(snapshot.asks) {
+            let order = match self.book_type {
+                BookType::L3_MBO => order, // No order pre-processing
+                BookType::L2_MBP | BookType::L1_TBBO => self.pre_process_order(order),
+            };
+
+            match order.side {
+                OrderSide::Buy => self.bids.add(order),
+                OrderSide::Sell => self.asks.add(order),
+                _ => panic!("{}", BookIntegrityError::NoOrderSide),
+            }
+        }
+
+        self.increment(snapshot.ts_event, snapshot.sequence);
+        let update_id = self.count;
+        for level in self
+            .bids
+            .levels
+            .values_mut()
+            .chain(self.asks.levels.values_mut())
+        {
+            level.update_id = update_id;
+        }
+    }
+
+    /// Returns the deltas which transform this book into the `other` book.
+    ///
+    /// Orders missing from `other` are deleted, orders with a changed price or
+    /// size are updated and orders new in `other` are added, in that order.
+    /// Deltas are sequenced consecutively from the current book sequence, and
+    /// are timestamped with the last event time of `other`.
+    #[must_use]
+    pub fn diff(&self, other: &OrderBook) -> Vec<OrderBookDelta> {
+        // A L1_TBBO book only accepts updates
+        let add_action = match self.book_type {
+            BookType::L1_TBBO => BookAction::Update,
+            _ => BookAction::Add,
+        };
+
+        let mut deletes = Vec::new();
+        let mut updates = Vec::new();
+        let mut adds = Vec::new();
+        for (ladder, other_ladder) in [(&self.bids, &other.bids), (&self.asks, &other.asks)] {
+            let orders: HashMap<u64, &BookOrder> = ladder
+                .levels
+                .values()
+                .flat_map(|level| &level.orders)
+                .map(|order| (order.order_id, order))
+                .collect();
+            let other_orders: HashMap<u64, &BookOrder> = other_ladder
+                .levels
+                .values()
+                .flat_map(|level| &level.orders)
+                .map(|order| (order.order_id, order))
+                .collect();
+
+            for order in ladder.levels.values().flat_map(|level| &level.orders) {
+                if !other_orders.contains_key(&order.order_id) {
+                    deletes.push((BookAction::Delete, *order));
+                }
+            }
+            for other_order in other_ladder.levels.values().flat_map(|level| &level.orders) {
+                match orders.get(&other_order.order_id) {
+                    Some(order) => {
+                        if order.price != other_order.price || order.size != other_order.size {
+                            updates.push((BookAction::Update, *other_order));
+                        }
+                    }
+                    None => adds.push((add_action, *other_order)),
+                }
+            }
+        }
+
+        deletes
+            .into_iter()
+            .chain(updates)
+            .chain(adds)
+            .zip(self.sequence + 1..)
+            .map(|((action, order), sequence)| {
+                OrderBookDelta::new(
+                    self.instrument_id,
+                    action,
+                    order,
+                    0,
+                    sequence,
+                    other.ts_last,
+                    other.ts_last,
+                )
+            })
+            .collect()
+    }
+
     pub fn bids(&self) -> Vec<&Level> {
         self.bids.levels.values().collect()
     }
@@ -184,6 +320,36 @@ impl OrderBook {
         }
     }
 
+    /// Returns the number of bid price levels, in O(1).
+    #[must_use]
+    pub fn bid_levels(&self) -> usize {
+        self.bids.len()
+    }
+
+    /// Returns the number of ask price levels, in O(1).
+    #[must_use]
+    pub fn ask_levels(&self) -> usize {
+        self.asks.len()
+    }
+
+    /// Returns whether the book has orders on both the bid and ask sides.
+    #[must_use]
+    pub fn has_liquidity(&self) -> bool {
+        self.has_bid() && self.has_ask()
+    }
+
+    /// Returns the total quantity of all bid levels, in O(n) of the bid orders.
+    #[must_use]
+    pub fn total_bid_qty(&self) -> f64 {
+        self.bids.volumes()
+    }
+
+    /// Returns the total quantity of all ask levels, in O(n) of the ask orders.
+    #[must_use]
+    pub fn total_ask_qty(&self) -> f64 {
+        self.asks.volumes()
+    }
+
     pub fn best_bid_price(&self) -> Option<Price> {
         self.bids.top().map(|top| top.price.value)
     }
@@ -206,6 +372,36 @@ impl OrderBook {
         }
     }
 
+    /// Returns whether the best bid price is at or above the best ask price.
+    #[must_use]
+    pub fn is_crossed(&self) -> bool {
+        match (self.best_bid_price(), self.best_ask_price()) {
+            (Some(bid), Some(ask)) => bid >= ask,
+            _ => false,
+        }
+    }
+
+    /// Removes top levels until the book is no longer crossed, returning the
+    /// removed levels in removal order.
+    ///
+    /// Of the crossed best bid and best ask, the level updated more recently by
+    /// `update_id` is removed, with the bid removed when both were updated together.
+    pub fn uncross(&mut self) -> Vec<Level> {
+        let mut removed = Vec::new();
+        while self.is_crossed() {
+            // Unwrapping is safe as a crossed book has both a bid and an ask
+            let bid_id = self.bids.top().unwrap().update_id;
+            let ask_id = self.asks.top().unwrap().update_id;
+            let ladder = if ask_id > bid_id {
+                &mut self.asks
+            } else {
+                &mut self.bids
+            };
+            removed.extend(ladder.remove_top());
+        }
+        removed
+    }
+
     pub fn spread(&self) -> Option<f64> {
         match (self.best_ask_price(), self.best_bid_price()) {
             (Some(ask), Some(bid)) => Some(ask.as_f64() - bid.as_f64()),
@@ -246,6 +442,42 @@ impl OrderBook {
         }
     }
 
+    /// Returns the simulated (price, filled quantity) fills of a market buy
+    /// order for `qty`, walking up the ask levels from the best ask without
+    /// mutating the book.
+    ///
+    /// The fills total less than `qty` if the order exhausts the asks, and are
+    /// empty if there are no asks or `qty` is not positive.
+    #[must_use]
+    pub fn simulate_market_buy(&self, qty: f64) -> Vec<(f64, f64)> {
+        simulate_market_fills(&self.asks, qty)
+    }
+
+    /// Returns the simulated (price, filled quantity) fills of a market sell
+    /// order for `qty`, walking down the bid levels from the best bid without
+    /// mutating the book.
+    ///
+    /// The fills total less than `qty` if the order exhausts the bids, and are
+    /// empty if there are no bids or `qty` is not positive.
+    #[must_use]
+    pub fn simulate_market_sell(&self, qty: f64) -> Vec<(f64, f64)> {
+        simulate_market_fills(&self.bids, qty)
+    }
+
+    /// Returns the volume weighted average price of the simulated fills of a
+    /// market buy order for `qty`, or `None` if there would be no fills.
+    #[must_use]
+    pub fn simulate_market_buy_vwap(&self, qty: f64) -> Option<f64> {
+        fills_vwap(&self.simulate_market_buy(qty))
+    }
+
+    /// Returns the volume weighted average price of the simulated fills of a
+    /// market sell order for `qty`, or `None` if there would be no fills.
+    #[must_use]
+    pub fn simulate_market_sell_vwap(&self, qty: f64) -> Option<f64> {
+        fills_vwap(&self.simulate_market_sell(qty))
+    }
+
     pub fn update_quote_tick(&mut self, tick: &QuoteTick) {
         self.update_bid(BookOrder::from_quote_tick(tick, OrderSide::Buy));
         self.update_ask(BookOrder::from_quote_tick(tick, OrderSide::Sell));
@@ -378,6 +610,18 @@ impl OrderBook {
         Ok(())
     }
 
+    fn stamp_level(&mut self, order: &BookOrder) {
+        let ladder = match order.side {
+            OrderSide::Buy => &mut self.bids,
+            OrderSide::Sell => &mut self.asks,
+            _ => return,
+        };
+        // The level will not exist if the update deleted its last order
+        if let Some(level) = ladder.levels.get_mut(&order.to_book_price()) {
+            level.update_id = self.count;
+        }
+    }
+
     fn increment(&mut self, ts_event: u64, sequence: u64) {
         self.ts_last = ts_event;
         self.sequence = sequence;
@@ -461,6 +705,34 @@ impl OrderBook {
     }
 }
 
+/// Returns the (price, filled quantity) fills of `qty` against the levels of
+/// the `ladder`, from the top of the book.
+fn simulate_market_fills(ladder: &Ladder, qty: f64) -> Vec<(f64, f64)> {
+    let mut fills = Vec::new();
+    let mut remaining = qty;
+    for (book_price, level) in &ladder.levels {
+        if remaining <= 0.0 {
+            break;
+        }
+        let filled = level.volume().min(remaining);
+        if filled > 0.0 {
+            fills.push((book_price.value.as_f64(), filled));
+            remaining -= filled;
+        }
+    }
+    fills
+}
+
+/// Returns the volume weighted average price of the `fills`, or `None` if
+/// there are none.
+fn fills_vwap(fills: &[(f64, f64)]) -> Option<f64> {
+    let volume: f64 = fills.iter().map(|(_, qty)| qty).sum();
+    if volume <= 0.0 {
+        return None;
+    }
+    Some(fills.iter().map(|(price, qty)| price * qty).sum::<f64>() / volume)
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Tests
 ////////////////////////////////////////////////////////////////////////////////
@@ -550,6 +822,90 @@ mod tests {
         assert_eq!(book.best_ask_size(), Some(Quantity::from("2.0")));
         assert_eq!(book.has_ask(), true);
     }
+
+    #[rstest]
+    fn test_level_counts_and_liquidity_empty_book() {
+        let book = create_stub_book(BookType::L2_MBP);
+
+        assert_eq!(book.bid_levels(), 0);
+        assert_eq!(book.ask_levels(), 0);
+        assert!(!book.has_liquidity());
+        assert_eq!(book.total_bid_qty(), 0.0);
+        assert_eq!(book.total_ask_qty(), 0.0);
+    }
+
+    #[rstest]
+    fn test_level_counts_and_liquidity_one_sided_book() {
+        let mut book = create_stub_book(BookType::L3_MBO);
+        book.add(
+            BookOrder::new(
+                OrderSide::Buy,
+                Price::from("1.000"),
+                Quantity::from("1.0"),
+                1,
+            ),
+            100,
+            1,
+        );
+        book.add(
+            BookOrder::new(
+                OrderSide::Buy,
+                Price::from("1.000"),
+                Quantity::from("2.0"),
+                2,
+            ),
+            100,
+            2,
+        );
+        book.add(
+            BookOrder::new(
+                OrderSide::Buy,
+                Price::from("0.990"),
+                Quantity::from("3.0"),
+                3,
+            ),
+            100,
+            3,
+        );
+
+        assert_eq!(book.bid_levels(), 2);
+        assert_eq!(book.ask_levels(), 0);
+        assert!(!book.has_liquidity());
+        assert_eq!(book.total_bid_qty(), 6.0);
+        assert_eq!(book.total_ask_qty(), 0.0);
+    }
+
+    #[rstest]
+    fn test_level_counts_and_liquidity_two_sided_book() {
+        let mut book = create_stub_book(BookType::L2_MBP);
+        book.add(
+            BookOrder::new(
+                OrderSide::Buy,
+                Price::from("1.000"),
+                Quantity::from("1.0"),
+                1,
+            ),
+            100,
+            1,
+        );
+        book.add(
+            BookOrder::new(
+                OrderSide::Sell,
+                Price::from("2.000"),
+                Quantity::from("2.0"),
+                2,
+            ),
+            100,
+            2,
+        );
+
+        assert_eq!(book.bid_levels(), 1);
+        assert_eq!(book.ask_levels(), 1);
+        assert!(book.has_liquidity());
+        assert_eq!(book.total_bid_qty(), 1.0);
+        assert_eq!(book.total_ask_qty(), 2.0);
+    }
+
     #[rstest]
     fn test_spread_with_no_bids_or_asks() {
         let book = create_stub_book(BookType::L3_MBO);
@@ -712,6 +1068,481 @@ mod tests {
         assert_eq!(book.best_ask_size().unwrap(), size);
     }
 
+    fn receive(
+        book: &mut OrderBook,
+        action: BookAction,
+        order: BookOrder,
+        ts_event: u64,
+        ts_init: u64,
+    ) {
+        let delta = OrderBookDelta::new(
+            book.instrument_id,
+            action,
+            order,
+            0,
+            book.sequence + 1,
+            ts_event,
+            ts_init,
+        );
+        book.apply_delta(delta);
+    }
+
+    #[rstest]
+    fn test_apply_delta_stamps_receipt_time() {
+        let mut book = create_stub_book(BookType::L3_MBO);
+        let order = BookOrder::new(
+            OrderSide::Buy,
+            Price::from("1.000"),
+            Quantity::from("1.0"),
+            1,
+        );
+        receive(&mut book, BookAction::Add, order, 100, 1_000);
+
+        assert_eq!(book.bids()[0].orders[0].ts_received, 1_000);
+        assert_eq!(book.bids()[0].orders[0].age(1_500), 500);
+    }
+
+    #[rstest]
+    fn test_remove_stale_after_feed_freeze() {
+        let mut book = create_stub_book(BookType::L3_MBO);
+        let bid1 = BookOrder::new(
+            OrderSide::Buy,
+            Price::from("1.000"),
+            Quantity::from("1.0"),
+            1,
+        );
+        let bid2 = BookOrder::new(
+            OrderSide::Buy,
+            Price::from("1.000"),
+            Quantity::from("2.0"),
+            2,
+        );
+        let bid3 = BookOrder::new(
+            OrderSide::Buy,
+            Price::from("0.990"),
+            Quantity::from("2.0"),
+            3,
+        );
+        let ask1 = BookOrder::new(
+            OrderSide::Sell,
+            Price::from("1.010"),
+            Quantity::from("1.0"),
+            4,
+        );
+        let ask2 = BookOrder::new(
+            OrderSide::Sell,
+            Price::from("1.020"),
+            Quantity::from("2.0"),
+            5,
+        );
+        for order in [bid1, bid2, bid3, ask1, ask2] {
+            receive(&mut book, BookAction::Add, order, 900, 1_000);
+        }
+
+        // Only the top orders keep updating before the feed freezes, with
+        // event times lagging the receipt times
+        let bid1 = BookOrder::new(
+            OrderSide::Buy,
+            Price::from("1.000"),
+            Quantity::from("3.0"),
+            1,
+        );
+        let ask1 = BookOrder::new(
+            OrderSide::Sell,
+            Price::from("1.010"),
+            Quantity::from("3.0"),
+            4,
+        );
+        receive(&mut book, BookAction::Update, bid1, 1_000, 5_000);
+        receive(&mut book, BookAction::Update, ask1, 1_000, 5_000);
+
+        book.remove_stale(2_000, 6_000);
+
+        assert_eq!(book.bids().len(), 1);
+        assert_eq!(book.bids()[0].orders, vec![bid1]);
+        assert_eq!(book.asks().len(), 1);
+        assert_eq!(book.best_bid_size(), Some(Quantity::from("3.0")));
+        assert_eq!(book.best_ask_size(), Some(Quantity::from("3.0")));
+
+        // The feed stays frozen until the top orders are stale too
+        book.remove_stale(2_000, 7_000);
+        assert_eq!(book.bids().len(), 1);
+
+        book.remove_stale(2_000, 7_001);
+        assert!(!book.has_bid());
+        assert!(!book.has_ask());
+    }
+
+    #[rstest]
+    fn test_remove_stale_then_feed_resumes() {
+        let mut book = create_stub_book(BookType::L2_MBP);
+        let bid = BookOrder::new(
+            OrderSide::Buy,
+            Price::from("1.000"),
+            Quantity::from("1.0"),
+            1,
+        );
+        receive(&mut book, BookAction::Add, bid, 1_000, 1_000);
+
+        book.remove_stale(500, 2_000);
+        assert!(!book.has_bid());
+
+        // A stale order re-added by the resumed feed is tracked afresh
+        receive(&mut book, BookAction::Add, bid, 3_000, 3_000);
+        book.remove_stale(500, 3_200);
+
+        assert_eq!(book.best_bid_price(), Some(Price::from("1.000")));
+        assert_eq!(book.bids()[0].orders[0].ts_received, 3_000);
+    }
+
+    fn add_levels(book: &mut OrderBook, side: OrderSide, prices: &[&str], ts_received: u64) {
+        for price in prices {
+            let mut order = BookOrder::new(side, Price::from(*price), Quantity::from("1.0"), 0);
+            order.ts_received = ts_received;
+            book.add(order, ts_received, book.sequence + 1);
+        }
+    }
+
+    /// Returns an L2 book with 5 ask levels from 100 and 5 bid levels from 99,
+    /// with sizes of 1 to 5 moving away from the top of the book.
+    fn create_five_level_book() -> OrderBook {
+        let mut book = create_stub_book(BookType::L2_MBP);
+        for i in 0..5 {
+            let size = Quantity::new(f64::from(i + 1), 0).unwrap();
+            let ask = Price::new(100.0 + f64::from(i), 2).unwrap();
+            let bid = Price::new(99.0 - f64::from(i), 2).unwrap();
+            book.add(
+                BookOrder::new(OrderSide::Sell, ask, size, 0),
+                0,
+                book.sequence + 1,
+            );
+            book.add(
+                BookOrder::new(OrderSide::Buy, bid, size, 0),
+                0,
+                book.sequence + 1,
+            );
+        }
+        book
+    }
+
+    #[rstest]
+    fn test_simulate_market_buy_partial_level() {
+        let book = create_five_level_book();
+
+        assert_eq!(
+            book.simulate_market_buy(4.5),
+            vec![(100.0, 1.0), (101.0, 2.0), (102.0, 1.5)]
+        );
+        assert_eq!(book.simulate_market_buy_vwap(4.5), Some(455.0 / 4.5));
+    }
+
+    #[rstest]
+    fn test_simulate_market_buy_exhausts_liquidity() {
+        let book = create_five_level_book();
+        let fills = book.simulate_market_buy(20.0);
+
+        assert_eq!(
+            fills,
+            vec![
+                (100.0, 1.0),
+                (101.0, 2.0),
+                (102.0, 3.0),
+                (103.0, 4.0),
+                (104.0, 5.0)
+            ]
+        );
+        assert_eq!(fills.iter().map(|(_, qty)| qty).sum::<f64>(), 15.0);
+        assert_eq!(book.simulate_market_buy_vwap(20.0), Some(1_540.0 / 15.0));
+        // The simulation does not consume the book
+        assert_eq!(book.ask_levels(), 5);
+        assert_eq!(book.total_ask_qty(), 15.0);
+    }
+
+    #[rstest]
+    fn test_simulate_market_sell() {
+        let book = create_five_level_book();
+
+        assert_eq!(
+            book.simulate_market_sell(3.0),
+            vec![(99.0, 1.0), (98.0, 2.0)]
+        );
+        assert_eq!(book.simulate_market_sell_vwap(3.0), Some(295.0 / 3.0));
+    }
+
+    #[rstest]
+    fn test_simulate_market_orders_with_empty_book() {
+        let book = create_stub_book(BookType::L2_MBP);
+
+        assert!(book.simulate_market_buy(1.0).is_empty());
+        assert!(book.simulate_market_sell(1.0).is_empty());
+        assert_eq!(book.simulate_market_buy_vwap(1.0), None);
+        assert_eq!(book.simulate_market_sell_vwap(1.0), None);
+        assert!(create_five_level_book().simulate_market_buy(0.0).is_empty());
+    }
+
+    #[rstest]
+    fn test_is_crossed() {
+        let mut book = create_stub_book(BookType::L2_MBP);
+        assert!(!book.is_crossed());
+
+        add_levels(&mut book, OrderSide::Buy, &["1.000"], 1_000);
+        add_levels(&mut book, OrderSide::Sell, &["1.010"], 1_000);
+        assert!(!book.is_crossed());
+
+        add_levels(&mut book, OrderSide::Sell, &["1.000"], 1_000);
+        assert!(book.is_crossed());
+    }
+
+    #[rstest]
+    fn test_uncross_removes_newer_levels() {
+        let mut book = create_stub_book(BookType::L2_MBP);
+        add_levels(
+            &mut book,
+            OrderSide::Buy,
+            &["1.000", "0.990", "0.980"],
+            1_000,
+        );
+        add_levels(&mut book, OrderSide::Sell, &["1.010", "1.020"], 1_000);
+        add_levels(
+            &mut book,
+            OrderSide::Sell,
+            &["0.985", "0.995", "1.000"],
+            2_000,
+        );
+        assert!(book.is_crossed());
+
+        let removed = book.uncross();
+
+        assert!(!book.is_crossed());
+        let removed_prices: Vec<Price> = removed.iter().map(|level| level.price.value).collect();
+        assert_eq!(
+            removed_prices,
+            [
+                Price::from("0.985"),
+                Price::from("0.995"),
+                Price::from("1.000")
+            ]
+        );
+        assert_eq!(book.bids().len(), 3);
+        assert_eq!(book.best_ask_price(), Some(Price::from("1.010")));
+        assert!(book.check_integrity().is_ok());
+    }
+
+    #[rstest]
+    fn test_uncross_removes_newer_bids() {
+        let mut book = create_stub_book(BookType::L2_MBP);
+        add_levels(&mut book, OrderSide::Sell, &["1.005", "1.030"], 1_000);
+        add_levels(
+            &mut book,
+            OrderSide::Buy,
+            &["1.020", "1.010", "1.000"],
+            1_000,
+        );
+
+        let removed = book.uncross();
+
+        assert!(!book.is_crossed());
+        assert_eq!(removed.len(), 2);
+        assert_eq!(book.best_bid_price(), Some(Price::from("1.000")));
+        assert_eq!(book.best_ask_price(), Some(Price::from("1.005")));
+        assert!(book.uncross().is_empty());
+    }
+
+    #[rstest]
+    fn test_uncross_uses_update_order_over_receipt_time() {
+        let mut book = create_stub_book(BookType::L2_MBP);
+        add_levels(&mut book, OrderSide::Buy, &["1.000", "0.990"], 2_000);
+        // Updated after the bids, though stamped with an earlier receipt time
+        add_levels(&mut book, OrderSide::Sell, &["0.995", "1.010"], 1_000);
+        assert!(book.is_crossed());
+
+        let removed = book.uncross();
+
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].price.value, Price::from("0.995"));
+        assert_eq!(book.bids().len(), 2);
+        assert_eq!(book.best_ask_price(), Some(Price::from("1.010")));
+    }
+
+    #[rstest]
+    fn test_uncross_removes_bids_when_updated_together() {
+        let mut book = create_stub_book(BookType::L2_MBP);
+        let snapshot = OrderBookSnapshot {
+            bids: vec![BookOrder::new(
+                OrderSide::Buy,
+                Price::from("1.010"),
+                Quantity::from("1.0"),
+                0,
+            )],
+            asks: vec![BookOrder::new(
+                OrderSide::Sell,
+                Price::from("1.000"),
+                Quantity::from("1.0"),
+                0,
+            )],
+            sequence: 1,
+            ts_event: 1_000,
+        };
+        book.apply_snapshot(snapshot);
+
+        let removed = book.uncross();
+
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].price.side, OrderSide::Buy);
+        assert!(!book.has_bid());
+        assert_eq!(book.best_ask_price(), Some(Price::from("1.000")));
+    }
+
+    fn book_state(book: &OrderBook) -> Vec<(OrderSide, Price, Quantity)> {
+        book.bids()
+            .into_iter()
+            .chain(book.asks())
+            .flat_map(|level| &level.orders)
+            .map(|order| (order.side, order.price, order.size))
+            .collect()
+    }
+
+    #[rstest]
+    fn test_apply_snapshot() {
+        let mut book = create_stub_book(BookType::L2_MBP);
+        add_levels(&mut book, OrderSide::Buy, &["0.990", "0.980"], 1_000);
+        add_levels(&mut book, OrderSide::Sell, &["1.010"], 1_000);
+
+        let snapshot = OrderBookSnapshot {
+            bids: vec![BookOrder::new(
+                OrderSide::Buy,
+                Price::from("1.000"),
+                Quantity::from("5.0"),
+                0,
+            )],
+            asks: vec![
+                BookOrder::new(
+                    OrderSide::Sell,
+                    Price::from("1.020"),
+                    Quantity::from("1.0"),
+                    0,
+                ),
+                BookOrder::new(
+                    OrderSide::Sell,
+                    Price::from("1.030"),
+                    Quantity::from("2.0"),
+                    0,
+                ),
+            ],
+            sequence: 10,
+            ts_event: 2_000,
+        };
+        book.apply_snapshot(snapshot);
+
+        assert_eq!(book.sequence, 10);
+        assert_eq!(book.ts_last, 2_000);
+        assert_eq!(book.bids().len(), 1);
+        assert_eq!(book.asks().len(), 2);
+        assert_eq!(book.best_bid_price(), Some(Price::from("1.000")));
+        assert_eq!(book.best_bid_size(), Some(Quantity::from("5.0")));
+        assert_eq!(book.best_ask_price(), Some(Price::from("1.020")));
+    }
+
+    #[rstest]
+    #[should_panic(expected = "snapshot sequence 1 is before book sequence 5")]
+    fn test_apply_snapshot_with_stale_sequence() {
+        let mut book = create_stub_book(BookType::L2_MBP);
+        book.sequence = 5;
+
+        book.apply_snapshot(OrderBookSnapshot {
+            bids: vec![],
+            asks: vec![],
+            sequence: 1,
+            ts_event: 0,
+        });
+    }
+
+    #[rstest]
+    #[case(BookType::L2_MBP)]
+    #[case(BookType::L3_MBO)]
+    fn test_diff_restores_pre_snapshot_state(#[case] book_type: BookType) {
+        let mut original = create_stub_book(book_type);
+        let orders = [
+            BookOrder::new(
+                OrderSide::Buy,
+                Price::from("1.000"),
+                Quantity::from("1.0"),
+                1,
+            ),
+            BookOrder::new(
+                OrderSide::Buy,
+                Price::from("0.990"),
+                Quantity::from("2.0"),
+                2,
+            ),
+            BookOrder::new(
+                OrderSide::Buy,
+                Price::from("0.985"),
+                Quantity::from("3.0"),
+                3,
+            ),
+            BookOrder::new(
+                OrderSide::Sell,
+                Price::from("1.010"),
+                Quantity::from("1.0"),
+                4,
+            ),
+            BookOrder::new(
+                OrderSide::Sell,
+                Price::from("1.020"),
+                Quantity::from("2.0"),
+                5,
+            ),
+        ];
+        for (i, order) in orders.iter().enumerate() {
+            original.add(*order, 1_000, i as u64 + 1);
+        }
+
+        let mut book = create_stub_book(book_type);
+        for (i, order) in orders.iter().enumerate() {
+            book.add(*order, 1_000, i as u64 + 1);
+        }
+        book.apply_snapshot(OrderBookSnapshot {
+            bids: vec![
+                BookOrder::new(
+                    OrderSide::Buy,
+                    Price::from("1.000"),
+                    Quantity::from("4.0"),
+                    1,
+                ),
+                BookOrder::new(
+                    OrderSide::Buy,
+                    Price::from("0.980"),
+                    Quantity::from("1.0"),
+                    6,
+                ),
+            ],
+            asks: vec![BookOrder::new(
+                OrderSide::Sell,
+                Price::from("1.010"),
+                Quantity::from("1.0"),
+                4,
+            )],
+            sequence: 10,
+            ts_event: 2_000,
+        });
+        assert_ne!(book_state(&book), book_state(&original));
+
+        let deltas = book.diff(&original);
+
+        assert!(!deltas.is_empty());
+        let sequences: Vec<u64> = deltas.iter().map(|delta| delta.sequence).collect();
+        assert_eq!(
+            sequences,
+            (11..11 + deltas.len() as u64).collect::<Vec<u64>>()
+        );
+        for delta in deltas {
+            book.apply_delta(delta);
+        }
+        assert_eq!(book_state(&book), book_state(&original));
+        assert!(book.diff(&original).is_empty());
+    }
+
     #[rstest]
     fn test_pprint() {
         let mut book = create_stub_book(BookType::L3_MBO);
@@ -172,6 +172,31 @@ pub extern "C" fn orderbook_has_ask(book: &mut OrderBook_API) -> u8 {
     book.has_ask() as u8
 }
 
+#[no_mangle]
+pub extern "C" fn orderbook_bid_levels(book: &OrderBook_API) -> usize {
+    book.bid_levels()
+}
+
+#[no_mangle]
+pub extern "C" fn orderbook_ask_levels(book: &OrderBook_API) -> usize {
+    book.ask_levels()
+}
+
+#[no_mangle]
+pub extern "C" fn orderbook_has_liquidity(book: &OrderBook_API) -> u8 {
+    u8::from(book.has_liquidity())
+}
+
+#[no_mangle]
+pub extern "C" fn orderbook_total_bid_qty(book: &OrderBook_API) -> f64 {
+    book.total_bid_qty()
+}
+
+#[no_mangle]
+pub extern "C" fn orderbook_total_ask_qty(book: &OrderBook_API) -> f64 {
+    book.total_ask_qty()
+}
+
 #[no_mangle]
 pub extern "C" fn orderbook_best_bid_price(book: &mut OrderBook_API) -> Price {
     book.best_bid_price()
@@ -19,6 +19,8 @@ use std::{
     fmt::{Display, Formatter},
 };
 
+use nautilus_core::time::UnixNanos;
+
 use super::book::BookIntegrityError;
 use crate::{
     data::order::BookOrder,
@@ -107,14 +109,13 @@ impl Ladder {
 
     pub fn add(&mut self, order: BookOrder) {
         let book_price = order.to_book_price();
+        self.cache.insert(order.order_id, book_price);
         match self.levels.get_mut(&book_price) {
             Some(level) => {
                 level.add(order);
             }
             None => {
-                let order_id = order.order_id;
                 let level = Level::from_order(order);
-                self.cache.insert(order_id, book_price);
                 self.levels.insert(book_price, level);
             }
         }
@@ -155,6 +156,31 @@ impl Ladder {
         }
     }
 
+    /// Removes and returns the top level, if any.
+    pub fn remove_top(&mut self) -> Option<Level> {
+        let (_, level) = self.levels.pop_first()?;
+        for order in &level.orders {
+            self.cache.remove(&order.order_id);
+        }
+        Some(level)
+    }
+
+    /// Removes all orders last received more than `max_age_ns` before `now`,
+    /// along with any levels left empty.
+    pub fn remove_stale(&mut self, max_age_ns: u64, now: UnixNanos) {
+        let cache = &mut self.cache;
+        self.levels.retain(|_, level| {
+            level.orders.retain(|order| {
+                let is_stale = order.age(now) > max_age_ns;
+                if is_stale {
+                    cache.remove(&order.order_id);
+                }
+                !is_stale
+            });
+            !level.is_empty()
+        });
+    }
+
     #[must_use]
     pub fn volumes(&self) -> f64 {
         return self.levels.values().map(|l| l.volume()).sum();
@@ -413,6 +439,7 @@ mod tests {
             size: Quantity::from(500),
             side,
             order_id: 2,
+            ts_received: 0,
         };
 
         let fills = ladder.simulate_fills(&order);
@@ -435,6 +462,7 @@ mod tests {
             size: Quantity::from(100),
             side: ladder_side,
             order_id: 1,
+            ts_received: 0,
         });
 
         let order = BookOrder {
@@ -442,6 +470,7 @@ mod tests {
             size: Quantity::from(500),
             side: order_side,
             order_id: 2,
+            ts_received: 0,
         };
 
         let fills = ladder.simulate_fills(&order);
@@ -458,6 +487,7 @@ mod tests {
             size: Quantity::from(100),
             side: OrderSide::Buy,
             order_id: 1,
+            ts_received: 0,
         });
 
         let order = BookOrder {
@@ -465,6 +495,7 @@ mod tests {
             size: Quantity::from(500),
             side: OrderSide::Buy,
             order_id: 2,
+            ts_received: 0,
         };
 
         let fills = ladder.simulate_fills(&order);
@@ -482,18 +513,21 @@ mod tests {
                 size: Quantity::from(100),
                 side: OrderSide::Sell,
                 order_id: 1,
+                ts_received: 0,
             },
             BookOrder {
                 price: Price::from("101.00"),
                 size: Quantity::from(200),
                 side: OrderSide::Sell,
                 order_id: 2,
+                ts_received: 0,
             },
             BookOrder {
                 price: Price::from("102.00"),
                 size: Quantity::from(400),
                 side: OrderSide::Sell,
                 order_id: 3,
+                ts_received: 0,
             },
         ]);
 
@@ -502,6 +536,7 @@ mod tests {
             size: Quantity::from(500),
             side: OrderSide::Buy,
             order_id: 4,
+            ts_received: 0,
         };
 
         let fills = ladder.simulate_fills(&order);
@@ -531,18 +566,21 @@ mod tests {
                 size: Quantity::from(100),
                 side: OrderSide::Buy,
                 order_id: 1,
+                ts_received: 0,
             },
             BookOrder {
                 price: Price::from("101.00"),
                 size: Quantity::from(200),
                 side: OrderSide::Buy,
                 order_id: 2,
+                ts_received: 0,
             },
             BookOrder {
                 price: Price::from("100.00"),
                 size: Quantity::from(400),
                 side: OrderSide::Buy,
                 order_id: 3,
+                ts_received: 0,
             },
         ]);
 
@@ -551,6 +589,7 @@ mod tests {
             size: Quantity::from(500),
             side: OrderSide::Sell,
             order_id: 4,
+            ts_received: 0,
         };
 
         let fills = ladder.simulate_fills(&order);
@@ -580,18 +619,21 @@ mod tests {
                 size: Quantity::from("100.000000000"),
                 side: OrderSide::Buy,
                 order_id: 1,
+                ts_received: 0,
             },
             BookOrder {
                 price: Price::from("101.00"),
                 size: Quantity::from("200.000000000"),
                 side: OrderSide::Buy,
                 order_id: 2,
+                ts_received: 0,
             },
             BookOrder {
                 price: Price::from("100.00"),
                 size: Quantity::from("400.000000000"),
                 side: OrderSide::Buy,
                 order_id: 3,
+                ts_received: 0,
             },
         ]);
 
@@ -600,6 +642,7 @@ mod tests {
             size: Quantity::from("699.999999999"), // <-- Size slightly less than total size in ladder
             side: OrderSide::Sell,
             order_id: 4,
+            ts_received: 0,
         };
 
         let fills = ladder.simulate_fills(&order);
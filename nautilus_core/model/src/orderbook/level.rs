@@ -25,6 +25,8 @@ use crate::{
 pub struct Level {
     pub price: BookPrice,
     pub orders: Vec<BookOrder>,
+    /// The book update count when the level was last added to or updated.
+    pub update_id: u64,
 }
 
 impl Level {
@@ -33,6 +35,7 @@ impl Level {
         Self {
             price,
             orders: Vec::new(),
+            update_id: 0,
         }
     }
 
@@ -41,6 +44,7 @@ impl Level {
         let mut level = Self {
             price: order.to_book_price(),
             orders: Vec::new(),
+            update_id: 0,
         };
         level.add(order);
         level
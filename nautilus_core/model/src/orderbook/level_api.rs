@@ -61,7 +61,9 @@ pub extern "C" fn level_new(order_side: OrderSide, price: Price, orders: CVec) -
         value: price,
         side: order_side,
     };
-    Level_API::new(Level { price, orders })
+    let mut level = Level::new(price);
+    level.orders = orders;
+    Level_API::new(level)
 }
 
 #[no_mangle]
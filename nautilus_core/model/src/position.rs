@@ -36,7 +36,7 @@ use crate::{
 ///
 /// The position ID may be assigned at the trading venue, or can be system
 /// generated depending on a strategies OMS (Order Management System) settings.
-struct Position {
+pub struct Position {
     events: Vec<OrderFilled>,
     client_order_ids: Vec<ClientOrderId>,
     venue_order_ids: Vec<VenueOrderId>,
@@ -197,6 +197,71 @@ impl PartialEq<Self> for Position {
 
 impl Eq for Position {}
 
+////////////////////////////////////////////////////////////////////////////////
+// Stubs
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+pub mod stubs {
+    use std::collections::HashMap;
+
+    use super::Position;
+    use crate::{
+        enums::{OrderSide, PositionSide},
+        identifiers::{
+            account_id::AccountId, client_order_id::ClientOrderId, instrument_id::InstrumentId,
+            position_id::PositionId, strategy_id::StrategyId, trader_id::TraderId,
+        },
+        types::{currency::Currency, quantity::Quantity},
+    };
+
+    /// Returns an open AUD/USD.SIM position of `signed_qty` at `avg_px_open`.
+    pub fn position_audusd(signed_qty: f64, avg_px_open: f64) -> Position {
+        let quantity = Quantity::new(signed_qty.abs(), 0).unwrap();
+        let (entry, side) = if signed_qty < 0.0 {
+            (OrderSide::Sell, PositionSide::Short)
+        } else {
+            (OrderSide::Buy, PositionSide::Long)
+        };
+        Position {
+            events: Vec::new(),
+            client_order_ids: Vec::new(),
+            venue_order_ids: Vec::new(),
+            trade_ids: Vec::new(),
+            buy_qty: Quantity::zero(0),
+            sell_qty: Quantity::zero(0),
+            commissions: HashMap::new(),
+            trader_id: TraderId::default(),
+            strategy_id: StrategyId::default(),
+            instrument_id: InstrumentId::from("AUD/USD.SIM"),
+            id: PositionId::from("P-1"),
+            account_id: AccountId::default(),
+            opening_order_id: ClientOrderId::default(),
+            closing_order_id: None,
+            entry,
+            side,
+            signed_qty,
+            quantity,
+            peak_qty: quantity,
+            price_precision: 5,
+            size_precision: 0,
+            multiplier: Quantity::new(1.0, 0).unwrap(),
+            is_inverse: false,
+            base_currency: Some(Currency::from("AUD")),
+            quote_currency: Currency::from("USD"),
+            settlement_currency: Currency::from("USD"),
+            ts_init: 0,
+            ts_opened: 0,
+            ts_last: 0,
+            ts_closed: None,
+            duration_ns: None,
+            avg_px_open,
+            avg_px_close: None,
+            realized_return: None,
+            realized_pnl: None,
+        }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Tests
 ////////////////////////////////////////////////////////////////////////////////
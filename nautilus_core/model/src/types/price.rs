@@ -48,6 +48,22 @@ pub const ERROR_PRICE: Price = Price {
     precision: 0,
 };
 
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum PriceError {
+    #[error("Invalid `tick_size`, was zero")]
+    ZeroTickSize,
+    #[error("Price difference {difference} was not a multiple of `tick_size` {tick_size}")]
+    NotTickMultiple { difference: Price, tick_size: Price },
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum PriceParseError {
+    #[error("Invalid separators, the grouping and decimal separators were both '{0}'")]
+    SameSeparators(char),
+    #[error("Invalid price string '{input}': {reason}")]
+    InvalidInput { input: String, reason: String },
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Eq, Default)]
 #[cfg_attr(
@@ -116,6 +132,128 @@ impl Price {
         let rescaled_raw = self.raw / i64::pow(10, (FIXED_PRECISION - self.precision) as u32);
         Decimal::from_i128_with_scale(rescaled_raw as i128, self.precision as u32)
     }
+
+    /// Returns the number of `tick_size` increments from `other` to this price,
+    /// which is negative if this price is below `other`.
+    ///
+    /// # Errors
+    ///
+    /// If `tick_size` is zero, or the difference between the prices is not a
+    /// whole number of ticks.
+    pub fn tick_count(&self, other: Price, tick_size: Price) -> Result<i64, PriceError> {
+        if tick_size.is_zero() {
+            return Err(PriceError::ZeroTickSize);
+        }
+        let difference = self.raw - other.raw;
+        if difference % tick_size.raw != 0 {
+            return Err(PriceError::NotTickMultiple {
+                difference: Price::from_raw(difference, self.precision.max(other.precision)),
+                tick_size,
+            });
+        }
+        Ok(difference / tick_size.raw)
+    }
+
+    /// Returns the price formatted for fixed-width columns, with the integer
+    /// part (including any `-` sign) left-padded with spaces to
+    /// `integer_digits` characters, and `decimal_digits` decimal places.
+    ///
+    /// The price is rounded half away from zero if it has more than
+    /// `decimal_digits` decimal places, and an integer part wider than
+    /// `integer_digits` is not truncated.
+    #[must_use]
+    pub fn as_fixed_str(&self, integer_digits: usize, decimal_digits: usize) -> String {
+        self.format_fixed(integer_digits, decimal_digits, false)
+    }
+
+    /// Returns the price formatted as for [`Price::as_fixed_str`], but always
+    /// signed with a `+` or `-`, where zero is signed with `+`.
+    #[must_use]
+    pub fn as_sign_padded_str(&self, integer_digits: usize, decimal_digits: usize) -> String {
+        self.format_fixed(integer_digits, decimal_digits, true)
+    }
+
+    fn format_fixed(&self, integer_digits: usize, decimal_digits: usize, signed: bool) -> String {
+        let rounded = self.as_decimal().round_dp_with_strategy(
+            decimal_digits as u32,
+            RoundingStrategy::MidpointAwayFromZero,
+        );
+        let sign = if rounded.is_sign_negative() && !rounded.is_zero() {
+            "-"
+        } else if signed {
+            "+"
+        } else {
+            ""
+        };
+        let digits = format!("{:.*}", decimal_digits, rounded.abs());
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, format!(".{fraction}")),
+            None => (digits.as_str(), String::new()),
+        };
+        format!("{:>integer_digits$}{fraction}", format!("{sign}{integer}"))
+    }
+
+    /// Returns the price parsed from a string using `decimal_sep` as the
+    /// decimal separator, such as `"1,00001"` with a `','` separator.
+    ///
+    /// # Errors
+    ///
+    /// If the normalized string is not a valid price.
+    pub fn from_locale_str(s: &str, decimal_sep: char) -> Result<Self, PriceParseError> {
+        let normalized: String = s
+            .trim()
+            .chars()
+            .map(|c| if c == decimal_sep { '.' } else { c })
+            .collect();
+        Self::parse_normalized(s, &normalized)
+    }
+
+    /// Returns the price parsed from a formatted string with `grouping_sep`
+    /// thousands separators and `decimal_sep` as the decimal separator, such
+    /// as `"1.234,56"` with `'.'` and `','` separators.
+    ///
+    /// The grouping separators are stripped wherever they occur, and the
+    /// precision is taken from the digits after the decimal separator.
+    ///
+    /// # Errors
+    ///
+    /// If the separators are the same, or the normalized string is not a
+    /// valid price.
+    pub fn from_formatted_str(
+        s: &str,
+        grouping_sep: char,
+        decimal_sep: char,
+    ) -> Result<Self, PriceParseError> {
+        if grouping_sep == decimal_sep {
+            return Err(PriceParseError::SameSeparators(decimal_sep));
+        }
+        let normalized: String = s
+            .trim()
+            .chars()
+            .filter(|c| *c != grouping_sep)
+            .map(|c| if c == decimal_sep { '.' } else { c })
+            .collect();
+        Self::parse_normalized(s, &normalized)
+    }
+
+    fn parse_normalized(input: &str, normalized: &str) -> Result<Self, PriceParseError> {
+        Self::from_str(normalized).map_err(|reason| PriceParseError::InvalidInput {
+            input: input.to_string(),
+            reason,
+        })
+    }
+
+    /// Returns the number of whole `tick_size` increments from zero to this
+    /// price, truncated toward zero.
+    ///
+    /// # Panics
+    ///
+    /// If `tick_size` is zero.
+    #[must_use]
+    pub fn ticks_from_zero(&self, tick_size: Price) -> i64 {
+        assert!(!tick_size.is_zero(), "`tick_size` was zero");
+        self.raw / tick_size.raw
+    }
 }
 
 impl FromStr for Price {
@@ -286,8 +424,8 @@ impl<'de> Deserialize<'de> for Price {
     where
         D: Deserializer<'de>,
     {
-        let price_str: &str = Deserialize::deserialize(_deserializer)?;
-        let price: Price = price_str.into();
+        let price_str: String = Deserialize::deserialize(_deserializer)?;
+        let price: Price = price_str.as_str().into();
         Ok(price)
     }
 }
@@ -688,6 +826,149 @@ mod tests {
 
     use super::*;
 
+    #[rstest]
+    #[case("1.10010", "1.10000", 10)]
+    #[case("1.10000", "1.10010", -10)]
+    #[case("1.10000", "1.10000", 0)]
+    fn test_tick_count(#[case] price: &str, #[case] other: &str, #[case] expected: i64) {
+        let tick_size = Price::from("0.00001");
+        let result = Price::from(price).tick_count(Price::from(other), tick_size);
+
+        assert_eq!(result, Ok(expected));
+    }
+
+    #[rstest]
+    fn test_tick_count_invalid_tick_size() {
+        let price = Price::from("1.10010");
+        let other = Price::from("1.10000");
+
+        assert_eq!(
+            price.tick_count(other, Price::from("0.00000")),
+            Err(PriceError::ZeroTickSize)
+        );
+        assert_eq!(
+            price.tick_count(other, Price::from("0.00003")),
+            Err(PriceError::NotTickMultiple {
+                difference: Price::from("0.00010"),
+                tick_size: Price::from("0.00003"),
+            })
+        );
+    }
+
+    #[rstest]
+    fn test_ticks_from_zero() {
+        let tick_size = Price::from("0.00001");
+
+        assert_eq!(Price::from("1.10010").ticks_from_zero(tick_size), 110_010);
+        assert_eq!(Price::from("-0.00005").ticks_from_zero(tick_size), -5);
+        assert_eq!(
+            Price::from("1.10010").ticks_from_zero(Price::from("0.0001")),
+            11_001
+        );
+    }
+
+    #[rstest]
+    #[should_panic(expected = "`tick_size` was zero")]
+    fn test_ticks_from_zero_zero_tick_size() {
+        let _ = Price::from("1.10010").ticks_from_zero(Price::from("0.00000"));
+    }
+
+    #[rstest]
+    #[case("1.5", 4, 5, "   1.50000")]
+    #[case("-10.1", 4, 5, " -10.10000")]
+    #[case("12345.5", 4, 1, "12345.5")]
+    #[case("1.123456", 2, 3, " 1.123")]
+    #[case("1.0005", 2, 3, " 1.001")]
+    #[case("-0.0001", 3, 2, "  0.00")]
+    #[case("7", 3, 0, "  7")]
+    fn test_as_fixed_str(
+        #[case] price: &str,
+        #[case] integer_digits: usize,
+        #[case] decimal_digits: usize,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            Price::from(price).as_fixed_str(integer_digits, decimal_digits),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("1.5", 4, 5, "  +1.50000")]
+    #[case("-10.1", 4, 5, " -10.10000")]
+    #[case("0", 3, 2, " +0.00")]
+    fn test_as_sign_padded_str(
+        #[case] price: &str,
+        #[case] integer_digits: usize,
+        #[case] decimal_digits: usize,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(
+            Price::from(price).as_sign_padded_str(integer_digits, decimal_digits),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("1,00001", ',', "1.00001")]
+    #[case("1.00001", '.', "1.00001")]
+    #[case(" -0,5 ", ',', "-0.5")]
+    fn test_from_locale_str(
+        #[case] input: &str,
+        #[case] decimal_sep: char,
+        #[case] expected: &str,
+    ) {
+        let price = Price::from_locale_str(input, decimal_sep).unwrap();
+
+        assert_eq!(price, Price::from(expected));
+        assert_eq!(price.precision, Price::from(expected).precision);
+    }
+
+    #[rstest]
+    fn test_from_locale_str_invalid() {
+        assert!(matches!(
+            Price::from_locale_str("1.234,56", ','),
+            Err(PriceParseError::InvalidInput { .. })
+        ));
+    }
+
+    #[rstest]
+    #[case("1.234,56", '.', ',', "1234.56")] // German
+    #[case("1'234.56", '\'', '.', "1234.56")] // Swiss
+    #[case("1,234.56", ',', '.', "1234.56")] // US
+    #[case("1,234,567.891", ',', '.', "1234567.891")]
+    #[case("-1.234,56", '.', ',', "-1234.56")]
+    #[case("-1'234.56", '\'', '.', "-1234.56")]
+    #[case("-1,234.56", ',', '.', "-1234.56")]
+    #[case("12,50", '.', ',', "12.50")]
+    fn test_from_formatted_str(
+        #[case] input: &str,
+        #[case] grouping_sep: char,
+        #[case] decimal_sep: char,
+        #[case] expected: &str,
+    ) {
+        let price = Price::from_formatted_str(input, grouping_sep, decimal_sep).unwrap();
+
+        assert_eq!(price, Price::from(expected));
+        assert_eq!(price.precision, Price::from(expected).precision);
+    }
+
+    #[rstest]
+    fn test_from_formatted_str_invalid() {
+        assert_eq!(
+            Price::from_formatted_str("1,234", ',', ','),
+            Err(PriceParseError::SameSeparators(','))
+        );
+        assert!(matches!(
+            Price::from_formatted_str("1,234.5.6", ',', '.'),
+            Err(PriceParseError::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            Price::from_formatted_str("abc", ',', '.'),
+            Err(PriceParseError::InvalidInput { .. })
+        ));
+    }
+
     #[rstest]
     #[should_panic(expected = "Condition failed: `precision` was greater than the maximum ")]
     fn test_invalid_precision_new() {
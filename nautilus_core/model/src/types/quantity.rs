@@ -43,6 +43,9 @@ use crate::types::fixed::{f64_to_fixed_u64, fixed_u64_to_f64};
 pub const QUANTITY_MAX: f64 = 18_446_744_073.0;
 pub const QUANTITY_MIN: f64 = 0.0;
 
+/// The SI suffixes of successive powers of 1,000 for human readable quantities.
+const SI_SUFFIXES: [&str; 3] = ["K", "M", "B"];
+
 #[repr(C)]
 #[derive(Copy, Clone, Eq, Default)]
 #[cfg_attr(
@@ -103,6 +106,63 @@ impl Quantity {
         let rescaled_raw = self.raw / u64::pow(10, (FIXED_PRECISION - self.precision) as u32);
         Decimal::from_i128_with_scale(rescaled_raw as i128, self.precision as u32)
     }
+
+    /// Returns the quantity formatted for display with an SI suffix (`K`, `M`
+    /// or `B`) and rounded to 3 significant figures, such as `1.50M` for
+    /// 1,500,000.
+    ///
+    /// Quantities below 1,000 are formatted as is, at their precision.
+    #[must_use]
+    pub fn as_human_str(&self) -> String {
+        let thousand = Decimal::from(1_000);
+        let value = self.as_decimal();
+        if value < thousand {
+            return self.to_string();
+        }
+
+        let mut index = 0;
+        let mut scaled = value / thousand;
+        while scaled >= thousand && index < SI_SUFFIXES.len() - 1 {
+            scaled /= thousand;
+            index += 1;
+        }
+        let (mut rounded, mut dp) = round_significant(scaled);
+        // Rounding up may carry into the next suffix, such as 999,950 to 1.00M
+        if rounded >= thousand && index < SI_SUFFIXES.len() - 1 {
+            (rounded, dp) = round_significant(scaled / thousand);
+            index += 1;
+        }
+        format!("{rounded:.dp$}{}", SI_SUFFIXES[index])
+    }
+
+    /// Returns the quantity formatted as for [`Quantity::as_human_str`],
+    /// followed by a space and the `unit`.
+    #[must_use]
+    pub fn as_human_str_with_unit(&self, unit: &str) -> String {
+        format!("{} {unit}", self.as_human_str())
+    }
+}
+
+/// Returns the `value` (at least 1) rounded half away from zero to 3
+/// significant figures, and the number of decimal places to display.
+fn round_significant(value: Decimal) -> (Decimal, usize) {
+    let decimal_places = |value: Decimal| {
+        if value >= Decimal::from(100) {
+            0
+        } else if value >= Decimal::from(10) {
+            1
+        } else {
+            2
+        }
+    };
+    let mut dp = decimal_places(value);
+    let mut rounded = value.round_dp_with_strategy(dp, RoundingStrategy::MidpointAwayFromZero);
+    // Rounding up may add an integer digit, such as 9.995 to 10.0
+    if decimal_places(rounded) != dp {
+        dp = decimal_places(rounded);
+        rounded = value.round_dp_with_strategy(dp, RoundingStrategy::MidpointAwayFromZero);
+    }
+    (rounded, dp as usize)
 }
 
 impl From<Quantity> for f64 {
@@ -283,8 +343,8 @@ impl<'de> Deserialize<'de> for Quantity {
     where
         D: Deserializer<'de>,
     {
-        let qty_str: &str = Deserialize::deserialize(_deserializer)?;
-        let qty: Quantity = qty_str.into();
+        let qty_str: String = Deserialize::deserialize(_deserializer)?;
+        let qty: Quantity = qty_str.as_str().into();
         Ok(qty)
     }
 }
@@ -582,6 +642,16 @@ impl Quantity {
         format!("Quantity('{self:?}')")
     }
 
+    fn __format__(&self, py: Python, format_spec: &str) -> PyResult<String> {
+        match format_spec {
+            "h" => Ok(self.as_human_str()),
+            "" => Ok(self.to_string()),
+            _ => PyModule::import(py, "builtins")?
+                .call_method1("format", (self.as_decimal(), format_spec))?
+                .extract(),
+        }
+    }
+
     #[getter]
     fn raw(&self) -> u64 {
         self.raw
@@ -883,6 +953,34 @@ mod tests {
         assert!(Quantity::new(0.9, 1).unwrap() <= Quantity::new(1.0, 1).unwrap());
     }
 
+    #[rstest]
+    #[case("999", "999")]
+    #[case("0.5", "0.5")]
+    #[case("1000", "1.00K")]
+    #[case("50000", "50.0K")]
+    #[case("9995", "10.0K")]
+    #[case("123456.789", "123K")]
+    #[case("999950", "1.00M")]
+    #[case("1500000", "1.50M")]
+    #[case("100000000", "100M")]
+    #[case("1000000000", "1.00B")]
+    #[case("18000000000", "18.0B")]
+    fn test_as_human_str(#[case] input: &str, #[case] expected: &str) {
+        let qty = Quantity::from(input);
+
+        assert_eq!(qty.as_human_str(), expected);
+        // Display formatting does not change the stored value
+        assert_eq!(qty, Quantity::from(input));
+    }
+
+    #[rstest]
+    fn test_as_human_str_with_unit() {
+        assert_eq!(
+            Quantity::from("1500000").as_human_str_with_unit("BTC"),
+            "1.50M BTC"
+        );
+    }
+
     #[rstest]
     fn test_display() {
         use std::fmt::Write as FmtWrite;
@@ -16,6 +16,8 @@
 pub mod arrow;
 pub mod backend;
 mod kmerge_batch;
+#[cfg(feature = "parquet")]
+pub mod parquet;
 pub mod wranglers;
 
 use pyo3::prelude::*;
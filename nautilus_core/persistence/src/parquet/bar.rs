@@ -0,0 +1,349 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    path::{Path, PathBuf},
+};
+
+use datafusion::parquet::{
+    arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter},
+    file::{properties::WriterProperties, statistics::Statistics},
+};
+use nautilus_core::{datetime::unix_nanos_to_iso8601, time::UnixNanos};
+use nautilus_model::data::bar::{Bar, BarBatchValidator, BarType};
+
+use super::ParquetStorageError;
+use crate::arrow::{DecodeFromRecordBatch, EncodeToRecordBatch};
+
+/// The index of the `ts_event` column in the [`Bar`] Arrow schema.
+const TS_EVENT_COLUMN: usize = 5;
+
+/// The default maximum number of rows per Parquet row group.
+pub const DEFAULT_ROW_GROUP_SIZE: usize = 10_000;
+
+/// Returns the partition directory name for the given `bar_type`.
+///
+/// Any byte other than an ASCII alphanumeric, `-`, `.` or `_` is percent-encoded,
+/// so a symbol such as `ETH/USDT` cannot nest or escape the partition directory.
+#[must_use]
+pub fn bar_type_dir_name(bar_type: &BarType) -> String {
+    let mut name = String::new();
+    for byte in bar_type.to_string().bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_') {
+            name.push(char::from(byte));
+        } else {
+            name.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    name
+}
+
+/// Provides a writer of [`Bar`] slices to Parquet files partitioned by bar type
+/// and date, with the layout `{root}/{bar_type}/{YYYY-MM-DD}.parquet` where the
+/// bar type directory is named by [`bar_type_dir_name`].
+pub struct BarParquetWriter {
+    root: PathBuf,
+    row_group_size: usize,
+}
+
+impl BarParquetWriter {
+    #[must_use]
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            row_group_size: DEFAULT_ROW_GROUP_SIZE,
+        }
+    }
+
+    /// Sets the maximum number of rows per row group, smaller row groups allow
+    /// finer grained predicate pushdown at the cost of file size.
+    #[must_use]
+    pub fn with_row_group_size(mut self, row_group_size: usize) -> Self {
+        self.row_group_size = row_group_size;
+        self
+    }
+
+    /// Writes the given `bars` to one file per bar type and `ts_event` date,
+    /// replacing any existing files, and returns the paths written.
+    ///
+    /// The price and size precisions written to the file metadata are taken
+    /// from the first bar of each partition. Nothing is written unless the
+    /// bars of each bar type are in non-decreasing `ts_event` order, with any
+    /// out of order index relative to the bars of that bar type.
+    pub fn write(&self, bars: &[Bar]) -> Result<Vec<PathBuf>, ParquetStorageError> {
+        let mut by_bar_type: BTreeMap<String, Vec<Bar>> = BTreeMap::new();
+        for bar in bars {
+            by_bar_type
+                .entry(bar_type_dir_name(&bar.bar_type))
+                .or_default()
+                .push(*bar);
+        }
+
+        let mut partitions: BTreeMap<(String, String), Vec<Bar>> = BTreeMap::new();
+        for (bar_type, bars) in by_bar_type {
+            BarBatchValidator::validate(&bars)?;
+            for bar in bars {
+                let date = unix_nanos_to_iso8601(bar.ts_event)[..10].to_string();
+                partitions
+                    .entry((bar_type.clone(), date))
+                    .or_default()
+                    .push(bar);
+            }
+        }
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(self.row_group_size)
+            .build();
+
+        let mut paths = Vec::with_capacity(partitions.len());
+        for ((bar_type, date), bars) in partitions {
+            let first = bars[0];
+            let metadata = Bar::get_metadata(
+                &first.bar_type,
+                first.open.precision,
+                first.volume.precision,
+            );
+            let batch = Bar::encode_batch(&metadata, &bars);
+
+            let dir = self.root.join(bar_type);
+            fs::create_dir_all(&dir)?;
+            let path = dir.join(format!("{date}.parquet"));
+            let file = File::create(&path)?;
+            let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(props.clone()))?;
+            writer.write(&batch)?;
+            writer.close()?;
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+}
+
+/// Provides a reader of [`Bar`]s from Parquet files written by [`BarParquetWriter`].
+pub struct BarParquetReader;
+
+impl BarParquetReader {
+    /// Reads all bars from the Parquet file at `path`.
+    pub fn read_file(path: &Path) -> Result<Vec<Bar>, ParquetStorageError> {
+        Self::read(path, None, UnixNanos::MIN, UnixNanos::MAX)
+    }
+
+    /// Reads the bars of `bar_type` from the Parquet file at `path` with a
+    /// `ts_event` in the inclusive range [`start`, `end`].
+    ///
+    /// Row groups whose `ts_event` statistics fall outside the range are
+    /// skipped without being decoded.
+    pub fn read_filtered(
+        path: &Path,
+        bar_type: &BarType,
+        start: UnixNanos,
+        end: UnixNanos,
+    ) -> Result<Vec<Bar>, ParquetStorageError> {
+        Self::read(path, Some(bar_type), start, end)
+    }
+
+    fn read(
+        path: &Path,
+        bar_type: Option<&BarType>,
+        start: UnixNanos,
+        end: UnixNanos,
+    ) -> Result<Vec<Bar>, ParquetStorageError> {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?;
+        let metadata = builder.schema().metadata().clone();
+        for key in ["bar_type", "price_precision", "size_precision"] {
+            if !metadata.contains_key(key) {
+                return Err(ParquetStorageError::MissingMetadata(key.to_string()));
+            }
+        }
+
+        if let Some(bar_type) = bar_type {
+            if metadata["bar_type"] != bar_type.to_string() {
+                return Ok(Vec::new());
+            }
+        }
+
+        // Predicate pushdown: only decode row groups which can overlap the range
+        let row_groups: Vec<usize> = builder
+            .metadata()
+            .row_groups()
+            .iter()
+            .enumerate()
+            .filter(
+                |(_, row_group)| match row_group.column(TS_EVENT_COLUMN).statistics() {
+                    Some(Statistics::Int64(stats)) if stats.has_min_max_set() => {
+                        *stats.max() as UnixNanos >= start && *stats.min() as UnixNanos <= end
+                    }
+                    _ => true,
+                },
+            )
+            .map(|(i, _)| i)
+            .collect();
+
+        let reader = builder.with_row_groups(row_groups).build()?;
+        let mut bars = Vec::new();
+        for batch in reader {
+            bars.extend(
+                Bar::decode_batch(&metadata, batch?)
+                    .into_iter()
+                    .filter(|bar| (start..=end).contains(&bar.ts_event)),
+            );
+        }
+        Ok(bars)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use nautilus_model::{
+        data::bar::BarBatchError,
+        types::{price::Price, quantity::Quantity},
+    };
+    use rstest::rstest;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const MINUTE: u64 = 60_000_000_000;
+    const DAY: u64 = 1_440 * MINUTE;
+
+    fn create_bars(bar_types: &[BarType], count: usize) -> Vec<Bar> {
+        (0..count)
+            .map(|i| {
+                let bar_type = bar_types[i % bar_types.len()];
+                let ts = (i / bar_types.len()) as u64 * MINUTE;
+                let price = Price::from_raw(1_000_000_000 + (i % 1_000) as i64 * 10_000, 5);
+                Bar::new(
+                    bar_type,
+                    price,
+                    price,
+                    price,
+                    price,
+                    Quantity::from(100),
+                    ts,
+                    ts,
+                )
+            })
+            .collect()
+    }
+
+    #[rstest]
+    fn test_write_and_read_filtered() {
+        let bar_types: Vec<BarType> = [
+            "AUDUSD.SIM-1-MINUTE-BID-EXTERNAL",
+            "GBPUSD.SIM-1-MINUTE-BID-EXTERNAL",
+            "EURUSD.SIM-1-MINUTE-BID-EXTERNAL",
+        ]
+        .iter()
+        .map(|s| BarType::from_str(s).unwrap())
+        .collect();
+        let bars = create_bars(&bar_types, 100_000);
+        let temp_dir = TempDir::new().unwrap();
+        let writer = BarParquetWriter::new(temp_dir.path().to_path_buf()).with_row_group_size(500);
+        let paths = writer.write(&bars).unwrap();
+
+        // 33_334 one-minute bars per bar type spans 24 days
+        assert_eq!(paths.len(), 3 * 24);
+
+        let bar_type = bar_types[1];
+        let path = temp_dir
+            .path()
+            .join(bar_type_dir_name(&bar_type))
+            .join("1970-01-02.parquet");
+        let all = BarParquetReader::read_file(&path).unwrap();
+        assert_eq!(all.len(), 1_440);
+        assert!(all.iter().all(|bar| bar.bar_type == bar_type));
+
+        let start = DAY + 100 * MINUTE;
+        let end = DAY + 899 * MINUTE;
+        let filtered = BarParquetReader::read_filtered(&path, &bar_type, start, end).unwrap();
+        let expected: Vec<Bar> = bars
+            .iter()
+            .filter(|bar| bar.bar_type == bar_type && (start..=end).contains(&bar.ts_event))
+            .copied()
+            .collect();
+        assert_eq!(filtered.len(), 800);
+        assert_eq!(filtered, expected);
+    }
+
+    #[rstest]
+    fn test_write_out_of_order_bars() {
+        let bar_type = BarType::from_str("AUDUSD.SIM-1-MINUTE-BID-EXTERNAL").unwrap();
+        let mut bars = create_bars(&[bar_type], 10);
+        bars.swap(4, 5);
+        let temp_dir = TempDir::new().unwrap();
+        let result = BarParquetWriter::new(temp_dir.path().to_path_buf()).write(&bars);
+
+        assert!(matches!(
+            result,
+            Err(ParquetStorageError::InvalidBatch(
+                BarBatchError::OutOfOrder { index: 5, .. }
+            ))
+        ));
+        assert!(!temp_dir.path().join(bar_type_dir_name(&bar_type)).exists());
+    }
+
+    #[rstest]
+    fn test_write_validates_each_bar_type() {
+        let bar_type = BarType::from_str("AUDUSD.SIM-1-MINUTE-BID-EXTERNAL").unwrap();
+        let other = BarType::from_str("GBPUSD.SIM-1-MINUTE-BID-EXTERNAL").unwrap();
+        // Each bar type is in order although the batch as a whole is not
+        let mut bars = create_bars(&[bar_type], 10);
+        bars.extend(create_bars(&[other], 10));
+        let temp_dir = TempDir::new().unwrap();
+        let paths = BarParquetWriter::new(temp_dir.path().to_path_buf())
+            .write(&bars)
+            .unwrap();
+
+        assert_eq!(paths.len(), 2);
+        assert_eq!(BarParquetReader::read_file(&paths[1]).unwrap(), bars[10..]);
+    }
+
+    #[rstest]
+    fn test_write_encodes_bar_type_dir_name() {
+        let bar_type = BarType::from_str("ETH/USDT.BINANCE-1-MINUTE-LAST-EXTERNAL").unwrap();
+        let bars = create_bars(&[bar_type], 10);
+        let temp_dir = TempDir::new().unwrap();
+        let paths = BarParquetWriter::new(temp_dir.path().to_path_buf())
+            .write(&bars)
+            .unwrap();
+
+        let dir = temp_dir
+            .path()
+            .join("ETH%2FUSDT.BINANCE-1-MINUTE-LAST-EXTERNAL");
+        assert_eq!(paths, [dir.join("1970-01-01.parquet")]);
+        assert_eq!(BarParquetReader::read_file(&paths[0]).unwrap(), bars);
+    }
+
+    #[rstest]
+    fn test_read_filtered_other_bar_type_is_empty() {
+        let bar_type = BarType::from_str("AUDUSD.SIM-1-MINUTE-BID-EXTERNAL").unwrap();
+        let other = BarType::from_str("AUDUSD.SIM-5-MINUTE-BID-EXTERNAL").unwrap();
+        let bars = create_bars(&[bar_type], 10);
+        let temp_dir = TempDir::new().unwrap();
+        let paths = BarParquetWriter::new(temp_dir.path().to_path_buf())
+            .write(&bars)
+            .unwrap();
+        let filtered = BarParquetReader::read_filtered(&paths[0], &other, 0, u64::MAX).unwrap();
+
+        assert!(filtered.is_empty());
+    }
+}
@@ -0,0 +1,36 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+pub mod bar;
+
+use std::io;
+
+use datafusion::{arrow::error::ArrowError, parquet::errors::ParquetError};
+use nautilus_model::data::bar::BarBatchError;
+use thiserror;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ParquetStorageError {
+    #[error("Arrow error: {0}")]
+    ArrowError(#[from] ArrowError),
+    #[error("Parquet error: {0}")]
+    ParquetError(#[from] ParquetError),
+    #[error("I/O error: {0}")]
+    IoError(#[from] io::Error),
+    #[error("Missing schema metadata key '{0}'")]
+    MissingMetadata(String),
+    #[error("Invalid bar batch: {0}")]
+    InvalidBatch(#[from] BarBatchError),
+}
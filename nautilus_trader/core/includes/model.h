@@ -679,6 +679,10 @@ typedef struct BookOrder_t {
      * The order ID.
      */
     uint64_t order_id;
+    /**
+     * The UNIX timestamp (nanoseconds) when the order was last received by the book.
+     */
+    uint64_t ts_received;
 } BookOrder_t;
 
 /**
@@ -1814,6 +1818,16 @@ uint8_t orderbook_has_bid(struct OrderBook_API *book);
 
 uint8_t orderbook_has_ask(struct OrderBook_API *book);
 
+uintptr_t orderbook_bid_levels(const struct OrderBook_API *book);
+
+uintptr_t orderbook_ask_levels(const struct OrderBook_API *book);
+
+uint8_t orderbook_has_liquidity(const struct OrderBook_API *book);
+
+double orderbook_total_bid_qty(const struct OrderBook_API *book);
+
+double orderbook_total_ask_qty(const struct OrderBook_API *book);
+
 struct Price_t orderbook_best_bid_price(struct OrderBook_API *book);
 
 struct Price_t orderbook_best_ask_price(struct OrderBook_API *book);
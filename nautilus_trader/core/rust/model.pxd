@@ -374,6 +374,8 @@ cdef extern from "../includes/model.h":
         Quantity_t size;
         # The order ID.
         uint64_t order_id;
+        # The UNIX timestamp (nanoseconds) when the order was last received by the book.
+        uint64_t ts_received;
 
     # Represents a single change/delta in an order book.
     cdef struct OrderBookDelta_t:
@@ -1259,6 +1261,16 @@ cdef extern from "../includes/model.h":
 
     uint8_t orderbook_has_ask(OrderBook_API *book);
 
+    uintptr_t orderbook_bid_levels(const OrderBook_API *book);
+
+    uintptr_t orderbook_ask_levels(const OrderBook_API *book);
+
+    uint8_t orderbook_has_liquidity(const OrderBook_API *book);
+
+    double orderbook_total_bid_qty(const OrderBook_API *book);
+
+    double orderbook_total_ask_qty(const OrderBook_API *book);
+
     Price_t orderbook_best_bid_price(OrderBook_API *book);
 
     Price_t orderbook_best_ask_price(OrderBook_API *book);
@@ -184,6 +184,8 @@ cdef class BarSpecification:
             or aggregation == BarAggregation.VOLUME_IMBALANCE
             or aggregation == BarAggregation.VALUE
             or aggregation == BarAggregation.VALUE_IMBALANCE
+            or aggregation == BarAggregation.RANGE
+            or aggregation == BarAggregation.RENKO
         ):
             return True
         else:
@@ -424,6 +426,8 @@ cdef class BarSpecification:
         - ``VOLUME_IMBALANCE``
         - ``VALUE``
         - ``VALUE_IMBALANCE``
+        - ``RANGE``
+        - ``RENKO``
 
         Returns
         -------
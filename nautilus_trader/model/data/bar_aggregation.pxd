@@ -31,3 +31,5 @@ cpdef enum BarAggregation:
     DAY = 14
     WEEK = 15
     MONTH = 16
+    RANGE = 17
+    RENKO = 18
@@ -47,6 +47,11 @@ cdef class OrderBook(Data):
 
     cpdef list bids(self)
     cpdef list asks(self)
+    cpdef int bid_levels(self)
+    cpdef int ask_levels(self)
+    cpdef bint has_liquidity(self)
+    cpdef double total_bid_qty(self)
+    cpdef double total_ask_qty(self)
     cpdef best_bid_price(self)
     cpdef best_ask_price(self)
     cpdef best_bid_size(self)
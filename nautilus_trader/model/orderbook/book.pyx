@@ -41,11 +41,13 @@ from nautilus_trader.core.rust.model cimport level_price
 from nautilus_trader.core.rust.model cimport level_volume
 from nautilus_trader.core.rust.model cimport orderbook_add
 from nautilus_trader.core.rust.model cimport orderbook_apply_delta
+from nautilus_trader.core.rust.model cimport orderbook_ask_levels
 from nautilus_trader.core.rust.model cimport orderbook_asks
 from nautilus_trader.core.rust.model cimport orderbook_best_ask_price
 from nautilus_trader.core.rust.model cimport orderbook_best_ask_size
 from nautilus_trader.core.rust.model cimport orderbook_best_bid_price
 from nautilus_trader.core.rust.model cimport orderbook_best_bid_size
+from nautilus_trader.core.rust.model cimport orderbook_bid_levels
 from nautilus_trader.core.rust.model cimport orderbook_bids
 from nautilus_trader.core.rust.model cimport orderbook_book_type
 from nautilus_trader.core.rust.model cimport orderbook_check_integrity
@@ -57,6 +59,7 @@ from nautilus_trader.core.rust.model cimport orderbook_delete
 from nautilus_trader.core.rust.model cimport orderbook_get_avg_px_for_quantity
 from nautilus_trader.core.rust.model cimport orderbook_has_ask
 from nautilus_trader.core.rust.model cimport orderbook_has_bid
+from nautilus_trader.core.rust.model cimport orderbook_has_liquidity
 from nautilus_trader.core.rust.model cimport orderbook_instrument_id
 from nautilus_trader.core.rust.model cimport orderbook_midpoint
 from nautilus_trader.core.rust.model cimport orderbook_new
@@ -65,6 +68,8 @@ from nautilus_trader.core.rust.model cimport orderbook_reset
 from nautilus_trader.core.rust.model cimport orderbook_sequence
 from nautilus_trader.core.rust.model cimport orderbook_simulate_fills
 from nautilus_trader.core.rust.model cimport orderbook_spread
+from nautilus_trader.core.rust.model cimport orderbook_total_ask_qty
+from nautilus_trader.core.rust.model cimport orderbook_total_bid_qty
 from nautilus_trader.core.rust.model cimport orderbook_ts_last
 from nautilus_trader.core.rust.model cimport orderbook_update
 from nautilus_trader.core.rust.model cimport orderbook_update_quote_tick
@@ -401,6 +406,65 @@ cdef class OrderBook(Data):
 
         return levels
 
+    cpdef int bid_levels(self):
+        """
+        Return the number of bid price levels in the book.
+
+        Returns
+        -------
+        int
+
+        """
+        return orderbook_bid_levels(&self._mem)
+
+    cpdef int ask_levels(self):
+        """
+        Return the number of ask price levels in the book.
+
+        Returns
+        -------
+        int
+
+        """
+        return orderbook_ask_levels(&self._mem)
+
+    cpdef bint has_liquidity(self):
+        """
+        Return whether the book has orders on both the bid and ask sides.
+
+        Returns
+        -------
+        bool
+
+        """
+        return orderbook_has_liquidity(&self._mem)
+
+    cpdef double total_bid_qty(self):
+        """
+        Return the total quantity of all bid levels in the book.
+
+        This is computed in O(n) of the bid orders.
+
+        Returns
+        -------
+        double
+
+        """
+        return orderbook_total_bid_qty(&self._mem)
+
+    cpdef double total_ask_qty(self):
+        """
+        Return the total quantity of all ask levels in the book.
+
+        This is computed in O(n) of the ask orders.
+
+        Returns
+        -------
+        double
+
+        """
+        return orderbook_total_ask_qty(&self._mem)
+
     cpdef best_bid_price(self):
         """
         Return the best bid price in the book (if no bids then returns ``None``).
@@ -0,0 +1,75 @@
+# -------------------------------------------------------------------------------------------------
+#  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+#  https://nautechsystems.io
+#
+#  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+#  You may not use this file except in compliance with the License.
+#  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+#
+#  Unless required by applicable law or agreed to in writing, software
+#  distributed under the License is distributed on an "AS IS" BASIS,
+#  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+#  See the License for the specific language governing permissions and
+#  limitations under the License.
+# -------------------------------------------------------------------------------------------------
+
+import pandas as pd
+
+from nautilus_trader.core.nautilus_pyo3.model import Bar
+from nautilus_trader.core.nautilus_pyo3.model import BarType
+from nautilus_trader.core.nautilus_pyo3.model import Price
+from nautilus_trader.core.nautilus_pyo3.model import Quantity
+from nautilus_trader.test_kit.performance import PerformanceHarness
+
+
+BAR_TYPE = BarType.from_str("AUDUSD.SIM-1-MINUTE-BID-EXTERNAL")
+
+BARS = [
+    Bar(
+        BAR_TYPE,
+        Price(1.00001, 5),
+        Price(1.00004, 5),
+        Price(1.00000, 5),
+        Price(1.00003, 5),
+        Quantity(100_000, 0),
+        i,
+        i,
+    )
+    for i in range(100_000)
+]
+
+
+def _naive_to_dataframe(bars: list[Bar]) -> pd.DataFrame:
+    return pd.DataFrame(
+        [
+            {
+                "open_raw": bar.open.raw,
+                "high_raw": bar.high.raw,
+                "low_raw": bar.low.raw,
+                "close_raw": bar.close.raw,
+                "volume_raw": bar.volume.raw,
+                "ts_event": bar.ts_event,
+                "ts_init": bar.ts_init,
+                "bar_type": bar.bar_type,
+            }
+            for bar in bars
+        ],
+    )
+
+
+class TestBarDataFramePerformance(PerformanceHarness):
+    def test_bar_to_dataframe(self):
+        self.benchmark.pedantic(
+            target=Bar.to_dataframe,
+            args=(BARS,),
+            iterations=1,
+            rounds=10,
+        )
+
+    def test_bar_to_dataframe_naive_loop(self):
+        self.benchmark.pedantic(
+            target=_naive_to_dataframe,
+            args=(BARS,),
+            iterations=1,
+            rounds=10,
+        )
@@ -221,6 +221,18 @@ class TestBarSpecification:
                 False,
                 True,
             ],
+            [
+                BarSpecification(10, BarAggregation.RANGE, PriceType.LAST),
+                False,
+                True,
+                False,
+            ],
+            [
+                BarSpecification(5, BarAggregation.RENKO, PriceType.LAST),
+                False,
+                True,
+                False,
+            ],
         ],
     )
     def test_aggregation_queries(
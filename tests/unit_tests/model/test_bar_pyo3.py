@@ -0,0 +1,256 @@
+# -------------------------------------------------------------------------------------------------
+#  Copyright (C) 2015-2023 Nautech Systems Pty Ltd. All rights reserved.
+#  https://nautechsystems.io
+#
+#  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+#  You may not use this file except in compliance with the License.
+#  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+#
+#  Unless required by applicable law or agreed to in writing, software
+#  distributed under the License is distributed on an "AS IS" BASIS,
+#  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+#  See the License for the specific language governing permissions and
+#  limitations under the License.
+# -------------------------------------------------------------------------------------------------
+
+import copy
+import pickle
+
+import pytest
+
+from nautilus_trader.core.nautilus_pyo3.model import Bar
+from nautilus_trader.core.nautilus_pyo3.model import BarType
+from nautilus_trader.core.nautilus_pyo3.model import Price
+from nautilus_trader.core.nautilus_pyo3.model import Quantity
+
+
+BAR_TYPE = BarType.from_str("AUDUSD.SIM-1-MINUTE-BID-EXTERNAL")
+
+
+def _make_bar() -> Bar:
+    return Bar(
+        BAR_TYPE,
+        Price(1.00001, 5),
+        Price(1.00004, 5),
+        Price(1.00002, 5),
+        Price(1.00003, 5),
+        Quantity(100_000, 0),
+        0,
+        1,
+    )
+
+
+class TestBarType:
+    def test_copy(self):
+        # Arrange, Act, Assert
+        assert copy.copy(BAR_TYPE) == BAR_TYPE
+
+    def test_deepcopy(self):
+        # Arrange, Act, Assert
+        assert copy.deepcopy(BAR_TYPE) == BAR_TYPE
+
+    def test_pickle_dumps_and_loads(self):
+        # Arrange
+        pickled = pickle.dumps(BAR_TYPE)
+
+        # Act
+        unpickled = pickle.loads(pickled)  # noqa S301 (pickle is safe here)
+
+        # Assert
+        assert unpickled == BAR_TYPE
+
+    def test_display_label(self):
+        # Arrange, Act, Assert
+        assert BAR_TYPE.display_label == "AUDUSD 1 Min Bid"
+
+    def test_from_parts(self):
+        # Arrange, Act
+        bar_type = BarType.from_parts("AUDUSD", "SIM", 1, "MINUTE", "BID", "EXTERNAL")
+
+        # Assert
+        assert bar_type == BAR_TYPE
+
+    def test_from_parts_with_invalid_part_raises_value_error(self):
+        # Arrange, Act, Assert
+        with pytest.raises(ValueError, match="'INVALID' at position 2"):
+            BarType.from_parts("AUDUSD", "SIM", 1, "INVALID", "BID", "EXTERNAL")
+
+
+class TestBar:
+    def test_copy(self):
+        # Arrange
+        bar = _make_bar()
+
+        # Act, Assert
+        assert copy.copy(bar) == bar
+
+    def test_deepcopy(self):
+        # Arrange
+        bar = _make_bar()
+
+        # Act, Assert
+        assert copy.deepcopy(bar) == bar
+
+    def test_pickle_dumps_and_loads(self):
+        # Arrange
+        bar = _make_bar()
+
+        # Act
+        pickled = pickle.dumps(bar)
+        unpickled = pickle.loads(pickled)  # noqa S301 (pickle is safe here)
+
+        # Assert
+        assert unpickled == bar
+
+    def test_as_ohlcv_tuple_unpacking(self):
+        # Arrange
+        bar = _make_bar()
+
+        # Act
+        o, h, l, c, v = bar.as_ohlcv_tuple()  # noqa: E741 (ambiguous variable name)
+
+        # Assert
+        assert o == bar.open
+        assert h == bar.high
+        assert l == bar.low
+        assert c == bar.close
+        assert v == bar.volume
+
+    def test_candle_properties(self):
+        # Arrange
+        bar = Bar(
+            BAR_TYPE,
+            Price(1.00010, 5),
+            Price(1.00050, 5),
+            Price(1.00000, 5),
+            Price(1.00040, 5),
+            Quantity(100_000, 0),
+            0,
+            1,
+        )
+
+        # Act, Assert
+        assert bar.is_bullish
+        assert not bar.is_bearish
+        assert not bar.is_doji(10)
+        assert bar.is_doji(30)
+        assert bar.body == Price(0.00030, 5)
+        assert bar.upper_shadow == Price(0.00010, 5)
+        assert bar.lower_shadow == Price(0.00010, 5)
+
+    def test_flat_bar_is_doji(self):
+        # Arrange
+        bar = Bar(
+            BAR_TYPE,
+            Price(1.00010, 5),
+            Price(1.00020, 5),
+            Price(1.00000, 5),
+            Price(1.00010, 5),
+            Quantity(100_000, 0),
+            0,
+            1,
+        )
+
+        # Act, Assert
+        assert bar.is_doji(0)
+        assert not bar.is_bullish
+        assert not bar.is_bearish
+
+    def test_scan_patterns(self):
+        # Arrange
+        def make_bar(open_: float, high: float, low: float, close: float) -> Bar:
+            return Bar(
+                BAR_TYPE,
+                Price(open_, 4),
+                Price(high, 4),
+                Price(low, 4),
+                Price(close, 4),
+                Quantity(100_000, 0),
+                0,
+                1,
+            )
+
+        bars = [
+            make_bar(1.0050, 1.0052, 1.0018, 1.0020),
+            make_bar(1.0015, 1.0018, 1.0008, 1.0012),
+            make_bar(1.0010, 1.0048, 1.0008, 1.0045),
+        ]
+
+        # Act
+        patterns = Bar.scan_patterns(bars)
+
+        # Assert
+        assert patterns == [(2, "BULLISH_ENGULFING"), (2, "MORNING_STAR")]
+
+    def test_as_named_tuple(self):
+        # Arrange
+        bar = _make_bar()
+
+        # Act
+        ohlcv = bar.as_named_tuple()
+
+        # Assert
+        assert type(ohlcv).__name__ == "OHLCV"
+        assert ohlcv._fields == ("open", "high", "low", "close", "volume")
+        assert ohlcv.open == bar.open
+        assert ohlcv.high == bar.high
+        assert ohlcv.low == bar.low
+        assert ohlcv.close == bar.close
+        assert ohlcv.volume == bar.volume
+        assert tuple(ohlcv) == bar.as_ohlcv_tuple()
+
+    def test_to_dataframe(self):
+        # Arrange
+        bars = [_make_bar(), _make_bar()]
+
+        # Act
+        df = Bar.to_dataframe(bars)
+
+        # Assert
+        assert list(df.columns) == [
+            "open_raw",
+            "high_raw",
+            "low_raw",
+            "close_raw",
+            "volume_raw",
+            "ts_event",
+            "ts_init",
+            "bar_type",
+        ]
+        assert len(df) == 2
+        assert all(df[column].dtype == "int64" for column in df.columns[:-1])
+        assert df["open_raw"][0] == bars[0].open.raw
+        assert df["volume_raw"][0] == bars[0].volume.raw
+        assert df["ts_init"][1] == 1
+        assert df["bar_type"][0] == BAR_TYPE
+
+    def test_to_dataframe_with_volume_overflowing_int64_raises(self):
+        # Arrange
+        bar = _make_bar()
+        bars = [
+            Bar(
+                bar.bar_type,
+                bar.open,
+                bar.high,
+                bar.low,
+                bar.close,
+                Quantity(10_000_000_000, 0),
+                bar.ts_event,
+                bar.ts_init,
+            ),
+        ]
+
+        # Act, Assert
+        with pytest.raises(
+            ValueError,
+            match="'volume_raw' value 10000000000000000000 overflows int64",
+        ):
+            Bar.to_dataframe(bars)
+
+    def test_to_dataframe_with_no_bars(self):
+        # Arrange, Act
+        df = Bar.to_dataframe([])
+
+        # Assert
+        assert len(df) == 0
+        assert df["close_raw"].dtype == "int64"
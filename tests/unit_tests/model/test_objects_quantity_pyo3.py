@@ -880,6 +880,30 @@ class TestQuantity:
         assert str(quantity) == "2100.166667"
         assert repr(quantity) == "Quantity('2100.166667')"
 
+    @pytest.mark.parametrize(
+        ("value", "expected"),
+        [
+            ["999", "999"],
+            ["50000", "50.0K"],
+            ["1500000", "1.50M"],
+            ["1000000000", "1.00B"],
+        ],
+    )
+    def test_format_human(self, value, expected):
+        # Arrange
+        quantity = Quantity.from_str(value)
+
+        # Act, Assert
+        assert f"{quantity:h}" == expected
+
+    def test_format_with_decimal_spec(self):
+        # Arrange
+        quantity = Quantity(2100.1666666, 6)
+
+        # Act, Assert
+        assert f"{quantity}" == "2100.166667"
+        assert f"{quantity:.2f}" == "2100.17"
+
     def test_pickle_dumps_and_loads(self):
         # Arrange
         quantity = Quantity(1.2000, 2)
@@ -166,6 +166,79 @@ class TestOrderBook:
         assert book.best_bid_size() is None
         assert book.best_ask_size() is None
 
+    def test_level_counts_and_liquidity_with_no_orders(self):
+        # Arrange
+        book = OrderBook(
+            instrument_id=self.instrument.id,
+            book_type=BookType.L2_MBP,
+        )
+
+        # Act, Assert
+        assert book.bid_levels() == 0
+        assert book.ask_levels() == 0
+        assert not book.has_liquidity()
+        assert book.total_bid_qty() == 0.0
+        assert book.total_ask_qty() == 0.0
+
+    def test_level_counts_and_liquidity_with_one_sided_book(self):
+        # Arrange
+        book = OrderBook(
+            instrument_id=self.instrument.id,
+            book_type=BookType.L2_MBP,
+        )
+        for i, price in enumerate([10.0, 9.0]):
+            book.add(
+                BookOrder(
+                    price=Price(price, 1),
+                    size=Quantity(5.0, 0),
+                    side=OrderSide.BUY,
+                    order_id=i,
+                ),
+                0,
+                i,
+            )
+
+        # Act, Assert
+        assert book.bid_levels() == 2
+        assert book.ask_levels() == 0
+        assert not book.has_liquidity()
+        assert book.total_bid_qty() == 10.0
+        assert book.total_ask_qty() == 0.0
+
+    def test_level_counts_and_liquidity_with_two_sided_book(self):
+        # Arrange
+        book = OrderBook(
+            instrument_id=self.instrument.id,
+            book_type=BookType.L2_MBP,
+        )
+        book.add(
+            BookOrder(
+                price=Price(10.0, 1),
+                size=Quantity(5.0, 0),
+                side=OrderSide.BUY,
+                order_id=0,
+            ),
+            0,
+            0,
+        )
+        book.add(
+            BookOrder(
+                price=Price(11.0, 1),
+                size=Quantity(6.0, 0),
+                side=OrderSide.SELL,
+                order_id=1,
+            ),
+            1,
+            1,
+        )
+
+        # Act, Assert
+        assert book.bid_levels() == 1
+        assert book.ask_levels() == 1
+        assert book.has_liquidity()
+        assert book.total_bid_qty() == 5.0
+        assert book.total_ask_qty() == 6.0
+
     def test_spread_with_no_orders_returns_none(self):
         # Arrange
         book = OrderBook(